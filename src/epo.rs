@@ -1,8 +1,26 @@
 pub mod app;
 pub mod arg;
+pub mod command;
 pub mod date;
+pub mod doctor;
+pub mod geo;
 pub mod help;
+pub mod json;
+pub mod layout;
+pub mod link;
+pub mod lookup;
+pub mod metazone;
+pub mod offset_diff;
+pub mod plan;
+pub mod posix;
 pub mod print;
+pub mod rules;
 pub mod script;
+pub mod transition;
 pub mod types;
 pub mod tz;
+pub mod tzif;
+pub mod tzinfo;
+pub mod windows;
+pub mod zic;
+pub mod zone;
@@ -1,7 +1,14 @@
-use super::types::{PrintMode, Settings};
-use super::{date, help, print};
+use super::types::{PrintMode, Settings, Table};
+use super::{date, help, json, layout, print};
 
 pub fn run(settings: &Settings) {
+    let is_json = matches!(settings.print_mode, PrintMode::Json | PrintMode::Ndjson);
+    if !is_json {
+        for warning in &settings.warnings {
+            eprintln!("Warning: {}", warning);
+        }
+    }
+
     if settings.help || settings.version {
         if settings.version {
             println!("{}", help::version());
@@ -12,43 +19,98 @@ pub fn run(settings: &Settings) {
         return;
     }
 
-    if !settings.epochs.is_empty() {
-        let (header, data) = print::to_string_rows_from_epochs(&settings.epochs, &settings.timezones);
-        print(&header, &data, &settings.print_mode);
+    // Warnings are part of the JSON output
+    if is_json {
+        json::print(settings);
+        return;
     }
 
-    if !settings.dates.is_empty() {
-        if !settings.epochs.is_empty() {
+    let mut tables: Vec<Table> = Vec::new();
+    let mut streamed = false;
+    // Plain text is not aligned, so it is not laid out for the terminal
+    let width = match settings.print_mode {
+        PrintMode::PlainText => None,
+        _ => layout::terminal_width(),
+    };
+    if settings.output.unified {
+        let table = print::to_string_rows_from_inputs(
+            &settings.inputs,
+            &settings.epochs,
+            &settings.dates,
+            &settings.timezones,
+            settings.local_zone,
+            &settings.output,
+        );
+        // Input and Kind come before the columns of the epoch
+        let (fixed, group) = print::column_groups(&settings.output);
+        tables.extend(layout::arrange(table, fixed + 2, group, settings.output.layout, width));
+    } else if settings.epochs.iter().any(|e| !e.epochs.is_empty()) {
+        // The epochs of ranges are made as they are printed
+        let epochs = || settings.epochs.iter().flat_map(|e| e.epochs.iter());
+        let (header, mut rows) = print::epoch_rows(epochs(), &settings.timezones, &settings.output);
+        streamed = print_stream(&header, &mut rows, settings, width);
+        if !streamed {
+            let table = print::to_string_rows_from_epochs(epochs(), &settings.timezones, &settings.output);
+            let (fixed, group) = print::column_groups(&settings.output);
+            tables.extend(layout::arrange(table, fixed, group, settings.output.layout, width));
+        }
+    }
+    if !settings.dates.is_empty() && !settings.output.unified {
+        let table = print::to_string_rows_from_dates(&settings.dates, &settings.timezones, &settings.output);
+        tables.extend(layout::arrange(table, 1, 1, settings.output.layout, width));
+    }
+
+    for (i, (header, data)) in tables.iter().enumerate() {
+        if i > 0 || streamed {
             println!();
         }
-        let (header, data) = print::to_string_rows_from_dates(&settings.dates, &settings.timezones);
-        print(&header, &data, &settings.print_mode);
+        print(header, data, &settings.print_mode);
+    }
+}
+
+/// Prints the rows as they are made when the table needs no measuring, so that millions of epochs do not have to fit in memory.
+/// Returns false without printing otherwise.
+fn print_stream(header: &[String], rows: &mut print::EpochRows, settings: &Settings, width: Option<usize>) -> bool {
+    if let PrintMode::PlainText = settings.print_mode {
+        print::print_stream_as_plaintext(rows, " ");
+        return true;
+    }
+    match print::fixed_column_widths(&settings.epochs, header, &settings.output, &settings.print_mode) {
+        Some(max_lengths) if layout::is_single_table(&max_lengths, settings.output.layout, width) => {
+            print::print_table_stream(header, rows, &max_lengths, &settings.print_mode);
+            true
+        }
+        _ => false,
     }
 }
 
 pub fn print(header: &[String], data: &[Vec<String>], mode: &PrintMode) {
     match mode {
-        PrintMode::Markdown => print::print_markdown_table(header, data),
         PrintMode::PlainText => print::print_as_plaintext(data, " "),
+        PrintMode::Json | PrintMode::Ndjson => json::print_table(header, data, mode),
+        _ => print::print_table(header, data, mode),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::super::date;
-    use super::super::types::{PrintMode, Settings, TimeMode, Zone};
+    use super::super::types::{EpochArg, OutputOptions, PrintMode, Settings, Zone};
     use super::*;
 
     #[test]
     fn test_run_empty() {
         let settings = Settings {
             timezones: vec![],
+            local_zone: 0,
             epochs: vec![],
             dates: vec![],
-            time_mode: TimeMode::Seconds,
+            inputs: vec![],
             print_mode: PrintMode::Markdown,
+            output: OutputOptions::default(),
             help: false,
             version: false,
+            warnings: vec![],
         };
         run(&settings);
     }
@@ -61,23 +123,18 @@ mod tests {
                 Zone::Offset(0),
                 Zone::Offset(-3600 * 5), //
             ],
-            epochs: vec![
-                date::EpochInfo {
-                    epoch_sec: 0,
-                    offset_sec: 0,
-                    datestr: "".to_string(),
-                },
-                date::EpochInfo {
-                    epoch_sec: 1651306548,
-                    offset_sec: 0,
-                    datestr: "".to_string(),
-                },
-            ],
+            local_zone: 0,
+            epochs: vec![EpochArg {
+                epochs: date::Epochs::List(vec![0, 1651306548]),
+                datestr: "[0,1651306548]".to_string(),
+            }],
             dates: vec![],
-            time_mode: TimeMode::Seconds,
+            inputs: vec![],
             print_mode: PrintMode::Markdown,
+            output: OutputOptions::default(),
             help: false,
             version: false,
+            warnings: vec![],
         };
         run(&settings);
     }
@@ -91,23 +148,24 @@ mod tests {
                 Zone::Offset(-3600 * 5),
                 //
             ],
+            local_zone: 0,
             epochs: vec![
-                date::EpochInfo {
-                    epoch_sec: 0,
-                    offset_sec: 0,
-                    datestr: "".to_string(),
+                EpochArg {
+                    epochs: date::Epochs::List(vec![0, 1651306548]),
+                    datestr: "[0,1651306548]".to_string(),
                 },
-                date::EpochInfo {
-                    epoch_sec: 1651306548,
-                    offset_sec: 0,
-                    datestr: "".to_string(),
+                EpochArg {
+                    epochs: date::Epochs::Range(1651306548, 1651306538),
+                    datestr: "range(1651306548, 1651306538)".to_string(),
                 },
             ],
             dates: vec![],
-            time_mode: TimeMode::Seconds,
+            inputs: vec![],
             print_mode: PrintMode::PlainText,
+            output: OutputOptions::default(),
             help: false,
             version: false,
+            warnings: vec![],
         };
         run(&settings);
     }
@@ -116,12 +174,15 @@ mod tests {
     fn test_run_help() {
         let settings = Settings {
             timezones: vec![],
+            local_zone: 0,
             epochs: vec![],
             dates: vec![],
-            time_mode: TimeMode::Seconds,
+            inputs: vec![],
             print_mode: PrintMode::Markdown,
+            output: OutputOptions::default(),
             help: true,
             version: false,
+            warnings: vec![],
         };
         run(&settings);
     }
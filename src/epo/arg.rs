@@ -1,21 +1,42 @@
-use super::types::{PrintMode, Settings, TimeMode, Zone};
-use super::{date, script, tz};
+use super::posix::PosixTz;
+use super::rules::ZoneRules;
+use super::types::{Column, EpochArg, Input, Layout, OutputOptions, PrintMode, Settings, TimeMode, Zone};
+use super::{date, geo, link, print, script, tz, tzif, windows, zic};
 use chrono_tz::Tz;
 use std::collections::HashSet;
+use std::env;
+use std::io::{stdout, IsTerminal};
+use std::path::{Path, PathBuf};
+
+/// More threads than this only add overhead, and a chunk for each would not fit in memory.
+const MAX_JOBS: usize = 256;
 
 enum ParseArgResult {
     EpochInfo(date::EpochInfo),
-    Epochs(Vec<i64>),
+    Epochs(date::Epochs),
     DateInfo(date::DateInfo),
     UtcOffset(i32),
     Tzname(String),
+    Posix(PosixTz),
     Error(String),
     TimeMode(TimeMode),
     PrintMode(PrintMode),
+    Abbreviation(bool),
+    LongName(bool),
+    WindowsId(bool),
+    Unified(bool),
     Help(bool),
     Version(bool),
 }
 
+/// Where zone rules are read from at runtime instead of the data compiled into epo.
+pub enum ZoneinfoOption {
+    /// Zone names are looked up in the directory (e.g. /usr/share/zoneinfo)
+    Dir(PathBuf),
+    /// The TZif file is added as a zone
+    File(PathBuf),
+}
+
 pub fn get_parse_settings() -> date::ParseSettings {
     let date_formats_10: Vec<String> = vec![
         "%Y-%m-%d".to_string(),
@@ -61,26 +82,183 @@ pub fn parse_arguments(args: &[String], parse_settings: &date::ParseSettings) ->
     }
 
     let mut all_timezones: Vec<Zone> = Vec::new();
-    let mut epochs: Vec<date::EpochInfo> = Vec::new();
+    // Indexes of the offsets of dates with an offset, which are columns the user did not name
+    let mut implied_zones: Vec<usize> = Vec::new();
+    let mut epochs: Vec<EpochArg> = Vec::new();
     let mut dates: Vec<date::DateInfo> = Vec::new();
+    let mut inputs: Vec<Input> = Vec::new();
     let mut errors: Vec<String> = Vec::new();
     let mut time_mode = TimeMode::Seconds;
     let mut print_mode = PrintMode::Markdown;
+    let mut output = OutputOptions::default();
+    let mut color: Option<bool> = None;
     let mut help = false;
     let mut version = false;
 
-    for arg in args.iter().skip(1) {
-        match parse_arg_value(arg, parse_settings) {
+    let zoneinfo = match parse_zoneinfo_option(args) {
+        Ok(zoneinfo) => zoneinfo,
+        Err(error) => return Err(vec![error]),
+    };
+    let zoneinfo_dir = match &zoneinfo {
+        Some(ZoneinfoOption::Dir(dir)) => Some(dir.as_path()),
+        _ => None,
+    };
+    if let Some(ZoneinfoOption::File(path)) = &zoneinfo {
+        match tzif::load_file(path) {
+            Ok(rules) => all_timezones.push(Zone::Rules(rules)),
+            Err(error) => errors.push(error),
+        }
+    }
+    let custom_zones = parse_zic_option(args)?;
+    let show_alias = args.iter().any(|arg| arg == "--alias");
+    let territory = args.iter().position(|arg| arg == "--territory").and_then(|i| args.get(i + 1));
+    match territory {
+        Some(t) if !windows::is_territory(t) => errors.push(format!("Invalid territory: {}", t)),
+        None if args.iter().any(|arg| arg == "--territory") => errors.push("--territory requires a value".to_string()),
+        _ => {}
+    }
+    let mut warnings: Vec<String> = Vec::new();
+    if let Some(i) = args.iter().position(|arg| arg == "--format") {
+        match args.get(i + 1).map(|format_str| date::parse_format_str(format_str)) {
+            Some(Ok(format)) => output.format = Some(format),
+            Some(Err(error)) => errors.push(error),
+            None => errors.push("--format requires a value".to_string()),
+        }
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--columns") {
+        match args.get(i + 1).map(|columns_str| parse_columns(columns_str)) {
+            Some(Ok(columns)) => output.columns = columns,
+            Some(Err(error)) => errors.push(error),
+            None => errors.push("--columns requires a value".to_string()),
+        }
+    }
+
+    let mut iter = args.iter().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--zoneinfo" | "--zic" | "--territory" | "--format" | "--columns" => {
+                iter.next();
+                continue;
+            }
+            "--system-tz" | "--alias" => continue,
+            "--style" => {
+                match iter.next().map(|style_str| parse_style(style_str)) {
+                    Some(Ok(new_print_mode)) => print_mode = new_print_mode,
+                    Some(Err(error)) => errors.push(error),
+                    None => errors.push("--style requires a value".to_string()),
+                }
+                continue;
+            }
+            "--layout" => {
+                match iter.next().map(|layout_str| parse_layout(layout_str)) {
+                    Some(Ok(layout)) => output.layout = layout,
+                    Some(Err(error)) => errors.push(error),
+                    None => errors.push("--layout requires a value".to_string()),
+                }
+                continue;
+            }
+            "--ref" => {
+                match iter.next().map(|reference_str| parse_reference(reference_str, parse_settings)) {
+                    Some(Ok(reference)) => output.reference = Some(reference),
+                    Some(Err(error)) => errors.push(error),
+                    None => errors.push("--ref requires a value".to_string()),
+                }
+                continue;
+            }
+            "--jobs" => {
+                match iter.next().map(|jobs_str| parse_jobs(jobs_str)) {
+                    Some(Ok(jobs)) => output.jobs = jobs,
+                    Some(Err(error)) => errors.push(error),
+                    None => errors.push("--jobs requires a value".to_string()),
+                }
+                continue;
+            }
+            "--color" => {
+                match iter.next().map(|when| parse_color(when)) {
+                    Some(Ok(new_color)) => color = new_color,
+                    Some(Err(error)) => errors.push(error),
+                    None => errors.push("--color requires a value".to_string()),
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        if let Some(rules) = find_custom_zone(&custom_zones, arg) {
+            all_timezones.push(Zone::Rules(rules));
+            continue;
+        }
+        if let Some(dir) = zoneinfo_dir {
+            if let Ok(rules) = tzif::load_from_dir(dir, arg) {
+                all_timezones.push(Zone::Rules(rules));
+                continue;
+            }
+        }
+
+        // Format of a single column (e.g. tokyo=rfc2822)
+        if let Some((zone_str, format_str)) = arg.split_once('=') {
+            if let Ok(zone) = parse_zone(zone_str, zoneinfo_dir, &custom_zones) {
+                match date::parse_format_str(format_str) {
+                    Ok(format) => {
+                        output.zone_formats.insert(print::zone_header(&zone), format);
+                        all_timezones.push(zone);
+                    }
+                    Err(error) => errors.push(error),
+                }
+                continue;
+            }
+        }
+
+        // Windows IDs shared by several zones resolve to the zone of the territory
+        let result = match territory.and_then(|t| windows::to_iana(arg, Some(t))) {
+            Some(iana) => ParseArgResult::Tzname(iana.to_string()),
+            None => parse_arg_value(arg, parse_settings),
+        };
+        match result {
             ParseArgResult::UtcOffset(offset_secs) => all_timezones.push(Zone::Offset(offset_secs)),
             ParseArgResult::EpochInfo(epoch_info) => {
+                implied_zones.push(all_timezones.len());
                 all_timezones.push(Zone::Offset(epoch_info.offset_sec));
-                epochs.push(epoch_info);
+                inputs.push(Input::DateWithOffset(epochs.len()));
+                epochs.push(EpochArg {
+                    epochs: date::Epochs::List(vec![epoch_info.epoch_sec]),
+                    datestr: epoch_info.datestr,
+                });
+            }
+            ParseArgResult::Tzname(tzname) => {
+                // Nautical zones found for geo: coordinates are not typed by the user
+                let inverted_offset = link::inverted_etc_offset(&tzname).filter(|_| !arg.starts_with("geo:"));
+                if let Some(offset_sec) = inverted_offset {
+                    warnings.push(inverted_etc_warning(&tzname, offset_sec));
+                }
+                let tzname = if show_alias {
+                    tzname
+                } else {
+                    link::canonical_name(&tzname).to_string()
+                };
+                match to_zone(tzname, zoneinfo_dir, &custom_zones) {
+                    Ok(zone) => all_timezones.push(zone),
+                    Err(error) => errors.push(error),
+                }
+            }
+            ParseArgResult::Posix(posix_tz) => all_timezones.push(Zone::Posix(posix_tz)),
+            ParseArgResult::Epochs(script_epochs) => {
+                inputs.push(Input::Epoch(epochs.len()));
+                epochs.push(EpochArg {
+                    epochs: script_epochs,
+                    datestr: arg.to_string(),
+                });
+            }
+            ParseArgResult::DateInfo(date_info) => {
+                inputs.push(Input::Date(dates.len()));
+                dates.push(date_info);
             }
-            ParseArgResult::Tzname(tzname) => all_timezones.push(Zone::Tzname(tzname)),
-            ParseArgResult::Epochs(epoch) => epochs.extend(make_epoch_infos(epoch, arg)),
-            ParseArgResult::DateInfo(date_info) => dates.push(date_info),
             ParseArgResult::TimeMode(new_time_mode) => time_mode = new_time_mode,
             ParseArgResult::PrintMode(new_print_mode) => print_mode = new_print_mode,
+            ParseArgResult::Abbreviation(abbreviation) => output.abbreviation = abbreviation,
+            ParseArgResult::LongName(long_name) => output.long_name = long_name,
+            ParseArgResult::WindowsId(windows_id) => output.windows_id = windows_id,
+            ParseArgResult::Unified(unified) => output.unified = unified,
             ParseArgResult::Help(new_help) => help = new_help,
             ParseArgResult::Version(new_version) => version = new_version,
             ParseArgResult::Error(error) => errors.push(error),
@@ -91,39 +269,51 @@ pub fn parse_arguments(args: &[String], parse_settings: &date::ParseSettings) ->
         return Err(errors);
     }
 
+    // Only the tables for the terminal are colored
+    output.color = matches!(print_mode, PrintMode::Markdown | PrintMode::PlainText) && color.unwrap_or_else(color_by_default);
+
+    if let TimeMode::Milliseconds = time_mode {
+        for input in &inputs {
+            if let Input::Epoch(i) = *input {
+                epochs[i].epochs = epochs[i].epochs.millis_to_secs();
+            }
+        }
+    }
+
     if all_timezones.is_empty() {
         all_timezones.push(Zone::Offset(date::current_date_info().offset_sec));
     }
+    let named_zone = (0..all_timezones.len())
+        .find(|i| !implied_zones.contains(i))
+        .map(|i| identity(&all_timezones[i]));
     let timezones = unique(all_timezones);
+    let local_zone = named_zone
+        .and_then(|named_zone| timezones.iter().position(|zone| identity(zone) == named_zone))
+        .unwrap_or(0);
 
     if epochs.is_empty() && dates.is_empty() {
-        epochs.push(date::current_date_info());
+        let now = date::current_date_info();
+        epochs.push(EpochArg {
+            epochs: date::Epochs::List(vec![now.epoch_sec]),
+            datestr: now.datestr,
+        });
+        inputs.push(Input::Epoch(0));
     }
 
     Ok(Settings {
         epochs,
         dates,
+        inputs,
         timezones,
-        time_mode,
+        local_zone,
         print_mode,
+        output,
         help,
         version,
+        warnings,
     })
 }
 
-fn make_epoch_infos(epochs: Vec<i64>, datestr: &str) -> Vec<date::EpochInfo> {
-    let mut result: Vec<date::EpochInfo> = Vec::new();
-    let offset_sec = date::get_utc_offset_sec();
-    for epoch_sec in epochs {
-        result.push(date::EpochInfo {
-            epoch_sec,
-            offset_sec,
-            datestr: datestr.to_string(),
-        });
-    }
-    result
-}
-
 fn parse_arg_value(arg: &str, parse_settings: &date::ParseSettings) -> ParseArgResult {
     if arg.len() >= 2 && (arg.starts_with('+') || arg.starts_with('-')) {
         if let Ok(offset_sec) = date::parse_offset_str(arg) {
@@ -134,11 +324,25 @@ fn parse_arg_value(arg: &str, parse_settings: &date::ParseSettings) -> ParseArgR
     match arg {
         "-m" => return ParseArgResult::TimeMode(TimeMode::Milliseconds),
         "-p" => return ParseArgResult::PrintMode(PrintMode::PlainText),
+        "--json" => return ParseArgResult::PrintMode(PrintMode::Json),
+        "--ndjson" => return ParseArgResult::PrintMode(PrintMode::Ndjson),
+        "--abbr" => return ParseArgResult::Abbreviation(true),
+        "--long-name" => return ParseArgResult::LongName(true),
+        "--windows-id" => return ParseArgResult::WindowsId(true),
+        "--unified" => return ParseArgResult::Unified(true),
         "-h" | "--help" => return ParseArgResult::Help(true),
         "--version" => return ParseArgResult::Version(true),
         _ => {}
     }
 
+    // Coordinates (e.g. geo:35.68,139.76)
+    if let Some(r) = geo::parse_geo_str(arg) {
+        return match r {
+            Ok((lat, lon)) => ParseArgResult::Tzname(geo::find_zone(lat, lon)),
+            Err(e) => ParseArgResult::Error(e),
+        };
+    }
+
     // Date with offset
     if let Ok(dt) = date::parse_datestr_with_offset(arg, parse_settings) {
         return ParseArgResult::EpochInfo(dt);
@@ -154,6 +358,14 @@ fn parse_arg_value(arg: &str, parse_settings: &date::ParseSettings) -> ParseArgR
         return ParseArgResult::Tzname(arg.to_string());
     }
 
+    // POSIX TZ string
+    if let Some(r) = parse_posix_tz(arg) {
+        return match r {
+            Ok(posix_tz) => ParseArgResult::Posix(posix_tz),
+            Err(e) => ParseArgResult::Error(e),
+        };
+    }
+
     // Time zone name (search)
     let founds = tz::search(arg);
     if founds.len() == 1 {
@@ -169,43 +381,261 @@ fn parse_arg_value(arg: &str, parse_settings: &date::ParseSettings) -> ParseArgR
     }
 }
 
-fn unique(values: Vec<Zone>) -> Vec<Zone> {
-    let mut int_set: HashSet<i32> = HashSet::new();
-    let mut string_set: HashSet<String> = HashSet::new();
-
-    let mut result: Vec<Zone> = Vec::new();
-    for v in values {
-        match v {
-            Zone::Offset(offset_sec) => {
-                if int_set.insert(offset_sec) {
-                    result.push(Zone::Offset(offset_sec));
-                }
+/// Parses a UTC offset (e.g. "+0900", "-5"), coordinates (e.g. "geo:35.68,139.76") or a time zone name.
+/// Names are taken from `custom_zones` or loaded from `zoneinfo_dir` if given.
+pub fn parse_zone(arg: &str, zoneinfo_dir: Option<&Path>, custom_zones: &[ZoneRules]) -> Result<Zone, String> {
+    if arg.len() >= 2 && (arg.starts_with('+') || arg.starts_with('-')) {
+        if let Ok(offset_sec) = date::parse_offset_str(arg) {
+            return Ok(Zone::Offset(offset_sec));
+        }
+    }
+    if let Some(rules) = find_custom_zone(custom_zones, arg) {
+        return Ok(Zone::Rules(rules));
+    }
+    if let Some(r) = geo::parse_geo_str(arg) {
+        let (lat, lon) = r?;
+        return to_zone(geo::find_zone(lat, lon), zoneinfo_dir, custom_zones);
+    }
+    if let Some(dir) = zoneinfo_dir {
+        if let Ok(rules) = tzif::load_from_dir(dir, arg) {
+            return Ok(Zone::Rules(rules));
+        }
+    }
+    if let Ok(tz) = arg.parse::<Tz>() {
+        return to_zone(link::canonical_name(tz.name()).to_string(), zoneinfo_dir, custom_zones);
+    }
+    if let Some(r) = parse_posix_tz(arg) {
+        return r.map(Zone::Posix);
+    }
+    let tz = tz::resolve(arg)?;
+    to_zone(link::canonical_name(tz.name()).to_string(), zoneinfo_dir, custom_zones)
+}
+
+/// Accepts POSIX TZ strings such as "JST-9" or "<+0330>-3:30".
+/// Lowercase abbreviations are not taken so that JavaScript such as "now+1" is not mistaken for one.
+fn parse_posix_tz(arg: &str) -> Option<Result<PosixTz, String>> {
+    if !(arg.starts_with('<') || arg.starts_with(|c: char| c.is_ascii_uppercase())) {
+        return None;
+    }
+    PosixTz::try_parse(arg)
+}
+
+/// Parses a comma separated list of extra columns (e.g. "weekday,isoweek,relative").
+fn parse_columns(columns_str: &str) -> Result<Vec<Column>, String> {
+    let mut columns: Vec<Column> = Vec::new();
+    for name in columns_str.split(',').map(str::trim) {
+        let column = match name {
+            "weekday" => Column::Weekday,
+            "isoweek" => Column::IsoWeek,
+            "usweek" => Column::UsWeek,
+            "yday" => Column::DayOfYear,
+            "quarter" => Column::Quarter,
+            "relative" => Column::Relative,
+            "ms" => Column::Milliseconds,
+            "us" => Column::Microseconds,
+            "ns" => Column::Nanoseconds,
+            "hex" => Column::Hex,
+            "rfc2822" => Column::Rfc2822,
+            "delta" => Column::Delta,
+            "delta-time" => Column::DeltaTime,
+            "elapsed" => Column::Elapsed,
+            "elapsed-time" => Column::ElapsedTime,
+            "calendar-delta" => Column::CalendarDelta,
+            _ => return Err(format!("Invalid column: {}", name)),
+        };
+        if !columns.contains(&column) {
+            columns.push(column);
+        }
+    }
+    Ok(columns)
+}
+
+/// Parses the value of --style.
+fn parse_style(style_str: &str) -> Result<PrintMode, String> {
+    match style_str {
+        "markdown" => Ok(PrintMode::Markdown),
+        "box" => Ok(PrintMode::Box),
+        "org" => Ok(PrintMode::Org),
+        "rst" => Ok(PrintMode::Rst),
+        "asciidoc" => Ok(PrintMode::AsciiDoc),
+        "latex" => Ok(PrintMode::Latex),
+        "html" => Ok(PrintMode::Html),
+        _ => Err(format!("Invalid style: {}", style_str)),
+    }
+}
+
+fn parse_layout(layout_str: &str) -> Result<Layout, String> {
+    match layout_str {
+        "auto" => Ok(Layout::Auto),
+        "wide" => Ok(Layout::Wide),
+        "wrap" => Ok(Layout::Wrap),
+        "transpose" => Ok(Layout::Transpose),
+        _ => Err(format!("Invalid layout: {}", layout_str)),
+    }
+}
+
+/// Parses the value of --ref: an epoch or a date with an offset.
+fn parse_reference(reference_str: &str, parse_settings: &date::ParseSettings) -> Result<i64, String> {
+    if let Ok(epoch_sec) = reference_str.parse::<i64>() {
+        return Ok(epoch_sec);
+    }
+    date::parse_datestr_with_offset(reference_str, parse_settings)
+        .map(|epoch_info| epoch_info.epoch_sec)
+        .map_err(|_| format!("Invalid reference: {}", reference_str))
+}
+
+fn parse_jobs(jobs_str: &str) -> Result<usize, String> {
+    match jobs_str.parse::<usize>() {
+        Ok(jobs) if (1..=MAX_JOBS).contains(&jobs) => Ok(jobs),
+        _ => Err(format!("Invalid jobs: {}", jobs_str)),
+    }
+}
+
+/// Parses the value of --color; None is auto.
+fn parse_color(when: &str) -> Result<Option<bool>, String> {
+    match when {
+        "auto" => Ok(None),
+        "always" => Ok(Some(true)),
+        "never" => Ok(Some(false)),
+        _ => Err(format!("Invalid color: {}", when)),
+    }
+}
+
+/// Colors are used when stdout is a terminal and NO_COLOR (https://no-color.org) is not set.
+fn color_by_default() -> bool {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    !no_color && stdout().is_terminal()
+}
+
+/// Finds --json or --ndjson so that errors can be printed in the requested format even if parsing fails.
+pub fn find_json_mode(args: &[String]) -> Option<PrintMode> {
+    args.iter().rev().find_map(|arg| match arg.as_str() {
+        "--json" => Some(PrintMode::Json),
+        "--ndjson" => Some(PrintMode::Ndjson),
+        _ => None,
+    })
+}
+
+/// Finds --zoneinfo <dir|file> or --system-tz.
+pub fn parse_zoneinfo_option(args: &[String]) -> Result<Option<ZoneinfoOption>, String> {
+    let mut zoneinfo: Option<ZoneinfoOption> = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--system-tz" => zoneinfo = Some(ZoneinfoOption::Dir(tzif::system_dir())),
+            "--zoneinfo" => {
+                let path = PathBuf::from(iter.next().ok_or("--zoneinfo requires a directory or a TZif file")?);
+                zoneinfo = if path.is_dir() {
+                    Some(ZoneinfoOption::Dir(path))
+                } else if path.is_file() {
+                    Some(ZoneinfoOption::File(path))
+                } else {
+                    return Err(format!("No such directory or file: {}", path.display()));
+                };
             }
-            Zone::Tzname(tzname) => {
-                if string_set.insert(tzname.clone()) {
-                    result.push(Zone::Tzname(tzname));
-                }
+            _ => {}
+        }
+    }
+    Ok(zoneinfo)
+}
+
+/// Finds --zic <file> and compiles the zones defined in the files.
+pub fn parse_zic_option(args: &[String]) -> Result<Vec<ZoneRules>, Vec<String>> {
+    let mut zones: Vec<ZoneRules> = Vec::new();
+    let mut errors: Vec<String> = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg != "--zic" {
+            continue;
+        }
+        match iter.next() {
+            Some(path) => match zic::compile_file(Path::new(path)) {
+                Ok(compiled) => zones.extend(compiled),
+                Err(e) => errors.extend(e),
+            },
+            None => errors.push("--zic requires a file".to_string()),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(zones)
+}
+
+/// Matches a custom zone by its full name (e.g. "Europe/London") or its location (e.g. "london").
+fn find_custom_zone(custom_zones: &[ZoneRules], arg: &str) -> Option<ZoneRules> {
+    let key = tz::normalize(arg);
+    custom_zones
+        .iter()
+        .find(|rules| {
+            let name = tz::normalize(&rules.name);
+            name == key || name.rsplit('/').next() == Some(key.as_str())
+        })
+        .cloned()
+}
+
+fn to_zone(tzname: String, zoneinfo_dir: Option<&Path>, custom_zones: &[ZoneRules]) -> Result<Zone, String> {
+    if let Some(rules) = custom_zones.iter().find(|rules| rules.name == tzname) {
+        return Ok(Zone::Rules(rules.clone()));
+    }
+    match zoneinfo_dir {
+        Some(dir) => tzif::load_from_dir(dir, &tzname).map(Zone::Rules),
+        None => tzname.parse().map(Zone::Tz).map_err(|_| format!("No time zone found: {}", tzname)),
+    }
+}
+
+fn inverted_etc_warning(tzname: &str, offset_sec: i32) -> String {
+    format!(
+        "{} is UTC{} (the sign of Etc/GMT zones is inverted)",
+        tzname,
+        date::to_offset_str(offset_sec)
+    )
+}
+
+/// Removes zones that give the same local times, keeping the first one.
+fn unique(values: Vec<Zone>) -> Vec<Zone> {
+    let mut set: HashSet<String> = HashSet::new();
+    values.into_iter().filter(|zone| set.insert(identity(zone))).collect()
+}
+
+/// Links (e.g. "US/Pacific") are the same as their zone, and fixed "Etc/*" zones (e.g. "UTC") the same as the offset.
+fn identity(zone: &Zone) -> String {
+    match zone {
+        Zone::Offset(offset_sec) => date::to_offset_str(*offset_sec),
+        Zone::Tz(tz) => {
+            let canonical_name = link::canonical_name(tz.name());
+            match canonical_name.parse::<Tz>() {
+                Ok(tz) if canonical_name.starts_with("Etc/") => date::to_offset_str(date::offset_info_with_tz(0, tz).offset_sec),
+                _ => canonical_name.to_string(),
             }
         }
+        Zone::Posix(posix_tz) => posix_tz.tz_str.to_string(),
+        Zone::Rules(rules) => rules.header(),
     }
-    result
 }
 
 fn make_default_settings() -> Settings {
     let now = date::current_date_info();
     Settings {
         timezones: vec![Zone::Offset(now.offset_sec)],
-        epochs: vec![now],
+        local_zone: 0,
+        epochs: vec![EpochArg {
+            epochs: date::Epochs::List(vec![now.epoch_sec]),
+            datestr: now.datestr,
+        }],
         dates: vec![],
-        time_mode: TimeMode::Seconds,
+        inputs: vec![Input::Epoch(0)],
         print_mode: PrintMode::Markdown,
+        output: OutputOptions::default(),
         help: false,
         version: false,
+        warnings: vec![],
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::super::print;
     use super::*;
     #[test]
     fn test_parse_arg_value_epoch() {
@@ -247,6 +677,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_arg_value_posix() {
+        for arg in ["JST-9", "EST5EDT,M3.2.0,M11.1.0", "<+0330>-3:30", "PST8"] {
+            match parse_arg_value(arg, &get_parse_settings()) {
+                ParseArgResult::Posix(posix_tz) => assert_eq!(arg, posix_tz.tz_str),
+                _ => unreachable!(),
+            }
+        }
+        match parse_arg_value("EST5EDT", &get_parse_settings()) {
+            ParseArgResult::Tzname(tzname) => assert_eq!("EST5EDT", tzname),
+            _ => unreachable!(),
+        }
+        match parse_arg_value("now+1", &get_parse_settings()) {
+            ParseArgResult::Epochs(_) => (),
+            _ => unreachable!(),
+        }
+        for arg in ["ABC-24", "ABC-30", "ABC3DEF-24,M3.2.0,M11.1.0"] {
+            match parse_arg_value(arg, &get_parse_settings()) {
+                ParseArgResult::Error(e) => assert_eq!(format!("Invalid timezone: {}", arg), e),
+                _ => unreachable!(),
+            }
+        }
+    }
+
     #[test]
     fn test_parse_arg_value_error() {
         let test_data: Vec<&str> = vec!["+", "-", "x", "", "1x", "1.0.0"];
@@ -269,7 +723,7 @@ mod tests {
         assert_eq!(0, actual.dates.len());
         assert_eq!(1, actual.timezones.len());
         assert_eq!(1, actual.epochs.len());
-        assert_eq!(now.epoch_sec, actual.epochs[0].epoch_sec);
+        assert_eq!(date::Epochs::List(vec![now.epoch_sec]), actual.epochs[0].epochs);
     }
 
     #[test]
@@ -297,7 +751,9 @@ mod tests {
 
         assert_eq!(4, actual.dates.len());
         assert_eq!(6, actual.timezones.len());
-        assert_eq!(5, actual.epochs.len());
+        assert_eq!(4, actual.epochs.len());
+        assert_eq!(5, actual.epochs.iter().map(|e| e.epochs.iter().count()).sum::<usize>());
+        assert_eq!("[1651789910,1651789910+86400]", actual.epochs[3].datestr);
     }
 
     #[test]
@@ -323,4 +779,296 @@ mod tests {
         assert_eq!(1, actual.timezones.len());
         assert_eq!(1, actual.epochs.len());
     }
+
+    #[test]
+    fn test_find_custom_zone() {
+        let custom_zones = zic::compile("Zone\tEurope/London\t1:00\t-\t+01\nLink\tEurope/London\tGB", "test").unwrap();
+        assert_eq!("Europe/London", find_custom_zone(&custom_zones, "Europe/London").unwrap().name);
+        assert_eq!("Europe/London", find_custom_zone(&custom_zones, "london").unwrap().name);
+        assert_eq!("GB", find_custom_zone(&custom_zones, "gb").unwrap().name);
+        assert!(find_custom_zone(&custom_zones, "tokyo").is_none());
+
+        match to_zone("Europe/London".to_string(), None, &custom_zones) {
+            Ok(Zone::Rules(rules)) => assert_eq!("Europe/London [test]", rules.header()),
+            _ => unreachable!(),
+        }
+        match to_zone("Asia/Tokyo".to_string(), None, &custom_zones) {
+            Ok(Zone::Tz(tz)) => assert_eq!("Asia/Tokyo", tz.name()),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_parse_arguments_unique() {
+        let args: Vec<String> = [
+            "dummy",
+            "0",
+            "US/Pacific",
+            "America/Los_Angeles",
+            "PST8PDT",
+            "+0000",
+            "UTC",
+            "Etc/GMT+5",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let actual = parse_arguments(&args, &get_parse_settings()).unwrap();
+        let headers: Vec<String> = actual.timezones.iter().map(print::zone_header).collect();
+        // PST8PDT is a zone of its own in the bundled tzdata, not a link
+        assert_eq!(vec!["America/Los_Angeles", "PST8PDT", "+0000", "Etc/GMT+5"], headers);
+        assert_eq!(
+            vec!["Etc/GMT+5 is UTC-0500 (the sign of Etc/GMT zones is inverted)"],
+            actual.warnings
+        );
+
+        let mut args = args;
+        args.push("--alias".to_string());
+        args.push("--windows-id".to_string());
+        let actual = parse_arguments(&args, &get_parse_settings()).unwrap();
+        let headers: Vec<String> = actual.timezones.iter().map(print::zone_header).collect();
+        assert_eq!(vec!["US/Pacific", "PST8PDT", "+0000", "Etc/GMT+5"], headers);
+        assert!(actual.output.windows_id);
+    }
+
+    #[test]
+    fn test_parse_arguments_territory() {
+        let args: Vec<String> = ["dummy", "0", "Pacific Standard Time", "Eastern Time (US & Canada)"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let actual = parse_arguments(&args, &get_parse_settings()).unwrap();
+        let headers: Vec<String> = actual.timezones.iter().map(print::zone_header).collect();
+        assert_eq!(vec!["America/Los_Angeles", "America/New_York"], headers);
+
+        let mut args = args;
+        args.push("--territory".to_string());
+        args.push("CA".to_string());
+        let actual = parse_arguments(&args, &get_parse_settings()).unwrap();
+        let headers: Vec<String> = actual.timezones.iter().map(print::zone_header).collect();
+        assert_eq!(vec!["America/Vancouver", "America/New_York"], headers);
+
+        let with_territory = |territory: &[&str]| {
+            let mut args: Vec<String> = ["dummy", "0", "Pacific Standard Time"].iter().map(|s| s.to_string()).collect();
+            args.extend(territory.iter().map(|s| s.to_string()));
+            parse_arguments(&args, &get_parse_settings())
+        };
+        assert_eq!(vec!["Invalid territory: ZZ"], with_territory(&["--territory", "ZZ"]).err().unwrap());
+        assert_eq!(
+            vec!["Invalid territory: 001"],
+            with_territory(&["--territory", "001"]).err().unwrap()
+        );
+        assert_eq!(
+            vec!["--territory requires a value"],
+            with_territory(&["--territory"]).err().unwrap()
+        );
+        assert!(with_territory(&["--territory", "ca"]).is_ok());
+    }
+
+    #[test]
+    fn test_parse_arguments_geo() {
+        let args: Vec<String> = ["dummy", "0", "geo:35.68,139.76", "geo:30,-150"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let actual = parse_arguments(&args, &get_parse_settings()).unwrap();
+        let headers: Vec<String> = actual.timezones.iter().map(print::zone_header).collect();
+        assert_eq!(vec!["Asia/Tokyo", "Etc/GMT+10"], headers);
+        assert!(actual.warnings.is_empty());
+
+        let args: Vec<String> = ["dummy", "0", "geo:135,35"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            vec!["Invalid coordinates: geo:135,35"],
+            parse_arguments(&args, &get_parse_settings()).err().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_find_json_mode() {
+        let args: Vec<String> = ["dummy", "x/y", "--json"].iter().map(|s| s.to_string()).collect();
+        assert!(matches!(find_json_mode(&args), Some(PrintMode::Json)));
+        assert!(parse_arguments(&args, &get_parse_settings()).is_err());
+
+        let args: Vec<String> = ["dummy", "0", "--ndjson"].iter().map(|s| s.to_string()).collect();
+        assert!(matches!(find_json_mode(&args), Some(PrintMode::Ndjson)));
+        assert!(matches!(
+            parse_arguments(&args, &get_parse_settings()).unwrap().print_mode,
+            PrintMode::Ndjson
+        ));
+        assert!(find_json_mode(&args[..2]).is_none());
+    }
+
+    #[test]
+    fn test_parse_arguments_format() {
+        let args: Vec<String> = ["dummy", "0", "tokyo=rfc2822", "london", "--format", "sql"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let actual = parse_arguments(&args, &get_parse_settings()).unwrap();
+        let headers: Vec<String> = actual.timezones.iter().map(print::zone_header).collect();
+        assert_eq!(vec!["Asia/Tokyo", "Europe/London"], headers);
+        assert_eq!("%Y-%m-%d %H:%M:%S", actual.output.format.unwrap().pattern);
+        assert_eq!("%a, %d %b %Y %H:%M:%S %z", actual.output.zone_formats["Asia/Tokyo"].pattern);
+
+        let args: Vec<String> = ["dummy", "0", "tokyo=bad"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            vec!["Invalid format: bad"],
+            parse_arguments(&args, &get_parse_settings()).err().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_columns() {
+        assert_eq!(
+            vec![Column::Weekday, Column::Hex, Column::IsoWeek],
+            parse_columns("weekday,hex, isoweek,weekday").unwrap()
+        );
+        assert_eq!(Err("Invalid column: week".to_string()), parse_columns("weekday,week"));
+        assert_eq!(
+            vec![Column::Delta, Column::ElapsedTime, Column::CalendarDelta],
+            parse_columns("delta,elapsed-time,calendar-delta").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_arguments_inputs() {
+        let args: Vec<String> = [
+            "dummy",
+            "2022-03-13T02:30",
+            "[0, 60]",
+            "2022-04-17T21:09:49+0900",
+            "tokyo",
+            "--unified",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let settings = parse_arguments(&args, &get_parse_settings()).unwrap();
+        assert!(settings.output.unified);
+        assert_eq!(vec![Input::Date(0), Input::Epoch(0), Input::DateWithOffset(1)], settings.inputs);
+        assert_eq!(date::Epochs::List(vec![0, 60]), settings.epochs[0].epochs);
+
+        let args: Vec<String> = ["dummy", "tokyo"].iter().map(|s| s.to_string()).collect();
+        let settings = parse_arguments(&args, &get_parse_settings()).unwrap();
+        assert!(!settings.output.unified);
+        assert_eq!(vec![Input::Epoch(0)], settings.inputs);
+    }
+
+    #[test]
+    fn test_parse_arguments_local_zone() {
+        let local_zone = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+            let settings = parse_arguments(&args, &get_parse_settings()).unwrap();
+            print::zone_header(&settings.timezones[settings.local_zone])
+        };
+        // The offset of a date with an offset is a column, but not a zone the user named
+        assert_eq!(
+            "Europe/London",
+            local_zone(&["dummy", "2022-04-17T21:09:49+0900", "2023-11-15T09:00", "london"])
+        );
+        assert_eq!("+0900", local_zone(&["dummy", "2022-04-17T21:09:49+0900", "+9", "london"]));
+        assert_eq!("Europe/London", local_zone(&["dummy", "2022-04-17T21:09:49+0900", "london", "+9"]));
+        assert_eq!("+0900", local_zone(&["dummy", "2022-04-17T21:09:49+0900", "2023-11-15T09:00"]));
+    }
+
+    #[test]
+    fn test_parse_arguments_milliseconds() {
+        let args: Vec<String> = ["dummy", "1650197389123", "2022-04-17T21:09:49+0900", "-m"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let settings = parse_arguments(&args, &get_parse_settings()).unwrap();
+        assert_eq!(date::Epochs::List(vec![1650197389]), settings.epochs[0].epochs);
+        // Dates are not epochs
+        assert_eq!(date::Epochs::List(vec![1650197389]), settings.epochs[1].epochs);
+    }
+
+    #[test]
+    fn test_parse_arguments_reference() {
+        let args: Vec<String> = ["dummy", "0", "--ref", "1650197389"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            Some(1650197389),
+            parse_arguments(&args, &get_parse_settings()).unwrap().output.reference
+        );
+
+        let args: Vec<String> = ["dummy", "0", "--ref", "2022-04-17T21:09:49+0900"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            Some(1650197389),
+            parse_arguments(&args, &get_parse_settings()).unwrap().output.reference
+        );
+
+        let args: Vec<String> = ["dummy", "0", "--ref", "tokyo"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            vec!["Invalid reference: tokyo"],
+            parse_arguments(&args, &get_parse_settings()).err().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_arguments_style() {
+        let args: Vec<String> = ["dummy", "0", "--style", "org"].iter().map(|s| s.to_string()).collect();
+        let settings = parse_arguments(&args, &get_parse_settings()).unwrap();
+        assert!(matches!(settings.print_mode, PrintMode::Org));
+
+        let args: Vec<String> = ["dummy", "0", "--style", "csv"].iter().map(|s| s.to_string()).collect();
+        let errors = parse_arguments(&args, &get_parse_settings()).err().unwrap();
+        assert_eq!(vec!["Invalid style: csv"], errors);
+    }
+
+    #[test]
+    fn test_parse_arguments_color() {
+        let args: Vec<String> = ["dummy", "0", "--color", "always"].iter().map(|s| s.to_string()).collect();
+        assert!(parse_arguments(&args, &get_parse_settings()).unwrap().output.color);
+
+        // Only the Markdown and plain text tables are colored
+        let args: Vec<String> = ["dummy", "0", "--color", "always", "--style", "html"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert!(!parse_arguments(&args, &get_parse_settings()).unwrap().output.color);
+
+        let args: Vec<String> = ["dummy", "0", "--color", "never"].iter().map(|s| s.to_string()).collect();
+        assert!(!parse_arguments(&args, &get_parse_settings()).unwrap().output.color);
+
+        let args: Vec<String> = ["dummy", "0", "--color", "yes"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            vec!["Invalid color: yes"],
+            parse_arguments(&args, &get_parse_settings()).err().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_arguments_layout() {
+        let args: Vec<String> = ["dummy", "0", "--layout", "transpose"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            Layout::Transpose,
+            parse_arguments(&args, &get_parse_settings()).unwrap().output.layout
+        );
+
+        let args: Vec<String> = ["dummy", "0", "--layout", "tall"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            vec!["Invalid layout: tall"],
+            parse_arguments(&args, &get_parse_settings()).err().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_arguments_jobs() {
+        let args: Vec<String> = ["dummy", "0", "--jobs", "4"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(4, parse_arguments(&args, &get_parse_settings()).unwrap().output.jobs);
+
+        let args: Vec<String> = ["dummy", "0"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(0, parse_arguments(&args, &get_parse_settings()).unwrap().output.jobs);
+
+        for jobs in ["0", "-1", "x", "257", "4611686018427387904", "18446744073709551615"] {
+            let args: Vec<String> = ["dummy", "0", "--jobs", jobs].iter().map(|s| s.to_string()).collect();
+            assert_eq!(
+                vec![format!("Invalid jobs: {}", jobs)],
+                parse_arguments(&args, &get_parse_settings()).err().unwrap()
+            );
+        }
+    }
 }
@@ -0,0 +1,17 @@
+use super::{doctor, lookup, offset_diff, plan, transition, tzinfo};
+
+/// Runs a subcommand such as `epo tz list`. Returns None if the first argument is not a subcommand.
+pub fn run(args: &[String]) -> Option<Result<i32, Vec<String>>> {
+    let name = args.get(1)?;
+    let sub_args = &args[2..];
+
+    match name.as_str() {
+        "tz" => Some(tzinfo::run(sub_args)),
+        "transitions" => Some(transition::run(sub_args)),
+        "plan" => Some(plan::run(sub_args)),
+        "lookup" => Some(lookup::run(sub_args)),
+        "doctor" => Some(doctor::run(sub_args)),
+        "offset-diff" => Some(offset_diff::run(sub_args)),
+        _ => None,
+    }
+}
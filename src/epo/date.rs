@@ -1,6 +1,8 @@
-use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
-use chrono_tz::Tz;
+use chrono::format::{Fixed, Item, StrftimeItems};
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveDateTime, Offset, TimeZone, Timelike, Utc};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
 use once_cell::sync::Lazy;
+use std::fmt::Write;
 
 pub struct EpochInfo {
     pub epoch_sec: i64,
@@ -8,11 +10,71 @@ pub struct EpochInfo {
     pub datestr: String,
 }
 
+/// The epochs of an argument, kept as a range instead of a list when it is one so that millions of them are made only when printed.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Epochs {
+    List(Vec<i64>),
+    /// From the start toward the end one second at a time, without the end (range() of the scripts)
+    Range(i64, i64),
+}
+
+impl Epochs {
+    pub fn iter(&self) -> Box<dyn Iterator<Item = i64> + '_> {
+        match *self {
+            Epochs::List(ref epochs) => Box::new(epochs.iter().copied()),
+            Epochs::Range(start, end) if start <= end => Box::new(start..end),
+            Epochs::Range(start, end) => Box::new((end + 1..=start).rev()),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match *self {
+            Epochs::List(ref epochs) => epochs.is_empty(),
+            Epochs::Range(start, end) => start == end,
+        }
+    }
+
+    /// The smallest and the largest epoch
+    pub fn bounds(&self) -> Option<(i64, i64)> {
+        match *self {
+            Epochs::List(ref epochs) => Some((*epochs.iter().min()?, *epochs.iter().max()?)),
+            Epochs::Range(start, end) if start < end => Some((start, end - 1)),
+            Epochs::Range(start, end) if start > end => Some((end + 1, start)),
+            Epochs::Range(..) => None,
+        }
+    }
+
+    /// The seconds of epochs given in milliseconds (-m)
+    pub fn millis_to_secs(&self) -> Epochs {
+        match *self {
+            Epochs::List(ref epochs) => Epochs::List(epochs.iter().map(|ms| ms.div_euclid(1000)).collect()),
+            Epochs::Range(start, end) if start < end => Epochs::Range(start.div_euclid(1000), (end - 1).div_euclid(1000) + 1),
+            Epochs::Range(start, end) if start > end => Epochs::Range(start.div_euclid(1000), (end + 1).div_euclid(1000) - 1),
+            Epochs::Range(start, _) => Epochs::Range(start.div_euclid(1000), start.div_euclid(1000)),
+        }
+    }
+}
+
 pub struct DateInfo {
     pub date_time: NaiveDateTime,
     pub datestr: String,
 }
 
+#[derive(PartialEq, Debug)]
+pub struct OffsetInfo {
+    pub offset_sec: i32,
+    pub dst_sec: i32,
+    pub abbreviation: String,
+}
+
+/// A strftime pattern for the cells, given with --format.
+#[derive(Clone)]
+pub struct DateFormat {
+    pub pattern: String,
+    /// HTTP and cookie dates are always written in GMT whatever the zone is
+    pub utc: bool,
+}
+
 pub struct ParseSettings {
     pub date_formats_10: Vec<String>,
     pub date_formats_16: Vec<String>,
@@ -28,15 +90,130 @@ pub fn to_datestr_from_ndt(dt: NaiveDateTime) -> String {
 }
 
 pub fn to_datestr(epoch_sec: i64, offset_sec: i32) -> String {
-    let dt = Utc.timestamp(epoch_sec, 0).with_timezone(&FixedOffset::east(offset_sec));
+    let dt = Utc
+        .timestamp_opt(epoch_sec, 0)
+        .unwrap()
+        .with_timezone(&FixedOffset::east_opt(offset_sec).unwrap());
+
+    let mut s = String::with_capacity(24);
+    write_datestr(&mut s, &dt);
+    s
+}
+
+/// Appends a date in the format of to_datestr. Parsing the format for every cell takes most of the time of a large table,
+/// so four-digit years are written directly.
+pub fn write_datestr(out: &mut String, dt: &DateTime<FixedOffset>) {
+    if !(0..=9999).contains(&dt.year()) {
+        write!(out, "{}", dt.format(DEFAULT_DATE_FORMAT_WITH_TZ)).unwrap();
+        return;
+    }
+    let offset_sec = dt.offset().local_minus_utc();
+    let offset_min = offset_sec.unsigned_abs() / 60;
+    push_digits(out, dt.year() as u32, 4);
+    out.push('-');
+    push_digits(out, dt.month(), 2);
+    out.push('-');
+    push_digits(out, dt.day(), 2);
+    out.push('T');
+    push_digits(out, dt.hour(), 2);
+    out.push(':');
+    push_digits(out, dt.minute(), 2);
+    out.push(':');
+    push_digits(out, dt.second(), 2);
+    out.push(if offset_sec < 0 { '-' } else { '+' });
+    push_digits(out, offset_min / 60, 2);
+    push_digits(out, offset_min % 60, 2);
+}
+
+/// Appends a number zero-padded to `width` digits.
+fn push_digits(out: &mut String, value: u32, width: u32) {
+    for i in (0..width).rev() {
+        out.push(char::from(b'0' + (value / 10u32.pow(i) % 10) as u8));
+    }
+}
+
+pub fn to_datestr_with_format(epoch_sec: i64, offset_sec: i32, format: &DateFormat) -> String {
+    if format.utc {
+        return Utc.timestamp_opt(epoch_sec, 0).unwrap().format(&format.pattern).to_string();
+    }
+    let dt = Utc
+        .timestamp_opt(epoch_sec, 0)
+        .unwrap()
+        .with_timezone(&FixedOffset::east_opt(offset_sec).unwrap());
+    dt.format(&format.pattern).to_string()
+}
+
+/// Same as `to_datestr_with_format` but %Z is the abbreviation of the zone (e.g. "PDT").
+pub fn to_datestr_with_tz_format(epoch_sec: i64, tz: Tz, format: &DateFormat) -> String {
+    if format.utc {
+        return Utc.timestamp_opt(epoch_sec, 0).unwrap().format(&format.pattern).to_string();
+    }
+    tz.timestamp_opt(epoch_sec, 0).unwrap().format(&format.pattern).to_string()
+}
 
-    dt.format(DEFAULT_DATE_FORMAT_WITH_TZ).to_string()
+/// Formats a date without an offset; the parts of the pattern that need an offset (%z, %Z, ...) are left out.
+pub fn to_naive_datestr_with_format(dt: NaiveDateTime, format: &DateFormat) -> String {
+    let items: Vec<Item> = StrftimeItems::new(&format.pattern)
+        .filter(|item| {
+            !matches!(
+                item,
+                Item::Fixed(Fixed::TimezoneName)
+                    | Item::Fixed(Fixed::TimezoneOffset)
+                    | Item::Fixed(Fixed::TimezoneOffsetColon)
+                    | Item::Fixed(Fixed::TimezoneOffsetColonZ)
+                    | Item::Fixed(Fixed::TimezoneOffsetZ)
+                    | Item::Fixed(Fixed::RFC2822)
+                    | Item::Fixed(Fixed::RFC3339)
+            )
+        })
+        .collect();
+    dt.format_with_items(items.into_iter()).to_string().trim().to_string()
 }
 
-pub fn to_datestr_with_tz(epoch_sec: i64, timezone: &str) -> String {
-    let tz: Tz = timezone.parse().unwrap();
-    let dt = tz.timestamp(epoch_sec, 0);
-    dt.format(DEFAULT_DATE_FORMAT_WITH_TZ).to_string()
+/// Takes a preset name (e.g. "rfc3339") or a strftime pattern (e.g. "%Y/%m/%d %H:%M").
+pub fn parse_format_str(format_str: &str) -> Result<DateFormat, String> {
+    let (pattern, utc) = match format_str {
+        "rfc3339" => ("%Y-%m-%dT%H:%M:%S%:z", false),
+        "rfc2822" => ("%a, %d %b %Y %H:%M:%S %z", false),
+        "http" => ("%a, %d %b %Y %H:%M:%S GMT", true),
+        "clf" => ("%d/%b/%Y:%H:%M:%S %z", false),
+        "sql" => ("%Y-%m-%d %H:%M:%S", false),
+        "cookie" => ("%a, %d-%b-%Y %H:%M:%S GMT", true),
+        "iso-basic" => ("%Y%m%dT%H%M%S%z", false),
+        _ => (format_str, false),
+    };
+    if !pattern.contains('%') || StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error)) {
+        return Err(format!("Invalid format: {}", format_str));
+    }
+    Ok(DateFormat {
+        pattern: pattern.to_string(),
+        utc,
+    })
+}
+
+pub fn offset_info_with_tz(epoch_sec: i64, tz: Tz) -> OffsetInfo {
+    let dt = tz.timestamp_opt(epoch_sec, 0).unwrap();
+    let offset = dt.offset();
+    OffsetInfo {
+        offset_sec: offset.fix().local_minus_utc(),
+        dst_sec: offset.dst_offset().num_seconds() as i32,
+        abbreviation: offset.abbreviation().to_string(),
+    }
+}
+
+/// Converts a local date time to an epoch for zones whose offset is given by `offset_at`.
+/// Ambiguous times resolve to the earlier instant; times in a gap are shifted by the offset before the gap.
+pub fn local_to_epoch(date_time: NaiveDateTime, offset_at: impl Fn(i64) -> i32) -> i64 {
+    let local_sec = date_time.timestamp();
+    let before = local_sec - offset_at(local_sec - 86400) as i64;
+    let after = local_sec - offset_at(local_sec + 86400) as i64;
+
+    let valid = |epoch_sec: i64| local_sec - offset_at(epoch_sec) as i64 == epoch_sec;
+    match (valid(before), valid(after)) {
+        (true, true) => before.min(after),
+        (false, true) => after,
+        _ => before,
+    }
 }
 
 pub fn parse_datestr_with_offset(datestr: &str, parse_settings: &ParseSettings) -> Result<EpochInfo, String> {
@@ -100,7 +277,7 @@ pub fn parse_naive_datestr(datestr: &str, parse_settings: &ParseSettings) -> Res
 fn parse_datestr(datestr: &str, format: &str) -> Result<DateInfo, String> {
     if datestr.len() <= 10 {
         if let Ok(date) = NaiveDate::parse_from_str(datestr, format) {
-            let date_time = date.and_hms(0, 0, 0);
+            let date_time = date.and_hms_opt(0, 0, 0).unwrap();
             return Ok(DateInfo {
                 date_time,
                 datestr: datestr.to_string(),
@@ -123,21 +300,17 @@ fn parse_datestr(datestr: &str, format: &str) -> Result<DateInfo, String> {
 static START_DATE_TIME: Lazy<DateTime<Local>> = Lazy::new(Local::now);
 
 pub fn current_epoch() -> i64 {
-    (&START_DATE_TIME).timestamp()
+    START_DATE_TIME.timestamp()
 }
 
 pub fn current_date_info() -> EpochInfo {
-    to_date_value((&START_DATE_TIME).with_timezone(&Local))
-}
-
-pub fn get_utc_offset_sec() -> i32 {
-    (&START_DATE_TIME).date().offset().local_minus_utc()
+    to_date_value(START_DATE_TIME.with_timezone(&Local))
 }
 
 fn to_date_value(time: DateTime<Local>) -> EpochInfo {
     let epoch_sec = time.timestamp();
-    let offset_sec = time.date().offset().local_minus_utc();
-    let datestr = to_datestr(epoch_sec, offset_sec / 3600);
+    let offset_sec = time.offset().local_minus_utc();
+    let datestr = to_datestr(epoch_sec, offset_sec);
 
     EpochInfo {
         epoch_sec,
@@ -163,6 +336,75 @@ pub fn to_offset_str(offset_sec: i32) -> String {
     format!("{}{:02}{:02}", sign, hour, min)
 }
 
+/// Describes an epoch relative to `now` with the two largest units (e.g. "3d 4h ago", "in 2h").
+pub fn to_relative_str(epoch_sec: i64, now: i64) -> String {
+    let diff_sec = (epoch_sec - now).unsigned_abs();
+    let parts = [
+        (diff_sec / 86400, "d"),
+        (diff_sec % 86400 / 3600, "h"),
+        (diff_sec % 3600 / 60, "m"),
+        (diff_sec % 60, "s"),
+    ];
+    let first = match parts.iter().position(|(value, _)| *value != 0) {
+        Some(first) => first,
+        None => return "now".to_string(),
+    };
+    let s: Vec<String> = parts[first..(first + 2).min(parts.len())]
+        .iter()
+        .filter(|(value, _)| *value != 0)
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect();
+    if epoch_sec < now {
+        format!("{} ago", s.join(" "))
+    } else {
+        format!("in {}", s.join(" "))
+    }
+}
+
+/// Formats a signed difference of seconds as days and a time (e.g. "+1d 02:03:04", "-00:00:30").
+pub fn to_duration_str(diff_sec: i64) -> String {
+    let sign = if diff_sec < 0 { '-' } else { '+' };
+    let sec = diff_sec.unsigned_abs();
+    let time = format!("{:02}:{:02}:{:02}", sec % 86400 / 3600, sec % 3600 / 60, sec % 60);
+    match sec / 86400 {
+        0 => format!("{}{}", sign, time),
+        days => format!("{}{}d {}", sign, days, time),
+    }
+}
+
+/// Formats the difference of two local date times in days and a time (e.g. "+1 day", "+2 days 01:00:00").
+/// A day is a change of the date at the same local time however long it took.
+pub fn to_calendar_delta_str(from: NaiveDateTime, to: NaiveDateTime) -> String {
+    let diff_sec = (to - from).num_seconds();
+    let sign = if diff_sec < 0 { '-' } else { '+' };
+    let sec = diff_sec.unsigned_abs();
+    let time = format!("{:02}:{:02}:{:02}", sec % 86400 / 3600, sec % 3600 / 60, sec % 60);
+    match (sec / 86400, sec % 86400) {
+        (0, _) => format!("{}{}", sign, time),
+        (days, 0) => format!("{}{} {}", sign, days, if days == 1 { "day" } else { "days" }),
+        (days, _) => format!("{}{} {} {}", sign, days, if days == 1 { "day" } else { "days" }, time),
+    }
+}
+
+/// Parses a duration such as "90s", "30m", "12h", "14d" or "2w" into seconds.
+pub fn parse_duration_str(duration_str: &str) -> Result<i64, String> {
+    let unit_sec = match duration_str.chars().last() {
+        Some('s') => 1,
+        Some('m') => 60,
+        Some('h') => 3600,
+        Some('d') => 86400,
+        Some('w') => 86400 * 7,
+        _ => return Err(format!("Invalid duration: {}", duration_str)),
+    };
+
+    match duration_str[..duration_str.len() - 1].parse::<i64>() {
+        Ok(value) if value >= 0 => value
+            .checked_mul(unit_sec)
+            .ok_or_else(|| format!("Invalid duration: {}", duration_str)),
+        _ => Err(format!("Invalid duration: {}", duration_str)),
+    }
+}
+
 fn parse_5letters_offset_str(offset_str: &str) -> Result<i32, String> {
     if !(offset_str.starts_with('+') || offset_str.starts_with('-')) {
         return Err("Invalid offset".to_string());
@@ -208,6 +450,26 @@ mod tests {
         assert_eq!("2022-04-17T21:09:49+0900", to_datestr(1650197389, 32400));
         assert_eq!("2022-04-17T12:09:49+0000", to_datestr(1650197389, 0));
         assert_eq!("2022-04-17T07:09:49-0500", to_datestr(1650197389, -18000));
+        // LMT of Tokyo; the seconds of the offset are not shown
+        assert_eq!("1874-12-08T03:58:59+0918", to_datestr(-3000000000, 33539));
+        assert_eq!("2022-04-17T02:39:49-0930", to_datestr(1650197389, -34200));
+
+        for (epoch_sec, offset_sec) in [
+            (-62167219200, 0),
+            (-62167219201, 0),
+            (253402300799, 0),
+            (253402300800, 3600),
+            (1650197389, -1),
+        ] {
+            let dt = Utc
+                .timestamp_opt(epoch_sec, 0)
+                .unwrap()
+                .with_timezone(&FixedOffset::east_opt(offset_sec).unwrap());
+            assert_eq!(
+                dt.format(DEFAULT_DATE_FORMAT_WITH_TZ).to_string(),
+                to_datestr(epoch_sec, offset_sec)
+            );
+        }
     }
 
     #[test]
@@ -263,6 +525,10 @@ mod tests {
 
     #[test]
     fn test_to_datestr2() {
+        let to_datestr_with_tz = |epoch_sec: i64, timezone: &str| {
+            let tz: Tz = timezone.parse().unwrap();
+            to_datestr(epoch_sec, offset_info_with_tz(epoch_sec, tz).offset_sec)
+        };
         assert_eq!("1970-01-01T00:00:00+0000", to_datestr_with_tz(0, "UTC"));
         assert_eq!("1970-01-01T00:00:00+0000", to_datestr_with_tz(0, "GMT"));
         assert_eq!("1970-01-01T09:00:00+0900", to_datestr_with_tz(0, "Asia/Tokyo"));
@@ -277,6 +543,19 @@ mod tests {
         assert_eq!("2021-12-31T12:30:40-0500", to_datestr_with_tz(1640971840, "America/New_York"));
         assert_eq!("2021-12-31T10:30:40-0700", to_datestr_with_tz(1640971840, "America/Phoenix"));
     }
+    #[test]
+    fn test_offset_info_with_tz() {
+        let info = offset_info_with_tz(1650197389, Tz::America__Los_Angeles);
+        assert_eq!(-7 * 3600, info.offset_sec);
+        assert_eq!(3600, info.dst_sec);
+        assert_eq!("PDT", info.abbreviation);
+
+        let info = offset_info_with_tz(1640971840, Tz::America__Los_Angeles);
+        assert_eq!(-8 * 3600, info.offset_sec);
+        assert_eq!(0, info.dst_sec);
+        assert_eq!("PST", info.abbreviation);
+    }
+
     #[test]
     fn test_to_date_value() {
         let dt: Result<DateTime<Local>, _> = Local.datetime_from_str("2023/12/07 22:45:56", "%Y/%m/%d %H:%M:%S");
@@ -333,6 +612,22 @@ mod tests {
         assert!(parse_hours_offset_str("--").is_err());
     }
 
+    #[test]
+    fn test_parse_duration_str() {
+        assert_eq!(90, parse_duration_str("90s").unwrap());
+        assert_eq!(1800, parse_duration_str("30m").unwrap());
+        assert_eq!(3600 * 12, parse_duration_str("12h").unwrap());
+        assert_eq!(86400 * 14, parse_duration_str("14d").unwrap());
+        assert_eq!(86400 * 14, parse_duration_str("2w").unwrap());
+        assert!(parse_duration_str("").is_err());
+        assert!(parse_duration_str("14").is_err());
+        assert!(parse_duration_str("d").is_err());
+        assert!(parse_duration_str("-1d").is_err());
+        assert!(parse_duration_str("1y").is_err());
+        assert_eq!(i64::MAX, parse_duration_str(&format!("{}s", i64::MAX)).unwrap());
+        assert!(parse_duration_str(&format!("{}w", i64::MAX / 3600)).is_err());
+    }
+
     #[test]
     fn test_to_offset_str() {
         assert_eq!("+0000", to_offset_str(0));
@@ -349,4 +644,109 @@ mod tests {
         assert_eq!("+2359", to_offset_str(3600 * 23 + 3540));
         assert_eq!("-2359", to_offset_str(-(3600 * 23 + 3540)));
     }
+
+    #[test]
+    fn test_to_datestr_with_format() {
+        let presets = [
+            ("rfc3339", "2022-04-17T21:09:49+09:00"),
+            ("rfc2822", "Sun, 17 Apr 2022 21:09:49 +0900"),
+            ("http", "Sun, 17 Apr 2022 12:09:49 GMT"),
+            ("clf", "17/Apr/2022:21:09:49 +0900"),
+            ("sql", "2022-04-17 21:09:49"),
+            ("cookie", "Sun, 17-Apr-2022 12:09:49 GMT"),
+            ("iso-basic", "20220417T210949+0900"),
+            ("%Y/%m/%d %H:%M", "2022/04/17 21:09"),
+        ];
+        for (format_str, expected) in presets {
+            let format = parse_format_str(format_str).unwrap();
+            assert_eq!(expected, to_datestr_with_format(1650197389, 32400, &format), "{}", format_str);
+        }
+        assert!(parse_format_str("%Y-%").is_err());
+        assert!(parse_format_str("rfc").is_err());
+    }
+
+    #[test]
+    fn test_to_datestr_with_tz_format() {
+        let format = parse_format_str("%H:%M %Z").unwrap();
+        assert_eq!(
+            "05:09 PDT",
+            to_datestr_with_tz_format(1650197389, Tz::America__Los_Angeles, &format)
+        );
+        let format = parse_format_str("http").unwrap();
+        assert_eq!(
+            "Sun, 17 Apr 2022 12:09:49 GMT",
+            to_datestr_with_tz_format(1650197389, Tz::America__Los_Angeles, &format)
+        );
+    }
+
+    #[test]
+    fn test_to_naive_datestr_with_format() {
+        let dt = NaiveDate::from_ymd_opt(2022, 4, 17).unwrap().and_hms_opt(21, 9, 49).unwrap();
+        assert_eq!(
+            "2022-04-17T21:09:49",
+            to_naive_datestr_with_format(dt, &parse_format_str("rfc3339").unwrap())
+        );
+        assert_eq!(
+            "Sun, 17 Apr 2022 21:09:49",
+            to_naive_datestr_with_format(dt, &parse_format_str("rfc2822").unwrap())
+        );
+        assert_eq!(
+            "2022/04/17",
+            to_naive_datestr_with_format(dt, &parse_format_str("%Y/%m/%d").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_to_relative_str() {
+        assert_eq!("now", to_relative_str(1000, 1000));
+        assert_eq!("3d 4h ago", to_relative_str(0, 86400 * 3 + 3600 * 4 + 59));
+        assert_eq!("in 2h", to_relative_str(7200 + 30, 30));
+        assert_eq!("in 1h", to_relative_str(3601, 0));
+        assert_eq!("in 1h 1m", to_relative_str(3660, 0));
+        assert_eq!("45s ago", to_relative_str(0, 45));
+    }
+
+    #[test]
+    fn test_to_duration_str() {
+        assert_eq!("+00:00:00", to_duration_str(0));
+        assert_eq!("-00:00:30", to_duration_str(-30));
+        assert_eq!("+23:00:00", to_duration_str(82800));
+        assert_eq!("+1d 02:03:04", to_duration_str(86400 + 7384));
+        assert_eq!("-400d 00:00:01", to_duration_str(-(86400 * 400 + 1)));
+    }
+
+    #[test]
+    fn test_to_calendar_delta_str() {
+        let date_time = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap();
+        let delta = |from: &str, to: &str| to_calendar_delta_str(date_time(from), date_time(to));
+        assert_eq!("+00:00:00", delta("2022-03-12T12:00:00", "2022-03-12T12:00:00"));
+        assert_eq!("+1 day", delta("2022-03-12T12:00:00", "2022-03-13T12:00:00"));
+        assert_eq!("+2 days 01:00:00", delta("2022-03-12T12:00:00", "2022-03-14T13:00:00"));
+        assert_eq!("-1 day", delta("2022-03-13T12:00:00", "2022-03-12T12:00:00"));
+        assert_eq!("+23:59:59", delta("2022-12-31T00:00:00", "2022-12-31T23:59:59"));
+    }
+
+    #[test]
+    fn test_epochs() {
+        let epochs = |e: &Epochs| e.iter().collect::<Vec<i64>>();
+        assert_eq!(vec![3, 1, 2], epochs(&Epochs::List(vec![3, 1, 2])));
+        assert_eq!(Some((1, 3)), Epochs::List(vec![3, 1, 2]).bounds());
+        assert_eq!(vec![10, 11, 12], epochs(&Epochs::Range(10, 13)));
+        assert_eq!(Some((10, 12)), Epochs::Range(10, 13).bounds());
+        assert_eq!(vec![0, -1, -2], epochs(&Epochs::Range(0, -3)));
+        assert_eq!(Some((-2, 0)), Epochs::Range(0, -3).bounds());
+
+        assert!(Epochs::Range(5, 5).is_empty());
+        assert_eq!(None, Epochs::Range(5, 5).bounds());
+        assert!(Epochs::List(vec![]).is_empty());
+        assert_eq!(None, Epochs::List(vec![]).bounds());
+
+        assert_eq!(
+            Epochs::List(vec![1681000000, -1]),
+            Epochs::List(vec![1681000000999, -1]).millis_to_secs()
+        );
+        assert_eq!(vec![-2, -1, 0, 1], epochs(&Epochs::Range(-1500, 1500).millis_to_secs()));
+        assert_eq!(vec![5, 4, 3, 2, 1, 0], epochs(&Epochs::Range(5000, 0).millis_to_secs()));
+        assert!(Epochs::Range(5000, 5000).millis_to_secs().is_empty());
+    }
 }
@@ -0,0 +1,268 @@
+use super::rules::ZoneRules;
+use super::types::{PrintMode, Table};
+use super::{app, date, print, transition, tzif};
+use chrono_tz::{Tz, TZ_VARIANTS};
+use std::path::{Path, PathBuf};
+
+const USAGE: &str = "Usage: epo doctor [--zoneinfo <dir>] [--from <year|date>] [--to <year|date>] [-p]";
+
+/// The tzdata release chrono-tz was built from. chrono-tz is pinned in Cargo.toml; update this together with it.
+pub const EMBEDDED_TZDATA_VERSION: &str = "2023b";
+
+/// Files and directories in a zoneinfo directory that are not zones.
+const NON_ZONE_NAMES: [&str; 4] = ["posix", "right", "posixrules", "localtime"];
+
+pub fn run(args: &[String]) -> Result<i32, Vec<String>> {
+    let mut print_mode = PrintMode::Markdown;
+    let mut dir = tzif::system_dir();
+    let mut from: Option<i64> = None;
+    let mut to: Option<i64> = None;
+    let mut errors: Vec<String> = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-p" => print_mode = PrintMode::PlainText,
+            "--zoneinfo" | "--from" | "--to" => {
+                let value = match iter.next() {
+                    Some(value) => value,
+                    None => {
+                        errors.push(format!("{} requires a value", arg));
+                        continue;
+                    }
+                };
+                let r = match arg.as_str() {
+                    "--zoneinfo" => {
+                        dir = PathBuf::from(value);
+                        Ok(())
+                    }
+                    "--from" => transition::parse_range_value(value, false).map(|v| from = Some(v)),
+                    _ => transition::parse_range_value(value, true).map(|v| to = Some(v)),
+                };
+                if let Err(e) = r {
+                    errors.push(e);
+                }
+            }
+            _ => errors.push(USAGE.to_string()),
+        }
+    }
+    if !dir.is_dir() {
+        errors.push(format!("No such directory: {}", dir.display()));
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let current_year = transition::current_year(date::current_epoch());
+    let from_year = from.unwrap_or_else(|| transition::year_start(1970));
+    let to_year = to.unwrap_or_else(|| transition::year_start(current_year + 10));
+
+    let summary = vec![
+        vec!["Embedded tzdata".to_string(), EMBEDDED_TZDATA_VERSION.to_string()],
+        vec![
+            "System tzdata".to_string(),
+            format!(
+                "{} ({})",
+                system_version(&dir).unwrap_or_else(|| "unknown".to_string()),
+                dir.display()
+            ),
+        ],
+        vec![
+            "Range".to_string(),
+            format!("{} - {}", date::to_datestr(from_year, 0), date::to_datestr(to_year, 0)),
+        ],
+    ];
+    print::print_as_plaintext(&summary, ": ");
+    println!();
+
+    let (header, data) = check(&dir, from_year, to_year);
+    if data.is_empty() {
+        println!("No differences");
+        return Ok(0);
+    }
+    app::print(&header, &data, &print_mode);
+    Ok(2)
+}
+
+/// Reads the tzdata version from tzdata.zi ("# version 2023c") or +VERSION.
+pub fn system_version(dir: &Path) -> Option<String> {
+    if let Ok(text) = std::fs::read_to_string(dir.join("tzdata.zi")) {
+        if let Some(version) = parse_version(&text) {
+            return Some(version);
+        }
+    }
+    let text = std::fs::read_to_string(dir.join("+VERSION")).ok()?;
+    let version = text.trim();
+    if version.is_empty() {
+        return None;
+    }
+    Some(version.to_string())
+}
+
+fn parse_version(tzdata_zi: &str) -> Option<String> {
+    tzdata_zi
+        .lines()
+        .take_while(|line| line.starts_with('#'))
+        .find_map(|line| line.strip_prefix("# version "))
+        .map(|version| version.trim().to_string())
+}
+
+/// Compares every zone in [start, end) and lists the zones that differ or exist on only one side.
+pub fn check(dir: &Path, start: i64, end: i64) -> Table {
+    let header: Vec<String> = vec![
+        "Zone".to_string(),
+        "Status".to_string(),
+        "UTC".to_string(),
+        "Embedded".to_string(),
+        "System".to_string(),
+    ];
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for tz in TZ_VARIANTS.iter() {
+        let name = tz.name();
+        if !dir.join(name).is_file() {
+            rows.push(vec![
+                name.to_string(),
+                "Missing in system".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+            ]);
+            continue;
+        }
+        match tzif::load_from_dir(dir, name) {
+            Ok(rules) => {
+                if let Some((epoch_sec, embedded, system)) = find_difference(*tz, &rules, start, end) {
+                    rows.push(vec![
+                        name.to_string(),
+                        "Offset differs".to_string(),
+                        date::to_datestr(epoch_sec, 0),
+                        to_offset_cell(&embedded),
+                        to_offset_cell(&system),
+                    ]);
+                }
+            }
+            Err(e) => rows.push(vec![name.to_string(), "Unreadable".to_string(), "".to_string(), "".to_string(), e]),
+        }
+    }
+
+    for name in list_zone_names(dir) {
+        if name.parse::<Tz>().is_err() {
+            rows.push(vec![
+                name,
+                "Missing in epo".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+            ]);
+        }
+    }
+    (header, rows)
+}
+
+/// Returns the first instant in [start, end) where the offsets differ.
+pub fn find_difference(tz: Tz, rules: &ZoneRules, start: i64, end: i64) -> Option<(i64, date::OffsetInfo, date::OffsetInfo)> {
+    let embedded_changes = transition::find_transitions(tz, start, end);
+    let system_changes = transition::find_offset_changes(start, end, |epoch_sec| rules.offset_info(epoch_sec));
+
+    let mut epochs: Vec<i64> = vec![start];
+    epochs.extend(embedded_changes.iter().map(|t| t.epoch_sec));
+    epochs.extend(system_changes.iter().map(|t| t.epoch_sec));
+    epochs.sort_unstable();
+    epochs.dedup();
+
+    epochs.into_iter().find_map(|epoch_sec| {
+        let embedded = date::offset_info_with_tz(epoch_sec, tz);
+        let system = rules.offset_info(epoch_sec);
+        if embedded.offset_sec != system.offset_sec {
+            return Some((epoch_sec, embedded, system));
+        }
+        None
+    })
+}
+
+/// Lists the names of the TZif files in a zoneinfo directory (e.g. "Asia/Tokyo").
+pub fn list_zone_names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    collect_zone_names(dir, "", &mut names);
+    names.sort();
+    names
+}
+
+fn collect_zone_names(dir: &Path, prefix: &str, names: &mut Vec<String>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if prefix.is_empty() && NON_ZONE_NAMES.contains(&file_name.as_str()) {
+            continue;
+        }
+        let name = format!("{}{}", prefix, file_name);
+        let path = entry.path();
+        if path.is_dir() {
+            collect_zone_names(&path, &format!("{}/", name), names);
+        } else if is_tzif_file(&path) {
+            names.push(name);
+        }
+    }
+}
+
+fn is_tzif_file(path: &Path) -> bool {
+    use std::io::Read;
+    let mut magic = [0u8; 4];
+    match std::fs::File::open(path) {
+        Ok(mut file) => file.read_exact(&mut magic).is_ok() && &magic == b"TZif",
+        Err(_) => false,
+    }
+}
+
+fn to_offset_cell(offset_info: &date::OffsetInfo) -> String {
+    format!("{} ({})", date::to_offset_str(offset_info.offset_sec), offset_info.abbreviation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::zic;
+    use super::*;
+
+    #[test]
+    fn test_embedded_tzdata_version() {
+        // Only 2023b delayed DST in Lebanon to 2023-04-21; 2023c reverted it
+        assert_eq!("2023b", EMBEDDED_TZDATA_VERSION);
+        // 2023-04-01T00:00:00Z
+        assert_eq!(7200, date::offset_info_with_tz(1680307200, Tz::Asia__Beirut).offset_sec);
+        // Egypt resumed DST in 2023a
+        assert_eq!(10800, date::offset_info_with_tz(1688169600, Tz::Africa__Cairo).offset_sec);
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            Some("2023c".to_string()),
+            parse_version("# version 2023c\n# This zic input file is in the public domain.\n")
+        );
+        assert_eq!(None, parse_version("R d 1916 o - May 14 23 1 S\n"));
+    }
+
+    #[test]
+    fn test_find_difference() {
+        let text = "
+Rule	EU	1981	max	-	Mar	lastSun	 1:00u	1:00	S
+Rule	EU	1996	max	-	Oct	lastSun	 1:00u	0	-
+Zone	Europe/London	0:00	EU	GMT/BST	2024 Oct 27 1:00u
+			1:00	-	+01
+";
+        let zones = zic::compile(text, "test").unwrap();
+        let start = transition::year_start(2022);
+
+        assert!(find_difference(Tz::Europe__London, &zones[0], start, transition::year_start(2024)).is_none());
+
+        let (epoch_sec, embedded, system) = find_difference(Tz::Europe__London, &zones[0], start, transition::year_start(2026)).unwrap();
+        // 2024-10-27T01:00:00Z
+        assert_eq!(1729990800, epoch_sec);
+        assert_eq!("+0000 (GMT)", to_offset_cell(&embedded));
+        assert_eq!("+0100 (+01)", to_offset_cell(&system));
+    }
+}
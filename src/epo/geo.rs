@@ -0,0 +1,291 @@
+/// Finds the zone of a coordinate such as "35.68,139.76" (latitude, longitude).
+/// Land uses simplified boundaries, so points near a border or a coast may get the neighboring zone.
+/// Points outside every boundary are at sea and get the nautical zone of the longitude (e.g. "Etc/GMT-9").
+pub fn find_zone(lat: f64, lon: f64) -> String {
+    for (zone, polygon) in regions() {
+        if contains(polygon, lat, lon) {
+            return zone.to_string();
+        }
+    }
+    nautical_zone(lon)
+}
+
+/// Parses "geo:35.68,139.76" into a latitude and a longitude.
+pub fn parse_geo_str(arg: &str) -> Option<Result<(f64, f64), String>> {
+    let value = arg.strip_prefix("geo:")?;
+    let error = || format!("Invalid coordinates: {}", arg);
+    let r = value.split_once(',').ok_or_else(error).and_then(|(lat, lon)| {
+        let lat: f64 = lat.trim().parse().map_err(|_| error())?;
+        let lon: f64 = lon.trim().parse().map_err(|_| error())?;
+        if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+            return Err(error());
+        }
+        Ok((lat, lon))
+    });
+    Some(r)
+}
+
+/// Zones at sea are 15 degrees wide and centered on multiples of 15 degrees; the sign of Etc/GMT is inverted.
+fn nautical_zone(lon: f64) -> String {
+    let hours = (lon / 15.0).round() as i32;
+    match hours {
+        0 => "Etc/GMT".to_string(),
+        h if h > 0 => format!("Etc/GMT-{}", h),
+        h => format!("Etc/GMT+{}", -h),
+    }
+}
+
+/// Ray casting: counts the edges crossed by a ray from the point toward the east.
+fn contains(polygon: &[(f64, f64)], lat: f64, lon: f64) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len() - 1;
+    for i in 0..polygon.len() {
+        let (lat_i, lon_i) = polygon[i];
+        let (lat_j, lon_j) = polygon[j];
+        if (lat_i > lat) != (lat_j > lat) {
+            let cross_lon = lon_i + (lat - lat_i) / (lat_j - lat_i) * (lon_j - lon_i);
+            if lon < cross_lon {
+                inside = !inside;
+            }
+        }
+        j = i;
+    }
+    inside
+}
+
+// Simplified zone boundaries as (latitude, longitude) polygons.
+// Regions are tested in order, so smaller regions come before the larger ones around them.
+
+#[rustfmt::skip]
+fn regions() -> Vec<(&'static str, &'static [(f64, f64)])> {
+    vec![
+        // North America
+        ("America/Phoenix", &[(37.0, -114.05), (37.0, -109.05), (31.33, -109.05), (31.33, -111.07), (32.5, -114.8), (35.0, -114.6), (36.0, -114.05)]),
+        ("America/Los_Angeles", &[(49.0, -125.0), (49.0, -116.05), (46.0, -116.9), (44.5, -117.2), (42.0, -117.0), (42.0, -114.05), (36.0, -114.05), (35.0, -114.6), (32.7, -114.7), (32.5, -117.3), (34.0, -120.8), (40.0, -124.8)]),
+        ("America/Denver", &[(49.0, -116.05), (49.0, -104.05), (45.9, -104.05), (45.9, -100.5), (43.0, -100.5), (41.0, -101.5), (41.0, -102.05), (37.0, -102.05), (37.0, -103.0), (32.0, -103.05), (31.8, -106.5), (31.33, -108.2), (31.33, -109.05), (37.0, -109.05), (37.0, -114.05), (42.0, -114.05), (42.0, -117.0), (44.5, -117.2), (46.0, -116.9)]),
+        ("America/New_York", &[(47.5, -68.9), (45.0, -71.5), (45.0, -74.7), (43.5, -79.2), (42.3, -83.1), (46.0, -84.2), (47.6, -87.9), (45.8, -87.0), (41.7, -87.0), (38.9, -87.5), (37.9, -86.5), (36.6, -85.8), (35.0, -85.4), (33.0, -85.2), (31.0, -85.0), (29.4, -85.1), (24.3, -82.2), (24.3, -80.0), (27.0, -79.7), (31.0, -80.8), (35.2, -75.2), (38.8, -74.5), (40.5, -71.5), (41.2, -69.8), (42.5, -70.3), (44.0, -68.3), (44.8, -66.8)]),
+        ("America/Chicago", &[(49.0, -104.05), (49.0, -95.2), (48.2, -89.4), (46.6, -90.4), (45.8, -87.0), (41.7, -87.0), (38.9, -87.5), (37.9, -86.5), (36.6, -85.8), (35.0, -85.4), (33.0, -85.2), (31.0, -85.0), (29.4, -85.1), (28.5, -89.5), (29.0, -94.5), (26.0, -97.0), (25.8, -97.2), (26.3, -98.5), (27.5, -99.5), (29.3, -100.9), (29.8, -101.4), (29.1, -103.2), (29.8, -104.6), (31.8, -106.5)]),
+        ("America/Anchorage", &[(71.5, -156.8), (69.6, -141.0), (60.3, -141.0), (59.5, -139.5), (58.0, -136.0), (54.7, -130.6), (54.5, -133.0), (58.0, -138.0), (59.5, -146.0), (57.0, -154.0), (54.5, -162.0), (52.5, -172.0), (53.5, -172.0), (55.5, -163.0), (60.0, -166.0), (63.0, -168.0), (66.0, -168.5), (68.0, -166.0)]),
+        ("Pacific/Honolulu", &[(22.4, -160.4), (22.4, -159.2), (21.2, -157.5), (20.9, -156.0), (19.9, -154.7), (18.8, -155.7), (19.7, -156.2), (20.8, -157.0), (21.5, -158.4)]),
+        ("America/Vancouver", &[(60.0, -139.1), (60.0, -120.0), (53.8, -120.0), (49.0, -114.05), (49.0, -125.0), (51.0, -128.5), (54.3, -133.3), (54.7, -130.6), (58.0, -136.0), (59.5, -137.5)]),
+        ("America/Edmonton", &[(60.0, -120.0), (60.0, -110.0), (49.0, -110.0), (49.0, -114.05), (53.8, -120.0)]),
+        ("America/Regina", &[(60.0, -110.0), (60.0, -102.0), (49.0, -101.4), (49.0, -110.0)]),
+        ("America/Winnipeg", &[(60.0, -102.0), (60.0, -94.8), (56.9, -89.0), (52.8, -89.5), (49.0, -95.2), (49.0, -101.4)]),
+        ("America/St_Johns", &[(51.7, -55.5), (51.6, -53.5), (49.6, -53.4), (46.5, -52.8), (46.6, -53.8), (47.5, -59.4), (48.6, -59.0), (50.0, -57.8), (51.5, -57.0)]),
+        ("America/Halifax", &[(48.1, -64.4), (47.0, -61.0), (46.0, -59.6), (43.3, -65.8), (44.6, -67.0), (45.1, -67.2), (47.0, -67.8), (48.0, -69.0)]),
+        ("America/Toronto", &[(63.0, -95.0), (56.9, -89.0), (52.8, -89.5), (49.0, -95.2), (48.2, -89.4), (47.6, -87.9), (46.0, -84.2), (42.3, -83.1), (41.7, -82.6), (43.5, -79.2), (45.0, -74.7), (45.0, -71.5), (47.5, -68.9), (48.0, -69.0), (48.1, -64.4), (50.2, -60.0), (52.0, -57.1), (55.0, -60.0), (60.3, -64.5), (62.5, -72.0), (63.0, -80.0)]),
+        ("America/Tijuana", &[(32.7, -117.3), (32.7, -114.7), (31.5, -114.8), (28.0, -114.0), (28.0, -115.3), (30.0, -116.2)]),
+        ("America/Hermosillo", &[(32.5, -114.8), (31.33, -111.07), (31.33, -108.2), (28.0, -108.5), (26.3, -108.6), (26.5, -109.5), (28.0, -111.5), (31.5, -114.8)]),
+        ("America/Mazatlan", &[(28.0, -114.0), (28.0, -108.5), (26.3, -108.6), (23.0, -105.7), (22.4, -105.5), (22.5, -106.5), (23.0, -109.5), (24.0, -110.7), (26.5, -112.5)]),
+        ("America/Cancun", &[(21.6, -87.0), (21.6, -87.6), (19.7, -89.15), (17.8, -89.15), (18.2, -87.8), (20.0, -87.3)]),
+        ("America/Mexico_City", &[(31.8, -106.5), (29.8, -104.6), (29.1, -103.2), (29.8, -101.4), (29.3, -100.9), (27.5, -99.5), (26.3, -98.5), (25.8, -97.2), (23.0, -97.6), (19.0, -95.8), (18.3, -94.2), (18.6, -91.5), (19.7, -89.15), (17.8, -89.15), (17.8, -91.0), (16.1, -91.7), (14.5, -92.2), (15.8, -95.5), (16.5, -99.0), (18.2, -103.5), (20.5, -105.6), (22.4, -105.5), (23.0, -105.7), (26.3, -108.6), (28.0, -108.5), (31.33, -108.2)]),
+        ("America/Guatemala", &[(17.8, -91.0), (17.8, -89.15), (15.9, -88.9), (14.4, -89.6), (13.7, -90.1), (14.0, -91.8), (14.5, -92.2), (16.1, -91.7)]),
+        ("America/Havana", &[(23.3, -84.9), (23.2, -80.5), (22.2, -77.5), (20.2, -74.1), (19.8, -75.5), (19.8, -77.7), (21.4, -80.5), (21.8, -84.5)]),
+        ("America/Port-au-Prince", &[(20.1, -72.8), (19.7, -71.75), (18.0, -71.75), (18.0, -74.5), (18.7, -74.5), (19.9, -73.4)]),
+        ("America/Santo_Domingo", &[(19.9, -71.75), (20.0, -70.0), (19.7, -69.0), (19.0, -68.3), (18.4, -68.3), (17.6, -71.4), (18.0, -71.75)]),
+        // South America
+        ("America/Bogota", &[(12.5, -71.6), (11.0, -72.5), (7.9, -72.5), (6.2, -69.4), (4.0, -67.8), (1.2, -66.9), (-4.2, -69.9), (-2.4, -71.0), (0.4, -75.3), (1.5, -78.9), (7.2, -77.9), (8.7, -77.2), (9.5, -76.0), (11.3, -74.2)]),
+        ("America/Caracas", &[(12.2, -72.0), (11.8, -68.0), (10.9, -63.0), (10.2, -61.9), (8.6, -59.8), (7.3, -60.5), (5.2, -60.7), (4.0, -62.8), (1.2, -64.1), (1.2, -66.9), (4.0, -67.8), (6.2, -69.4), (7.9, -72.5), (11.0, -72.5)]),
+        ("America/Guayaquil", &[(1.5, -78.9), (0.4, -75.3), (-1.5, -75.5), (-2.6, -76.6), (-4.2, -78.5), (-5.0, -79.0), (-4.4, -80.4), (-2.5, -81.0), (0.9, -80.2)]),
+        ("America/Lima", &[(-0.2, -75.2), (-4.2, -69.9), (-9.5, -72.3), (-11.0, -69.6), (-12.5, -68.7), (-15.0, -69.3), (-17.5, -69.5), (-18.4, -70.4), (-15.5, -75.3), (-12.0, -77.3), (-7.0, -80.0), (-4.4, -81.4), (-3.4, -80.3), (-5.0, -79.0), (-2.6, -76.6)]),
+        ("America/La_Paz", &[(-9.7, -65.4), (-11.0, -69.6), (-12.5, -68.7), (-15.0, -69.3), (-17.5, -69.5), (-18.4, -69.1), (-19.0, -68.4), (-21.0, -68.2), (-22.9, -67.2), (-22.1, -62.8), (-20.0, -58.2), (-16.3, -58.3), (-16.3, -60.2), (-13.6, -61.8), (-11.9, -65.3)]),
+        ("America/Santiago", &[(-17.5, -69.5), (-18.4, -69.1), (-19.0, -68.4), (-21.0, -68.2), (-22.9, -67.2), (-24.3, -68.4), (-28.0, -69.1), (-33.0, -70.0), (-37.0, -71.2), (-41.0, -71.9), (-46.0, -71.8), (-50.0, -73.4), (-52.0, -72.0), (-55.9, -67.3), (-55.0, -72.0), (-50.0, -76.0), (-42.0, -74.5), (-37.0, -73.8), (-30.0, -71.8), (-18.4, -70.4)]),
+        ("America/Argentina/Buenos_Aires", &[(-22.1, -62.8), (-22.9, -65.5), (-22.9, -67.2), (-24.3, -68.4), (-28.0, -69.1), (-33.0, -70.0), (-37.0, -71.2), (-41.0, -71.9), (-46.0, -71.8), (-50.0, -73.4), (-52.0, -72.0), (-55.1, -66.5), (-52.4, -68.3), (-50.0, -68.5), (-47.0, -65.7), (-42.0, -63.5), (-38.9, -62.0), (-36.3, -56.7), (-34.4, -58.4), (-33.0, -58.4), (-30.2, -57.6), (-27.3, -55.6), (-25.6, -54.6), (-27.5, -58.6), (-25.3, -57.6), (-22.1, -62.6)]),
+        ("America/Asuncion", &[(-19.3, -59.1), (-20.0, -58.2), (-22.1, -62.6), (-25.3, -57.6), (-27.5, -58.6), (-27.1, -55.7), (-25.6, -54.6), (-24.0, -54.3), (-22.5, -55.7), (-22.1, -58.0)]),
+        ("America/Montevideo", &[(-30.2, -57.6), (-33.0, -58.4), (-34.4, -58.4), (-34.9, -56.2), (-34.7, -54.0), (-33.7, -53.4), (-32.6, -53.1), (-30.1, -55.8)]),
+        ("America/Manaus", &[(1.2, -64.1), (4.0, -62.8), (5.2, -60.7), (3.8, -59.5), (1.5, -58.9), (-1.5, -56.5), (-8.5, -57.9), (-9.7, -61.5), (-11.9, -65.3), (-9.7, -65.4), (-10.9, -68.5), (-9.5, -72.3), (-4.2, -69.9), (1.2, -66.9)]),
+        ("America/Sao_Paulo", &[(5.3, -60.1), (5.0, -51.6), (2.0, -50.0), (-1.0, -48.0), (-2.6, -41.0), (-5.1, -35.3), (-8.0, -34.7), (-13.0, -38.3), (-23.0, -42.0), (-25.5, -48.2), (-28.5, -48.7), (-33.7, -53.4), (-32.6, -53.1), (-30.1, -55.8), (-27.1, -55.7), (-25.6, -54.6), (-24.0, -54.3), (-22.5, -55.7), (-22.1, -58.0), (-19.3, -59.1), (-16.3, -58.3), (-16.3, -60.2), (-13.6, -61.8), (-11.9, -65.3), (-9.7, -61.5), (-8.5, -57.9), (-1.5, -56.5), (1.5, -58.9), (3.8, -59.5)]),
+        // Europe
+        ("Atlantic/Reykjavik", &[(66.7, -23.5), (66.6, -14.5), (65.3, -13.4), (63.3, -18.0), (63.7, -22.8), (65.0, -24.6)]),
+        ("Europe/Dublin", &[(55.4, -7.3), (54.6, -5.7), (54.0, -6.3), (52.1, -6.3), (51.4, -9.6), (53.0, -10.3), (55.0, -8.5)]),
+        ("Europe/London", &[(58.7, -3.0), (57.7, -1.7), (55.8, -1.7), (53.5, 0.3), (52.9, 1.8), (51.3, 1.5), (50.7, 0.3), (50.0, -5.8), (51.6, -5.3), (53.4, -4.7), (54.6, -5.7), (55.4, -7.3), (57.3, -7.6), (58.6, -5.1)]),
+        ("Europe/Lisbon", &[(42.1, -8.9), (42.0, -6.5), (41.0, -6.9), (39.6, -7.5), (38.2, -7.0), (37.2, -7.4), (36.9, -8.9), (38.7, -9.6), (41.0, -8.8)]),
+        ("Atlantic/Canary", &[(29.5, -18.3), (29.5, -13.3), (27.5, -13.3), (27.5, -18.3)]),
+        ("Europe/Helsinki", &[(70.1, 27.7), (68.9, 28.4), (66.9, 29.1), (64.0, 30.1), (62.0, 31.6), (60.5, 27.8), (59.8, 23.0), (60.5, 21.2), (63.5, 21.3), (65.8, 24.2), (68.4, 21.0), (69.0, 20.6)]),
+        ("Europe/Tallinn", &[(59.6, 23.3), (59.5, 28.2), (58.0, 27.6), (57.5, 27.4), (57.9, 24.3), (58.5, 21.8)]),
+        ("Europe/Riga", &[(57.9, 24.3), (57.5, 27.4), (56.1, 28.2), (55.7, 26.6), (56.3, 21.0), (57.7, 21.7)]),
+        ("Europe/Vilnius", &[(56.3, 21.0), (55.7, 26.6), (54.0, 25.8), (53.9, 23.5), (55.1, 22.7), (55.3, 21.2)]),
+        ("Europe/Kaliningrad", &[(55.3, 21.2), (55.1, 22.7), (54.3, 22.8), (54.4, 19.6)]),
+        ("Europe/Minsk", &[(56.1, 28.2), (55.7, 30.9), (53.8, 32.7), (52.1, 31.8), (51.5, 30.6), (51.9, 23.6), (53.9, 23.5), (54.0, 25.8), (55.7, 26.6)]),
+        ("Europe/Kyiv", &[(51.9, 23.6), (51.5, 30.6), (52.1, 31.8), (52.4, 34.0), (50.4, 35.5), (49.6, 40.1), (47.8, 39.8), (46.6, 37.5), (46.0, 35.0), (44.4, 33.4), (45.4, 32.5), (46.6, 30.8), (45.3, 29.7), (45.5, 28.2), (48.0, 27.0), (48.4, 22.6)]),
+        ("Europe/Chisinau", &[(48.4, 27.0), (48.0, 27.0), (45.5, 28.2), (46.4, 30.1), (47.6, 29.2)]),
+        ("Europe/Bucharest", &[(48.3, 26.6), (48.0, 22.9), (46.2, 20.3), (45.0, 21.4), (44.2, 22.7), (43.7, 24.0), (43.9, 27.0), (43.7, 28.6), (45.2, 29.7), (45.5, 28.2)]),
+        ("Europe/Sofia", &[(44.2, 22.7), (43.2, 22.4), (42.3, 22.4), (41.3, 22.9), (41.4, 26.1), (42.0, 27.9), (43.7, 28.6), (43.9, 27.0), (43.7, 24.0)]),
+        ("Europe/Athens", &[(41.3, 22.9), (40.9, 20.9), (39.6, 20.0), (37.0, 21.5), (36.3, 22.5), (34.8, 24.5), (35.1, 26.5), (36.5, 28.0), (38.5, 26.4), (40.7, 26.3), (41.4, 26.1)]),
+        ("Europe/Istanbul", &[(42.0, 27.9), (41.4, 26.1), (40.7, 26.3), (39.5, 26.0), (36.7, 27.3), (36.2, 30.0), (36.1, 32.5), (36.0, 35.8), (36.8, 36.6), (36.8, 38.5), (37.1, 42.3), (37.3, 44.8), (39.8, 44.8), (41.2, 43.4), (41.5, 41.5), (41.0, 38.0), (42.0, 35.0), (41.5, 31.0)]),
+        ("Europe/Moscow", &[(69.9, 30.8), (66.0, 29.1), (62.0, 31.6), (60.5, 27.8), (59.5, 28.2), (56.1, 28.2), (55.7, 30.9), (53.8, 32.7), (52.4, 34.0), (50.4, 35.5), (49.6, 40.1), (47.8, 39.8), (46.6, 37.5), (44.5, 38.0), (43.4, 40.0), (41.2, 47.8), (43.9, 47.5), (46.5, 48.9), (51.5, 46.5), (53.5, 48.0), (55.5, 52.0), (58.5, 57.0), (61.5, 59.3), (67.5, 66.0), (70.0, 60.0), (68.5, 44.0), (66.0, 40.0), (67.0, 33.0)]),
+        ("Europe/Berlin", &[(54.9, 8.3), (55.1, 9.9), (54.4, 13.5), (53.9, 14.3), (52.4, 14.6), (50.9, 15.0), (50.3, 12.1), (48.6, 13.8), (47.5, 13.0), (47.3, 10.2), (47.6, 7.6), (49.0, 8.2), (49.4, 6.4), (50.8, 6.0), (51.8, 6.0), (53.6, 7.0)]),
+        ("Europe/Paris", &[(51.1, 2.5), (50.1, 4.2), (49.5, 6.0), (49.0, 8.2), (47.6, 7.6), (46.2, 6.1), (45.1, 7.1), (43.8, 7.5), (43.1, 6.0), (43.5, 3.3), (42.4, 3.2), (42.8, -1.8), (43.4, -1.8), (46.0, -1.4), (47.3, -2.8), (48.4, -4.8), (48.9, -1.6), (49.7, -1.3), (49.6, 0.1), (50.5, 1.6)]),
+        ("Europe/Madrid", &[(43.8, -8.0), (43.4, -1.8), (42.8, -1.8), (42.4, 3.2), (41.0, 1.0), (38.7, 0.2), (37.5, -0.7), (36.7, -2.1), (36.0, -5.6), (37.2, -7.4), (38.2, -7.0), (39.6, -7.5), (41.0, -6.9), (42.0, -6.5), (42.1, -8.9), (43.2, -9.3)]),
+        ("Europe/Rome", &[(47.1, 12.2), (46.5, 13.7), (45.6, 13.8), (44.0, 12.6), (42.0, 14.6), (41.0, 17.5), (40.0, 18.6), (38.0, 16.1), (37.9, 15.6), (36.6, 15.1), (38.2, 12.4), (38.8, 16.5), (40.0, 15.6), (41.2, 13.4), (42.4, 11.1), (44.0, 10.0), (44.3, 8.0), (43.8, 7.5), (45.1, 7.1), (46.2, 6.1), (46.5, 9.0), (46.9, 10.5)]),
+        ("Europe/Warsaw", &[(54.8, 18.3), (54.4, 19.6), (54.3, 22.8), (53.9, 23.5), (51.9, 23.6), (50.5, 24.1), (49.0, 22.6), (49.4, 20.0), (49.6, 18.8), (50.3, 16.9), (50.9, 15.0), (52.4, 14.6), (53.9, 14.3), (54.5, 16.5)]),
+        ("Europe/Copenhagen", &[(57.8, 10.6), (56.1, 12.6), (55.6, 12.7), (54.95, 12.6), (54.55, 11.9), (54.7, 10.7), (54.85, 9.9), (54.9, 8.6), (55.6, 8.0), (56.6, 8.1), (57.1, 8.6)]),
+        ("Europe/Stockholm", &[(69.0, 20.6), (68.4, 21.0), (65.8, 24.2), (63.5, 21.3), (60.6, 19.3), (59.4, 19.3), (58.7, 17.8), (57.9, 19.2), (57.0, 18.9), (56.2, 16.6), (55.4, 14.2), (55.5, 12.8), (57.6, 11.7), (59.1, 11.1), (61.5, 12.5), (64.0, 14.0), (66.0, 15.5), (68.5, 18.0)]),
+        ("Europe/Oslo", &[(71.2, 25.8), (70.1, 31.0), (69.0, 29.0), (70.1, 27.7), (69.0, 20.6), (68.5, 18.0), (66.0, 15.5), (64.0, 14.0), (61.5, 12.5), (59.1, 11.1), (58.0, 7.0), (58.5, 5.5), (61.0, 4.6), (63.0, 6.8), (66.0, 12.5), (68.5, 14.5), (70.0, 19.0)]),
+        // Central European Time around the zones listed above
+        ("Europe/Brussels", &[(58.0, 7.0), (57.7, 10.6), (56.0, 12.7), (55.5, 16.0), (54.5, 16.5), (54.8, 18.3), (54.4, 19.6), (54.3, 22.8), (53.9, 23.5), (51.9, 23.6), (50.5, 24.1), (48.4, 22.6), (48.0, 22.9), (46.2, 20.3), (45.0, 21.4), (44.2, 22.7), (43.2, 22.4), (42.3, 22.4), (41.3, 22.9), (40.9, 20.9), (39.6, 20.0), (40.0, 18.6), (43.0, 17.0), (45.6, 13.8), (46.5, 13.7), (47.1, 12.2), (46.9, 10.5), (46.5, 9.0), (46.2, 6.1), (47.6, 7.6), (49.0, 8.2), (49.5, 6.0), (50.1, 4.2), (51.1, 2.5), (51.5, 3.4), (53.5, 5.0), (53.6, 7.0), (54.9, 8.3), (57.0, 8.0)]),
+        // Asia
+        ("Asia/Tokyo", &[(45.6, 141.3), (44.5, 145.8), (43.0, 146.0), (41.0, 142.0), (38.0, 142.2), (35.0, 140.9), (34.5, 139.0), (33.2, 135.8), (31.0, 131.5), (30.8, 130.0), (31.5, 129.5), (33.2, 129.2), (34.5, 130.8), (35.6, 133.0), (37.2, 136.6), (38.5, 139.3), (41.3, 139.9), (42.5, 139.6)]),
+        ("Asia/Tokyo", &[(29.0, 128.5), (29.0, 131.5), (24.0, 131.5), (24.0, 122.8)]),
+        ("Asia/Seoul", &[(38.6, 128.4), (37.0, 129.6), (35.1, 129.3), (34.2, 126.6), (33.1, 126.3), (35.0, 126.1), (37.7, 126.1)]),
+        ("Asia/Pyongyang", &[(42.9, 130.7), (42.3, 130.6), (40.0, 128.3), (38.6, 128.4), (37.7, 126.1), (38.0, 124.6), (39.8, 124.3), (41.0, 126.5), (42.0, 128.0)]),
+        ("Asia/Taipei", &[(25.4, 121.6), (24.0, 121.9), (21.8, 120.9), (22.5, 120.2), (24.5, 120.4)]),
+        ("Asia/Hong_Kong", &[(22.6, 113.8), (22.6, 114.5), (22.1, 114.5), (22.1, 113.8)]),
+        ("Asia/Manila", &[(21.0, 121.0), (18.5, 122.5), (14.0, 124.5), (12.0, 125.8), (9.0, 126.6), (5.5, 125.8), (5.8, 121.0), (8.0, 117.0), (10.5, 118.5), (12.0, 120.0), (14.5, 119.8), (16.5, 119.7), (18.5, 120.5)]),
+        ("Asia/Ho_Chi_Minh", &[(23.4, 105.3), (22.5, 107.0), (21.5, 108.0), (18.0, 106.7), (15.0, 109.0), (12.0, 109.3), (10.5, 107.5), (8.6, 104.8), (10.4, 104.4), (11.5, 106.0), (14.7, 107.5), (16.5, 106.9), (18.0, 105.0), (19.5, 104.0), (21.5, 103.0), (22.5, 102.2)]),
+        ("Asia/Yangon", &[(28.5, 97.5), (26.5, 98.7), (24.0, 97.7), (21.8, 101.1), (20.4, 100.1), (18.5, 97.6), (16.0, 98.6), (10.0, 98.5), (15.0, 97.5), (16.5, 94.3), (20.0, 93.0), (21.0, 92.2), (22.5, 93.2), (24.0, 94.2), (26.0, 95.0), (27.3, 97.0)]),
+        ("Asia/Bangkok", &[(22.5, 102.2), (21.5, 103.0), (19.5, 104.0), (18.0, 105.0), (16.5, 106.9), (14.7, 107.5), (11.5, 106.0), (10.4, 104.4), (11.6, 102.9), (12.6, 101.0), (9.0, 99.9), (6.4, 101.2), (6.5, 99.7), (10.0, 98.5), (16.0, 98.6), (18.5, 97.6), (20.4, 100.1), (21.8, 101.1)]),
+        ("Asia/Singapore", &[(1.5, 103.6), (1.5, 104.1), (1.2, 104.1), (1.2, 103.6)]),
+        ("Asia/Kuala_Lumpur", &[(6.4, 101.2), (6.2, 102.3), (4.0, 103.5), (1.4, 104.3), (1.3, 103.5), (2.5, 101.5), (6.5, 99.7)]),
+        ("Asia/Kuching", &[(7.3, 116.8), (5.0, 119.3), (4.3, 117.6), (2.0, 114.5), (1.0, 110.5), (1.5, 109.6), (2.5, 111.0), (4.5, 114.0), (6.0, 115.9)]),
+        ("Asia/Jakarta", &[(5.9, 95.2), (1.5, 98.5), (-2.0, 101.0), (-5.9, 106.1), (-6.8, 114.0), (-8.8, 114.6), (-8.2, 110.5), (-7.7, 106.0), (-5.9, 104.5), (-3.5, 102.0), (0.0, 99.5), (2.3, 97.0)]),
+        ("Asia/Jakarta", &[(2.1, 108.8), (1.0, 110.5), (1.2, 114.0), (-3.0, 114.5), (-4.2, 111.0), (-3.0, 110.0), (-1.0, 108.9)]),
+        ("Asia/Makassar", &[(4.3, 117.6), (1.5, 119.0), (1.6, 125.3), (-1.0, 123.5), (-5.6, 122.8), (-5.6, 119.3), (-8.4, 116.0), (-10.3, 119.0), (-10.0, 125.0), (-8.0, 125.0), (-3.0, 116.0), (-3.0, 114.5), (1.2, 114.0)]),
+        ("Asia/Jayapura", &[(-0.3, 130.8), (-1.5, 134.0), (-2.4, 141.0), (-9.1, 141.0), (-8.0, 138.0), (-4.0, 133.0), (-3.5, 132.0), (-1.3, 130.9)]),
+        ("Asia/Kathmandu", &[(30.2, 81.0), (29.3, 83.5), (28.0, 86.0), (27.9, 88.2), (26.4, 88.1), (26.4, 87.0), (27.4, 83.3), (28.7, 80.1)]),
+        ("Asia/Thimphu", &[(28.3, 89.0), (28.0, 92.1), (26.8, 92.1), (26.7, 89.7)]),
+        ("Asia/Kolkata", &[(35.5, 77.8), (32.5, 79.5), (30.2, 81.0), (28.0, 84.0), (26.5, 88.1), (27.9, 88.2), (27.9, 92.0), (29.0, 94.0), (28.2, 97.3), (27.3, 97.0), (26.0, 95.0), (24.0, 94.2), (22.5, 93.2), (24.0, 92.2), (25.2, 92.0), (25.2, 90.0), (26.0, 89.8), (25.2, 88.5), (24.0, 88.7), (22.0, 89.0), (21.5, 87.0), (19.5, 85.0), (16.0, 82.3), (13.5, 80.3), (10.3, 79.9), (8.1, 77.5), (10.0, 76.2), (15.0, 73.9), (19.0, 72.8), (22.0, 69.0), (23.7, 68.2), (24.4, 71.0), (27.5, 70.3), (30.0, 73.4), (32.5, 74.6), (34.5, 73.8), (35.5, 76.0)]),
+        ("Asia/Colombo", &[(9.9, 80.0), (8.5, 81.4), (6.5, 81.9), (5.9, 80.6), (6.6, 79.8), (8.5, 79.7)]),
+        ("Asia/Dhaka", &[(26.6, 88.5), (26.0, 89.8), (25.2, 90.0), (25.2, 92.0), (24.0, 92.2), (22.5, 93.2), (21.0, 92.2), (22.0, 89.0), (24.0, 88.7), (25.2, 88.5)]),
+        ("Asia/Karachi", &[(37.0, 74.5), (35.5, 77.8), (35.5, 76.0), (34.5, 73.8), (32.5, 74.6), (30.0, 73.4), (27.5, 70.3), (24.4, 71.0), (23.7, 68.2), (25.0, 66.5), (25.2, 61.6), (29.8, 60.9), (29.5, 62.5), (31.5, 66.5), (33.9, 70.0), (35.5, 71.5)]),
+        ("Asia/Kabul", &[(38.5, 71.0), (37.0, 74.5), (35.5, 71.5), (33.9, 70.0), (31.5, 66.5), (29.5, 62.5), (29.8, 60.9), (31.5, 61.7), (34.5, 61.0), (35.6, 62.3), (36.6, 65.0), (37.3, 67.8)]),
+        ("Asia/Tehran", &[(39.8, 44.8), (39.4, 48.0), (38.4, 48.9), (37.0, 54.0), (37.5, 57.3), (36.0, 61.2), (34.5, 61.0), (31.5, 61.7), (29.8, 60.9), (25.2, 61.6), (25.5, 57.3), (27.1, 56.0), (26.5, 54.0), (27.8, 51.3), (30.0, 48.9), (31.0, 47.7), (32.5, 47.4), (34.0, 45.6), (35.8, 46.1), (37.3, 44.8)]),
+        ("Asia/Baghdad", &[(37.3, 42.3), (37.1, 44.8), (35.8, 46.1), (34.0, 45.6), (32.5, 47.4), (31.0, 47.7), (30.0, 48.9), (29.1, 47.7), (29.1, 44.7), (32.2, 39.2), (33.4, 38.8), (34.6, 41.0), (36.4, 41.3)]),
+        ("Asia/Dubai", &[(26.1, 56.1), (24.9, 56.4), (24.1, 55.8), (22.6, 55.2), (24.0, 51.6), (24.2, 52.6)]),
+        ("Asia/Muscat", &[(26.4, 56.4), (24.9, 56.4), (24.1, 55.8), (22.6, 55.2), (19.0, 52.0), (16.6, 53.1), (17.0, 55.3), (19.5, 57.8), (22.5, 59.8), (24.0, 58.0)]),
+        ("Asia/Qatar", &[(26.2, 51.2), (25.0, 51.7), (24.5, 51.2), (24.6, 50.8), (25.7, 50.8)]),
+        ("Asia/Jerusalem", &[(33.3, 35.6), (32.7, 35.6), (31.4, 35.4), (29.5, 35.0), (31.2, 34.2), (32.0, 34.7), (33.1, 35.1)]),
+        ("Asia/Amman", &[(32.7, 35.6), (32.3, 36.8), (33.4, 38.8), (32.2, 39.2), (31.5, 37.0), (29.2, 36.0), (29.5, 35.0), (31.4, 35.4)]),
+        ("Asia/Beirut", &[(34.7, 36.0), (34.6, 36.6), (33.3, 35.8), (33.1, 35.1), (34.0, 35.5)]),
+        ("Asia/Damascus", &[(37.1, 42.3), (36.4, 41.3), (34.6, 41.0), (33.4, 38.8), (32.3, 36.8), (32.7, 35.6), (33.3, 35.8), (34.6, 36.6), (34.7, 36.0), (35.9, 35.8), (36.8, 36.6), (36.8, 38.5)]),
+        ("Asia/Riyadh", &[(32.2, 39.2), (29.1, 44.7), (29.1, 47.7), (28.5, 48.5), (26.5, 50.0), (24.6, 50.8), (24.0, 51.6), (22.6, 55.2), (19.0, 52.0), (16.6, 53.1), (15.5, 52.2), (13.0, 45.0), (12.6, 43.5), (16.0, 42.7), (20.0, 40.0), (24.0, 37.5), (28.0, 34.7), (29.2, 36.0), (31.5, 37.0)]),
+        ("Asia/Baku", &[(41.9, 46.6), (41.2, 49.0), (40.4, 50.4), (38.4, 48.9), (39.4, 48.0), (39.7, 45.8), (41.2, 45.0)]),
+        ("Asia/Tbilisi", &[(43.6, 40.0), (42.3, 44.8), (41.9, 46.6), (41.2, 45.0), (41.2, 43.4), (41.5, 41.5)]),
+        ("Asia/Yerevan", &[(41.2, 45.0), (39.7, 45.8), (38.9, 46.5), (39.8, 44.8), (41.2, 43.4)]),
+        ("Asia/Tashkent", &[(45.6, 58.6), (43.0, 60.0), (41.9, 66.6), (41.2, 69.0), (41.4, 71.0), (41.0, 73.1), (40.0, 71.0), (39.2, 68.0), (37.3, 67.8), (37.3, 66.5), (40.0, 62.5), (41.3, 60.0), (42.5, 56.0), (45.0, 56.0)]),
+        ("Asia/Ashgabat", &[(42.5, 56.0), (41.3, 60.0), (40.0, 62.5), (37.3, 66.5), (35.6, 62.3), (36.0, 61.2), (37.5, 57.3), (37.0, 54.0), (40.0, 52.8), (41.8, 53.0)]),
+        ("Asia/Dushanbe", &[(41.0, 70.6), (39.5, 73.6), (37.0, 74.9), (37.3, 71.5), (37.3, 67.8), (39.2, 68.0), (40.0, 71.0)]),
+        ("Asia/Bishkek", &[(43.3, 74.3), (42.8, 80.2), (41.0, 77.8), (39.5, 73.6), (41.0, 70.6), (42.5, 71.0)]),
+        ("Asia/Almaty", &[(55.4, 69.0), (54.0, 76.0), (51.0, 80.0), (49.2, 87.3), (47.0, 85.5), (45.0, 82.5), (42.8, 80.2), (43.3, 74.3), (42.5, 71.0), (41.0, 70.6), (41.4, 71.0), (41.2, 69.0), (41.9, 66.6), (43.0, 60.0), (45.6, 58.6), (45.0, 56.0), (42.5, 56.0), (41.8, 53.0), (44.0, 50.3), (46.5, 48.9), (48.0, 47.2), (50.0, 46.8), (51.5, 50.5), (51.0, 55.0), (50.8, 61.0), (54.0, 61.3)]),
+        ("Asia/Ulaanbaatar", &[(52.1, 98.9), (50.4, 105.0), (49.6, 116.7), (46.5, 119.9), (44.9, 116.0), (42.6, 111.0), (41.6, 105.0), (42.5, 97.0), (45.0, 90.7), (48.0, 88.0), (50.0, 92.0)]),
+        ("Asia/Shanghai", &[(53.5, 123.5), (48.3, 135.0), (45.0, 133.0), (42.5, 131.0), (42.3, 130.6), (40.0, 124.3), (38.5, 121.5), (37.4, 122.7), (35.0, 119.5), (31.5, 122.0), (28.0, 121.5), (25.0, 119.5), (22.6, 114.5), (21.5, 111.0), (20.2, 110.5), (18.2, 109.5), (20.0, 108.6), (21.5, 108.0), (22.5, 107.0), (23.4, 105.3), (22.5, 102.2), (21.5, 101.1), (24.0, 97.7), (26.5, 98.7), (28.2, 97.3), (29.0, 94.0), (27.9, 92.0), (28.3, 89.0), (27.9, 88.2), (28.0, 86.0), (30.2, 81.0), (32.5, 79.5), (35.5, 77.8), (37.0, 74.9), (39.5, 73.6), (41.0, 77.8), (42.8, 80.2), (45.0, 82.5), (47.0, 85.5), (49.2, 87.3), (48.0, 88.0), (45.0, 90.7), (42.5, 97.0), (41.6, 105.0), (42.6, 111.0), (44.9, 116.0), (46.5, 119.9), (49.6, 116.7), (53.3, 120.0)]),
+        // Russia east of Moscow time, roughly by longitude
+        ("Asia/Yekaterinburg", &[(73.5, 66.0), (67.5, 66.0), (61.5, 59.3), (58.5, 57.0), (55.5, 52.0), (51.0, 55.0), (50.8, 61.0), (54.0, 61.3), (55.4, 69.0), (59.0, 70.0), (62.0, 76.0), (66.0, 78.0), (73.5, 80.0)]),
+        ("Asia/Omsk", &[(59.0, 70.0), (55.4, 69.0), (54.0, 76.0), (56.5, 76.5), (59.0, 76.0)]),
+        ("Asia/Novosibirsk", &[(59.0, 76.0), (56.5, 76.5), (54.0, 76.0), (51.0, 80.0), (49.2, 87.3), (52.0, 89.0), (56.0, 89.0), (60.0, 85.0)]),
+        ("Asia/Krasnoyarsk", &[(78.0, 80.0), (73.5, 80.0), (66.0, 78.0), (62.0, 76.0), (59.0, 76.0), (60.0, 85.0), (56.0, 89.0), (52.0, 89.0), (50.0, 92.0), (52.1, 98.9), (55.0, 98.0), (58.0, 102.0), (65.0, 106.0), (74.0, 112.0)]),
+        ("Asia/Irkutsk", &[(65.0, 106.0), (58.0, 102.0), (55.0, 98.0), (52.1, 98.9), (50.4, 105.0), (49.6, 116.7), (53.3, 120.0), (56.0, 117.0), (58.5, 114.0), (62.0, 110.0)]),
+        ("Asia/Vladivostok", &[(53.5, 123.5), (55.0, 130.0), (56.5, 136.0), (59.0, 142.0), (53.0, 141.5), (47.0, 139.5), (42.5, 131.0), (45.0, 133.0), (48.3, 135.0)]),
+        ("Asia/Sakhalin", &[(54.5, 142.5), (54.0, 143.5), (48.5, 144.5), (46.0, 143.5), (46.0, 141.7), (50.0, 141.9), (52.0, 141.5)]),
+        ("Asia/Kamchatka", &[(62.0, 165.0), (60.0, 165.0), (56.5, 163.5), (51.0, 156.8), (53.0, 155.5), (57.0, 155.8), (60.0, 160.0), (62.0, 160.0)]),
+        ("Asia/Magadan", &[(66.0, 152.0), (62.0, 160.0), (60.0, 160.0), (59.3, 154.0), (59.0, 142.0), (62.0, 145.0), (65.0, 147.0)]),
+        ("Asia/Anadyr", &[(70.0, 180.0), (64.5, 180.0), (62.0, 174.0), (60.0, 165.0), (62.0, 160.0), (66.0, 152.0), (70.0, 160.0)]),
+        ("Asia/Yakutsk", &[(74.0, 112.0), (65.0, 106.0), (62.0, 110.0), (58.5, 114.0), (56.0, 117.0), (53.3, 120.0), (53.5, 123.5), (55.0, 130.0), (56.5, 136.0), (59.0, 142.0), (62.0, 145.0), (65.0, 147.0), (66.0, 152.0), (70.0, 160.0), (73.0, 140.0)]),
+        // Africa
+        ("Africa/Casablanca", &[(35.9, -5.9), (35.1, -2.2), (32.1, -1.2), (29.5, -4.0), (27.7, -8.7), (27.7, -13.2), (31.0, -10.0), (33.7, -8.0)]),
+        ("Africa/Algiers", &[(36.9, 8.6), (37.0, 3.0), (36.1, 0.0), (35.9, -1.0), (35.1, -2.2), (32.1, -1.2), (29.5, -4.0), (27.7, -8.7), (25.0, -4.8), (19.1, 3.3), (19.8, 5.8), (23.5, 11.9), (26.4, 10.0), (30.2, 9.5), (33.2, 7.5)]),
+        ("Africa/Tunis", &[(37.3, 9.8), (36.8, 11.1), (33.2, 11.5), (30.2, 9.5), (33.2, 7.5), (36.9, 8.6)]),
+        ("Africa/Tripoli", &[(33.2, 11.5), (30.0, 20.0), (32.8, 22.5), (31.7, 25.1), (22.0, 25.0), (19.5, 24.0), (23.5, 15.0), (23.5, 11.9), (26.4, 10.0), (30.2, 9.5)]),
+        ("Africa/Cairo", &[(31.7, 25.1), (31.5, 32.0), (31.3, 34.2), (29.5, 35.0), (28.0, 34.7), (24.0, 35.6), (22.0, 37.0), (22.0, 25.0)]),
+        ("Africa/Khartoum", &[(22.0, 25.0), (22.0, 37.0), (18.0, 38.6), (14.4, 36.5), (12.7, 35.9), (10.0, 34.3), (9.5, 32.0), (10.0, 28.0), (9.5, 23.7), (15.7, 23.0), (19.5, 24.0)]),
+        ("Africa/Abidjan", &[(27.7, -13.2), (27.7, -8.7), (25.0, -4.8), (15.0, 4.0), (11.9, 0.9), (11.1, 0.0), (6.1, 1.2), (4.7, -2.0), (4.4, -7.5), (7.0, -11.5), (8.5, -13.3), (11.0, -15.0), (12.5, -17.0), (14.7, -17.7), (21.0, -17.2), (24.0, -16.0)]),
+        ("Africa/Lagos", &[(23.5, 11.9), (23.5, 15.0), (19.5, 24.0), (15.7, 23.0), (9.5, 23.7), (5.0, 27.5), (3.5, 18.6), (-1.0, 16.5), (-4.5, 15.6), (-6.0, 12.3), (-4.5, 11.2), (1.0, 9.3), (4.3, 8.5), (6.3, 3.0), (6.1, 1.2), (11.1, 0.0), (11.9, 0.9), (15.0, 4.0), (19.1, 3.3), (19.8, 5.8)]),
+        ("Africa/Lubumbashi", &[(5.0, 27.5), (4.2, 30.8), (1.0, 30.0), (-1.5, 29.2), (-4.5, 29.5), (-8.5, 30.8), (-12.5, 29.5), (-13.5, 23.0), (-12.0, 22.0), (-7.0, 21.8), (-5.0, 16.5), (-4.5, 15.6), (-1.0, 16.5), (3.5, 18.6)]),
+        ("Africa/Nairobi", &[(18.0, 38.6), (17.0, 43.0), (12.6, 43.5), (11.5, 51.3), (2.0, 46.0), (-4.7, 39.2), (-7.0, 39.5), (-10.5, 40.5), (-11.5, 34.9), (-9.4, 33.0), (-8.5, 30.8), (-4.5, 29.5), (-1.5, 29.6), (1.0, 30.0), (4.2, 30.8), (5.0, 27.5), (9.5, 23.7), (10.0, 28.0), (9.5, 32.0), (10.0, 34.3), (12.7, 35.9), (14.4, 36.5)]),
+        ("Africa/Maputo", &[(-8.5, 30.8), (-9.4, 33.0), (-11.5, 34.9), (-10.5, 40.5), (-15.0, 40.8), (-20.0, 35.0), (-25.0, 33.5), (-26.9, 32.9), (-25.5, 31.9), (-22.3, 31.3), (-22.2, 29.0), (-18.0, 25.0), (-17.8, 20.0), (-13.5, 23.0), (-12.5, 29.5)]),
+        ("Africa/Johannesburg", &[(-22.2, 29.0), (-22.3, 31.3), (-25.5, 31.9), (-26.9, 32.9), (-29.0, 32.5), (-34.0, 26.0), (-34.9, 20.0), (-34.2, 18.3), (-28.6, 16.4), (-28.7, 20.0), (-24.8, 20.0), (-25.9, 25.0)]),
+        ("Africa/Windhoek", &[(-17.4, 11.7), (-17.8, 20.0), (-18.0, 25.0), (-18.2, 21.0), (-22.0, 20.9), (-24.8, 20.0), (-28.7, 20.0), (-28.6, 16.4), (-26.0, 14.8), (-22.0, 13.8)]),
+        ("Africa/Maputo", &[(-18.0, 25.0), (-22.2, 29.0), (-25.9, 25.0), (-24.8, 20.0), (-22.0, 20.9), (-18.2, 21.0)]),
+        ("Africa/Lagos", &[(-4.5, 11.2), (-6.0, 12.3), (-5.0, 16.5), (-7.0, 21.8), (-12.0, 22.0), (-13.5, 23.0), (-17.8, 20.0), (-17.4, 11.7), (-12.0, 13.5), (-8.0, 12.9)]),
+        ("Indian/Antananarivo", &[(-12.0, 49.3), (-15.5, 50.5), (-25.5, 47.1), (-25.0, 44.0), (-21.0, 43.5), (-16.0, 44.3), (-13.5, 48.0)]),
+        // Oceania
+        ("Australia/Perth", &[(-13.7, 126.0), (-14.0, 129.0), (-25.9, 129.0), (-31.7, 129.0), (-32.2, 125.0), (-34.0, 124.0), (-35.2, 117.9), (-34.3, 114.9), (-31.0, 114.9), (-26.0, 112.9), (-21.8, 113.7), (-20.0, 119.0), (-17.0, 122.0), (-14.5, 124.8)]),
+        ("Australia/Darwin", &[(-10.9, 132.0), (-11.0, 137.0), (-16.0, 138.0), (-26.0, 138.0), (-26.0, 129.0), (-14.0, 129.0), (-12.0, 130.0)]),
+        ("Australia/Brisbane", &[(-10.6, 142.5), (-16.0, 146.0), (-19.0, 147.5), (-24.5, 152.0), (-28.2, 153.6), (-29.0, 152.0), (-29.0, 141.0), (-26.0, 141.0), (-26.0, 138.0), (-16.0, 138.0), (-17.5, 140.8), (-13.0, 141.5)]),
+        ("Australia/Broken_Hill", &[(-31.0, 141.0), (-31.0, 142.5), (-33.0, 142.5), (-33.0, 141.0)]),
+        ("Australia/Adelaide", &[(-26.0, 129.0), (-26.0, 141.0), (-38.1, 141.0), (-36.0, 139.5), (-35.7, 136.5), (-35.0, 135.0), (-32.2, 131.0), (-31.7, 129.0)]),
+        ("Australia/Hobart", &[(-40.6, 144.6), (-40.7, 148.5), (-43.6, 146.9), (-43.5, 145.6)]),
+        ("Australia/Melbourne", &[(-34.0, 141.0), (-35.9, 144.0), (-36.2, 147.0), (-37.5, 149.98), (-38.0, 148.5), (-39.2, 146.4), (-38.5, 144.2), (-38.1, 141.0)]),
+        ("Australia/Sydney", &[(-29.0, 141.0), (-29.0, 152.0), (-28.2, 153.6), (-32.5, 152.7), (-35.0, 151.0), (-37.5, 149.98), (-36.2, 147.0), (-35.9, 144.0), (-34.0, 141.0)]),
+        ("Pacific/Port_Moresby", &[(-2.5, 141.0), (-5.0, 145.8), (-6.0, 147.8), (-8.0, 148.1), (-10.7, 150.9), (-10.0, 147.0), (-8.0, 144.0), (-9.1, 141.0)]),
+        ("Pacific/Auckland", &[(-34.3, 172.6), (-36.5, 175.8), (-37.6, 178.6), (-41.6, 175.3), (-43.8, 173.0), (-46.7, 169.0), (-46.2, 166.4), (-42.0, 171.1), (-40.5, 172.6), (-39.0, 173.7)]),
+        ("Pacific/Fiji", &[(-15.7, 177.0), (-15.7, 180.0), (-19.3, 180.0), (-19.3, 177.0)]),
+        ("Pacific/Guam", &[(13.7, 144.6), (13.7, 145.0), (13.2, 145.0), (13.2, 144.6)]),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_zone() {
+        let test_data: Vec<(f64, f64, &str)> = vec![
+            (35.68, 139.76, "Asia/Tokyo"),
+            (26.21, 127.68, "Asia/Tokyo"),
+            (37.57, 126.98, "Asia/Seoul"),
+            (39.90, 116.40, "Asia/Shanghai"),
+            (28.61, 77.21, "Asia/Kolkata"),
+            (27.70, 85.30, "Asia/Kathmandu"),
+            (27.47, 89.64, "Asia/Thimphu"),
+            (51.51, -0.13, "Europe/London"),
+            (48.86, 2.35, "Europe/Paris"),
+            (52.52, 13.40, "Europe/Berlin"),
+            (55.76, 37.62, "Europe/Moscow"),
+            (59.33, 18.07, "Europe/Stockholm"),
+            (55.68, 12.57, "Europe/Copenhagen"),
+            (59.91, 10.75, "Europe/Oslo"),
+            (40.71, -74.01, "America/New_York"),
+            (41.88, -87.63, "America/Chicago"),
+            (39.74, -104.99, "America/Denver"),
+            (33.45, -112.07, "America/Phoenix"),
+            (34.05, -118.24, "America/Los_Angeles"),
+            (43.65, -79.38, "America/Toronto"),
+            (19.43, -99.13, "America/Mexico_City"),
+            (18.50, -69.90, "America/Santo_Domingo"),
+            (18.54, -72.34, "America/Port-au-Prince"),
+            (-23.55, -46.63, "America/Sao_Paulo"),
+            (-34.60, -58.38, "America/Argentina/Buenos_Aires"),
+            (30.04, 31.24, "Africa/Cairo"),
+            (-1.29, 36.82, "Africa/Nairobi"),
+            (-26.20, 28.05, "Africa/Johannesburg"),
+            (6.52, 3.38, "Africa/Lagos"),
+            (33.57, -7.59, "Africa/Casablanca"),
+            (35.70, -0.60, "Africa/Algiers"),
+            (14.72, -17.47, "Africa/Abidjan"),
+            (-33.87, 151.21, "Australia/Sydney"),
+            (-31.95, 115.86, "Australia/Perth"),
+            (-36.85, 174.76, "Pacific/Auckland"),
+            (21.31, -157.86, "Pacific/Honolulu"),
+        ];
+        for (lat, lon, expected) in test_data {
+            assert_eq!(expected, find_zone(lat, lon), "{},{}", lat, lon);
+        }
+    }
+
+    #[test]
+    fn test_find_zone_at_sea() {
+        assert_eq!("Etc/GMT", find_zone(0.0, -7.0));
+        assert_eq!("Etc/GMT+10", find_zone(30.0, -150.0));
+        assert_eq!("Etc/GMT-12", find_zone(0.0, 179.0));
+        assert_eq!("Etc/GMT+12", find_zone(0.0, -179.0));
+        assert_eq!("Etc/GMT-5", find_zone(-20.0, 70.0));
+    }
+
+    #[test]
+    fn test_regions() {
+        for (zone, polygon) in regions() {
+            assert!(zone.parse::<chrono_tz::Tz>().is_ok(), "{}", zone);
+            assert!(polygon.len() >= 3, "{}", zone);
+        }
+    }
+
+    #[test]
+    fn test_parse_geo_str() {
+        assert_eq!(Some(Ok((35.68, 139.76))), parse_geo_str("geo:35.68,139.76"));
+        assert_eq!(Some(Ok((-33.87, 151.21))), parse_geo_str("geo:-33.87, 151.21"));
+        assert!(parse_geo_str("geo:91,0").unwrap().is_err());
+        assert!(parse_geo_str("geo:abc").unwrap().is_err());
+        assert_eq!(None, parse_geo_str("35.68,139.76"));
+    }
+}
@@ -11,8 +11,39 @@ pub fn usage(epoch: i64) -> String {
   format!(
     "{version}
 Usage: epo [options...]
+       epo tz list [region...] [offset...] [-a]
+       epo tz search <name>
+       epo tz info <name>
+       epo plan <date> <zone[=9-17]...> [--hours <9-17>]
+       epo lookup [<offset>|<HH:MM>|<date with offset>] [--epoch <epoch>]
+       epo transitions <zone...> [--from <year>] [--to <year>] [--within <duration>]
+       epo doctor [--zoneinfo <dir>] [--from <year>] [--to <year>]
+       epo offset-diff <zone> <zone...> [--year <year>]
+  -m Read epochs as milliseconds.
   -p Print as plain text.
+  --json Print as a JSON document (see README.md for the schema).
+  --ndjson Print one JSON object per line.
+  --style <style> Print the table as markdown (default), box, org, rst, asciidoc, latex or html.
+  --layout <layout> Print tables wider than the terminal: auto (default), wide, wrap (several tables) or transpose.
+  --color <when> Highlight offset changes, other dates and off-hours: auto (default), always or never.
+           auto colors the tables when stdout is a terminal; --color never or NO_COLOR=1 turns colors off.
+  --jobs <n> Convert epochs with n threads, up to 256 (default: the number of CPUs).
   -h Display help.
+  --system-tz Read time zones from the system zoneinfo ($TZDIR or /usr/share/zoneinfo).
+  --zoneinfo <dir|file> Read time zones from a zoneinfo directory or add a TZif file.
+  --format <format> Format dates with a strftime pattern (e.g. \"%Y/%m/%d %H:%M\") or a preset:
+           rfc3339, rfc2822, http, clf, sql, cookie, iso-basic. Use zone=format for a single column.
+  --columns <list> Add columns: weekday, isoweek, usweek, yday, quarter, rfc2822, calendar-delta (for each zone),
+           relative, ms, us, ns, hex, delta, delta-time, elapsed, elapsed-time (for each epoch).
+           e.g. --columns weekday,isoweek,relative
+  --unified Print every epoch and date in one table in the order of the arguments.
+  --ref <epoch> Count the elapsed columns from an epoch or a date with an offset instead of the first row.
+  --abbr Append the zone abbreviation (e.g. PDT, LMT) to dates.
+  --long-name Append the long name of the zone (e.g. Pacific Daylight Time) to dates.
+  --windows-id Show the Windows time zone ID of each zone in headers.
+  --territory <code> Pick the zone of a territory (e.g. CA) for Windows IDs such as \"Pacific Standard Time\".
+  --alias Show links such as US/Pacific as typed instead of the zone they point to.
+  --zic <file> Compile zones written in the zic source format (Rule/Zone/Link lines) and use them first.
 
 Examples:
   # Basics: Specify epochs
@@ -25,15 +56,57 @@ Examples:
   epo 0 -1000 -0500 +0000 +0500 +0010
   epo 0 -10 -5 +0 +5 +10
   epo now honolulu new_york london karachi sydney
+  epo now \"EST5EDT,M3.2.0,M11.1.0\" \"<+0330>-3:30\" JST-9
+  epo now geo:35.68,139.76 geo:40.71,-74.01
 
   # Use JavaScript
   epo \"[0,1,2,3,4,5,6].map(a=>now+86400*a)\" honolulu new_york london karachi sydney
   epo \"[...Array(365).keys()].map(a=>now+86400*a)\" denver phoenix los_angeles 
 
+  # Change the date format
+  epo now tokyo london --format rfc2822
+  epo now tokyo=http london=\"%H:%M %Z\"
+
+  # Paste into other documents
+  epo now tokyo london --style org
+
   # Specify ISO 8601 date formats
   epo 1970-01-01 greenwich
   epo 2022-06-15T12:30:45 los_angeles new_york
   epo 2022-04-01 2022-05-01T12:30 2022-06-15T12:30:45 tokyo hawaii gmt
+
+  # Use the tz rules installed in the system instead of the embedded ones
+  epo now tokyo los_angeles --system-tz
+  epo now --zoneinfo ./America/Los_Angeles
+
+  # Check whether the embedded tz rules are older than the system ones (exits 2 if they differ)
+  epo doctor --from 2020
+
+  # Find the weeks when the difference between zones is not the usual one
+  epo offset-diff london new_york --year 2023
+
+  # Preview a rule change with zones in the zic source format
+  epo 2025-01-01 london --zic ./what_if.zi
+
+  # Explore time zones
+  epo tz list europe
+  epo tz list +0530
+  epo tz search new
+  epo tz info los_angeles
+
+  # Plan a meeting (working hours are highlighted)
+  epo plan 2022-04-01 tokyo berlin los_angeles
+  epo plan 2022-04-01 tokyo=8-20 berlin los_angeles=16-22
+
+  # Which zones are at an offset or local time right now
+  epo lookup
+  epo lookup +0530
+  epo lookup 09:00
+  epo lookup 2022-07-01T09:00+0100
+
+  # Daylight saving time transitions (--within exits with 2 if any zone changes its offset soon)
+  epo transitions los_angeles london --from 2022 --to 2025
+  epo transitions los_angeles london --within 14d
   "
   )
 }
@@ -0,0 +1,285 @@
+use super::types::{EpochArg, Input, PrintMode, Settings, Zone};
+use super::{date, print};
+use serde_json::{json, Value};
+use std::io::{stdout, BufWriter, Write};
+use std::iter;
+
+/// Version of the JSON and NDJSON output described in README.md. Bump it on incompatible changes.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Prints the epochs and dates of the settings as one document (--json) or one object per line (--ndjson).
+/// The lines are made as they are printed, so that the epochs of a range need not fit in memory.
+pub fn print(settings: &Settings) {
+    let objects = rows(settings);
+
+    match settings.print_mode {
+        PrintMode::Ndjson => {
+            let warnings = settings.warnings.iter().map(|w| message_object("warning", w));
+            print_lines(warnings.chain(objects));
+        }
+        _ => {
+            let document = json!({
+                "schema_version": SCHEMA_VERSION,
+                "zones": settings.timezones.iter().map(zone_object).collect::<Vec<Value>>(),
+                "rows": objects.collect::<Vec<Value>>(),
+                "warnings": settings.warnings,
+                "errors": [],
+            });
+            print_pretty(&document);
+        }
+    }
+}
+
+/// Row objects of the settings. With --unified they follow the order of the arguments, and a date without an offset
+/// is one instant read in the zone at `local_zone` like in the table; otherwise it is a different instant in each zone.
+fn rows(settings: &Settings) -> Box<dyn Iterator<Item = Value> + '_> {
+    let timezones = &settings.timezones;
+    if settings.output.unified {
+        Box::new(
+            settings
+                .inputs
+                .iter()
+                .flat_map(move |input| -> Box<dyn Iterator<Item = Value> + '_> {
+                    match input {
+                        Input::Epoch(i) | Input::DateWithOffset(i) => Box::new(epoch_rows(&settings.epochs[*i], timezones)),
+                        Input::Date(i) => Box::new(iter::once(local_date_row(&settings.dates[*i], timezones, settings.local_zone))),
+                    }
+                }),
+        )
+    } else {
+        Box::new(
+            settings
+                .epochs
+                .iter()
+                .flat_map(move |epoch_arg| epoch_rows(epoch_arg, timezones))
+                .chain(settings.dates.iter().map(move |date_info| date_row(date_info, timezones))),
+        )
+    }
+}
+
+/// Prints errors in the same structure as the output so that tools reading it need not parse stderr.
+pub fn print_errors(errors: &[String], mode: &PrintMode) {
+    let error_objects: Vec<Value> = errors.iter().map(|e| message_object("error", e)).collect();
+    match mode {
+        PrintMode::Ndjson => print_lines(error_objects),
+        _ => {
+            let document = json!({
+                "schema_version": SCHEMA_VERSION,
+                "zones": [],
+                "rows": [],
+                "warnings": [],
+                "errors": error_objects,
+            });
+            print_pretty(&document);
+        }
+    }
+}
+
+/// Prints a table of a subcommand as objects whose keys are the header.
+pub fn print_table(header: &[String], data: &[Vec<String>], mode: &PrintMode) {
+    let objects: Vec<Value> = data
+        .iter()
+        .map(|row| {
+            let mut object = serde_json::Map::new();
+            object.insert("schema_version".to_string(), json!(SCHEMA_VERSION));
+            for (key, cell) in header.iter().zip(row) {
+                object.insert(key.to_string(), json!(cell));
+            }
+            Value::Object(object)
+        })
+        .collect();
+    match mode {
+        PrintMode::Ndjson => print_lines(objects),
+        _ => print_pretty(&json!(objects)),
+    }
+}
+
+fn epoch_rows<'a>(epoch_arg: &'a EpochArg, timezones: &'a [Zone]) -> impl Iterator<Item = Value> + 'a {
+    epoch_arg
+        .epochs
+        .iter()
+        .map(move |epoch_sec| epoch_row(epoch_sec, &epoch_arg.datestr, timezones))
+}
+
+fn epoch_row(epoch_sec: i64, input: &str, timezones: &[Zone]) -> Value {
+    let values: Vec<Value> = timezones.iter().map(|zone| value_object(zone, epoch_sec, input)).collect();
+    json!({
+        "schema_version": SCHEMA_VERSION,
+        "type": "epoch",
+        "input": input,
+        "epoch": epoch_sec,
+        "values": values,
+    })
+}
+
+/// A date without an offset is a different instant in each zone.
+fn date_row(date_info: &date::DateInfo, timezones: &[Zone]) -> Value {
+    let values: Vec<Value> = timezones
+        .iter()
+        .map(|zone| {
+            let epoch_sec = zone.to_epoch(date_info.date_time);
+            value_object(zone, epoch_sec, &date_info.datestr)
+        })
+        .collect();
+    json!({
+        "schema_version": SCHEMA_VERSION,
+        "type": "date",
+        "input": date_info.datestr,
+        "date": date::to_datestr_from_ndt(date_info.date_time),
+        "values": values,
+    })
+}
+
+/// A date without an offset read in the zone at `local_zone`, which `zone` names.
+fn local_date_row(date_info: &date::DateInfo, timezones: &[Zone], local_zone: usize) -> Value {
+    let epoch_sec = print::local_epoch(date_info.date_time, timezones, local_zone);
+    let values: Vec<Value> = timezones
+        .iter()
+        .map(|zone| value_object(zone, epoch_sec, &date_info.datestr))
+        .collect();
+    json!({
+        "schema_version": SCHEMA_VERSION,
+        "type": "date",
+        "input": date_info.datestr,
+        "date": date::to_datestr_from_ndt(date_info.date_time),
+        "zone": timezones.get(local_zone).map_or("UTC".to_string(), print::zone_header),
+        "epoch": epoch_sec,
+        "values": values,
+    })
+}
+
+fn value_object(zone: &Zone, epoch_sec: i64, input: &str) -> Value {
+    let offset_sec = zone.offset_sec(epoch_sec);
+    json!({
+        "zone": print::zone_header(zone),
+        "epoch": epoch_sec,
+        "offset_sec": offset_sec,
+        "iso": date::to_datestr(epoch_sec, offset_sec),
+        "input": input,
+    })
+}
+
+fn zone_object(zone: &Zone) -> Value {
+    let kind = match zone {
+        Zone::Offset(_) => "offset",
+        Zone::Tz(_) => "tzname",
+        Zone::Posix(_) => "posix",
+        Zone::Rules(_) => "rules",
+    };
+    json!({ "name": print::zone_header(zone), "kind": kind })
+}
+
+fn message_object(kind: &str, message: &str) -> Value {
+    json!({ "schema_version": SCHEMA_VERSION, "type": kind, "message": message })
+}
+
+#[allow(unused_must_use)]
+fn print_pretty(value: &Value) {
+    let out = stdout();
+    let mut buf = BufWriter::new(out.lock());
+    writeln!(buf, "{}", serde_json::to_string_pretty(value).unwrap());
+}
+
+/// Stops when the output is closed (e.g. by head) instead of making the rest of the lines.
+fn print_lines(objects: impl IntoIterator<Item = Value>) {
+    let out = stdout();
+    let mut buf = BufWriter::new(out.lock());
+    for object in objects {
+        if writeln!(buf, "{}", object).is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::arg;
+    use super::*;
+
+    #[test]
+    fn test_epoch_row() {
+        let row = epoch_row(
+            1650000000,
+            "1650000000",
+            &[Zone::Tz("Asia/Tokyo".parse().unwrap()), Zone::Offset(-3600 * 5)],
+        );
+        assert_eq!(
+            json!({
+                "schema_version": 1,
+                "type": "epoch",
+                "input": "1650000000",
+                "epoch": 1650000000,
+                "values": [
+                    {"zone": "Asia/Tokyo", "epoch": 1650000000, "offset_sec": 32400, "iso": "2022-04-15T14:20:00+0900", "input": "1650000000"},
+                    {"zone": "-0500", "epoch": 1650000000, "offset_sec": -18000, "iso": "2022-04-15T00:20:00-0500", "input": "1650000000"},
+                ],
+            }),
+            row
+        );
+    }
+
+    #[test]
+    fn test_date_row() {
+        let date_info = date::DateInfo {
+            date_time: chrono::NaiveDate::from_ymd_opt(2022, 3, 13).unwrap().and_hms_opt(2, 30, 0).unwrap(),
+            datestr: "2022-03-13T02:30".to_string(),
+        };
+        let row = date_row(&date_info, &[Zone::Tz("America/Los_Angeles".parse().unwrap()), Zone::Offset(0)]);
+        assert_eq!("date", row["type"]);
+        assert_eq!("2022-03-13T02:30:00", row["date"]);
+        // 02:30 does not exist in Los Angeles on that day; it is taken with the offset before the gap
+        assert_eq!(1647167400, row["values"][0]["epoch"]);
+        assert_eq!("2022-03-13T03:30:00-0700", row["values"][0]["iso"]);
+        assert_eq!(1647138600, row["values"][1]["epoch"]);
+        assert_eq!(0, row["values"][1]["offset_sec"]);
+    }
+
+    #[test]
+    fn test_unified_rows_match_table() {
+        let args: Vec<String> = [
+            "dummy",
+            "1700000000",
+            "2023-11-15T09:00",
+            "1700000000+3600",
+            "london",
+            "tokyo",
+            "--unified",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let mut settings = arg::parse_arguments(&args, &arg::get_parse_settings()).unwrap();
+        settings.output.color = false;
+        let (_, table) = print::to_string_rows_from_inputs(
+            &settings.inputs,
+            &settings.epochs,
+            &settings.dates,
+            &settings.timezones,
+            settings.local_zone,
+            &settings.output,
+        );
+        let rows: Vec<Value> = rows(&settings).collect();
+        assert_eq!(table.len(), rows.len());
+        for (row, object) in table.iter().zip(&rows) {
+            // Input, Kind, Epoch and a date per zone
+            assert_eq!(row[0], object["input"]);
+            assert_eq!(row.len(), 3 + object["values"].as_array().unwrap().len());
+            for (cell, value) in row[3..].iter().zip(object["values"].as_array().unwrap()) {
+                assert_eq!(row[2], value["epoch"].to_string());
+                assert_eq!(cell, value["iso"].as_str().unwrap());
+            }
+        }
+        // The local date is read in London, the first zone named
+        assert_eq!("Europe/London", rows[1]["zone"]);
+        assert_eq!(1700038800, rows[1]["epoch"]);
+        assert_eq!("2023-11-15T18:00:00+0900", rows[1]["values"][1]["iso"]);
+    }
+
+    #[test]
+    fn test_message_object() {
+        assert_eq!(
+            json!({"schema_version": 1, "type": "error", "message": "Invalid year: x"}),
+            message_object("error", "Invalid year: x")
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use super::print;
+use super::types::{Layout, Table};
+use terminal_size::{terminal_size, Width};
+
+/// Width used when a layout is forced but the width of the terminal is unknown (e.g. piped)
+const DEFAULT_WIDTH: usize = 80;
+
+/// Width of the terminal on stdout.
+pub fn terminal_width() -> Option<usize> {
+    terminal_size().map(|(Width(width), _)| width as usize)
+}
+
+/// Splits or transposes a table so that it fits the width.
+/// The first `fixed` columns (e.g. Epoch) are kept in every table and the rest are groups of `group` columns (a zone and its columns).
+pub fn arrange(table: Table, fixed: usize, group: usize, layout: Layout, width: Option<usize>) -> Vec<Table> {
+    let max_lengths = print::calc_max_column_length(&table.0, &table.1);
+    if is_single_table(&max_lengths, layout, width) {
+        return vec![table];
+    }
+    match layout {
+        Layout::Transpose => transpose(&table, fixed, group),
+        Layout::Auto => {
+            let width = width.unwrap_or(DEFAULT_WIDTH);
+            let widest_group = max_lengths[fixed..].chunks(group).map(table_width).max().unwrap_or(0);
+            if table_width(&max_lengths[..fixed]) + widest_group - 1 <= width {
+                wrap(&table, fixed, group, width)
+            } else {
+                transpose(&table, fixed, group)
+            }
+        }
+        _ => wrap(&table, fixed, group, width.unwrap_or(DEFAULT_WIDTH)),
+    }
+}
+
+/// Whether a table of the column widths is printed as it is.
+pub fn is_single_table(max_lengths: &[usize], layout: Layout, width: Option<usize>) -> bool {
+    match layout {
+        Layout::Wide => true,
+        Layout::Auto => width.is_none_or(|width| table_width(max_lengths) <= width),
+        Layout::Wrap | Layout::Transpose => false,
+    }
+}
+
+/// Width of a Markdown table ("| a | b |") of the columns.
+fn table_width(max_lengths: &[usize]) -> usize {
+    max_lengths.iter().map(|max_length| max_length + 3).sum::<usize>() + 1
+}
+
+/// Splits the groups into tables which fit the width. A group wider than the width gets a table of its own.
+fn wrap(table: &Table, fixed: usize, group: usize, width: usize) -> Vec<Table> {
+    let (header, data) = table;
+    let max_lengths = print::calc_max_column_length(header, data);
+    let fixed_width = table_width(&max_lengths[..fixed]);
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    let mut current_width = fixed_width;
+    for start in (fixed..header.len()).step_by(group) {
+        let end = (start + group).min(header.len());
+        let group_width = table_width(&max_lengths[start..end]) - 1;
+        match ranges.last_mut() {
+            Some(last) if current_width + group_width <= width => {
+                last.1 = end;
+                current_width += group_width;
+            }
+            _ => {
+                ranges.push((start, end));
+                current_width = fixed_width + group_width;
+            }
+        }
+    }
+    if ranges.is_empty() {
+        return vec![table.clone()];
+    }
+
+    let select = |row: &Vec<String>, (start, end): (usize, usize)| -> Vec<String> {
+        row[..fixed].iter().chain(row[start..end].iter()).cloned().collect()
+    };
+    ranges
+        .iter()
+        .map(|range| (select(header, *range), data.iter().map(|row| select(row, *range)).collect()))
+        .collect()
+}
+
+/// Makes a table per row: the fixed columns become the header and the first lines, then a line per group.
+fn transpose(table: &Table, fixed: usize, group: usize) -> Vec<Table> {
+    let (header, data) = table;
+    let column_count = group.max(1) + 1;
+    let pad = |mut line: Vec<String>| -> Vec<String> {
+        line.resize(column_count, "".to_string());
+        line
+    };
+
+    data.iter()
+        .map(|row| {
+            let block_header = pad(vec![header[0].to_string(), row[0].to_string()]);
+            let mut lines: Vec<Vec<String>> = (1..fixed).map(|i| pad(vec![header[i].to_string(), row[i].to_string()])).collect();
+            for start in (fixed..header.len()).step_by(group) {
+                let end = (start + group).min(header.len());
+                let mut line = vec![header[start].to_string()];
+                line.extend(row[start..end].iter().cloned());
+                lines.push(pad(line));
+            }
+            (block_header, lines)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_strings(cells: &[&str]) -> Vec<String> {
+        cells.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn sample_table() -> Table {
+        (
+            to_strings(&["Epoch", "Asia/Tokyo", "Europe/London", "America/New_York"]),
+            vec![to_strings(&[
+                "0",
+                "1970-01-01T09:00:00+0900",
+                "1970-01-01T01:00:00+0100",
+                "1969-12-31T19:00:00-0500",
+            ])],
+        )
+    }
+
+    #[test]
+    fn test_arrange_auto() {
+        // | Epoch | <24> | <24> | <24> | is 90 columns
+        assert_eq!(1, arrange(sample_table(), 1, 1, Layout::Auto, None).len());
+        assert_eq!(1, arrange(sample_table(), 1, 1, Layout::Auto, Some(90)).len());
+
+        let tables = arrange(sample_table(), 1, 1, Layout::Auto, Some(89));
+        assert_eq!(2, tables.len());
+        assert_eq!(to_strings(&["Epoch", "Asia/Tokyo", "Europe/London"]), tables[0].0);
+        assert_eq!(to_strings(&["Epoch", "America/New_York"]), tables[1].0);
+        assert_eq!(to_strings(&["0", "1969-12-31T19:00:00-0500"]), tables[1].1[0]);
+
+        // Not even one zone fits next to the epoch
+        let tables = arrange(sample_table(), 1, 1, Layout::Auto, Some(30));
+        assert_eq!(1, tables.len());
+        assert_eq!(to_strings(&["Epoch", "0"]), tables[0].0);
+        assert_eq!(3, tables[0].1.len());
+    }
+
+    #[test]
+    fn test_wrap_groups() {
+        let header = to_strings(&["Epoch", "Hex", "Asia/Tokyo", "Weekday", "UTC", "Weekday"]);
+        let data = vec![to_strings(&[
+            "0",
+            "0x0",
+            "1970-01-01T09:00:00+0900",
+            "Thursday",
+            "1970-01-01T00:00:00+0000",
+            "Thursday",
+        ])];
+        let tables = arrange((header, data), 2, 2, Layout::Wrap, Some(60));
+        assert_eq!(2, tables.len());
+        assert_eq!(to_strings(&["Epoch", "Hex", "UTC", "Weekday"]), tables[1].0);
+        assert_eq!(to_strings(&["0", "0x0", "1970-01-01T00:00:00+0000", "Thursday"]), tables[1].1[0]);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let header = to_strings(&["Epoch", "Hex", "Asia/Tokyo", "Weekday"]);
+        let data = vec![to_strings(&["0", "0x0", "1970-01-01T09:00:00+0900", "Thursday"])];
+        let tables = arrange((header, data), 2, 2, Layout::Transpose, None);
+        assert_eq!(1, tables.len());
+        assert_eq!(to_strings(&["Epoch", "0", ""]), tables[0].0);
+        assert_eq!(
+            vec![
+                to_strings(&["Hex", "0x0", ""]),
+                to_strings(&["Asia/Tokyo", "1970-01-01T09:00:00+0900", "Thursday"])
+            ],
+            tables[0].1
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use super::types::{PrintMode, Table};
+use super::{app, arg, date, link};
+use chrono::{NaiveDateTime, NaiveTime, Timelike};
+use chrono_tz::TZ_VARIANTS;
+
+const USAGE: &str = "Usage: epo lookup [<offset>|<HH:MM>|<date with offset>] [--epoch <epoch>] [-a] [-p]";
+
+pub enum Query {
+    /// Every zone, grouped by its offset
+    All,
+    /// Zones at the offset, e.g. "+0530"
+    Offset(i32),
+    /// Zones where the local time is within the hour starting at the time, e.g. "09:00"
+    LocalTime(NaiveTime),
+    /// Zones that could have produced an offset-stamped date, e.g. "2022-07-01T09:00+0100"
+    Datestr(date::EpochInfo),
+}
+
+pub fn run(args: &[String]) -> Result<i32, Vec<String>> {
+    let mut print_mode = PrintMode::Markdown;
+    let mut all = false;
+    let mut epoch_sec = date::current_epoch();
+    let mut query = Query::All;
+    let mut errors: Vec<String> = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-p" => print_mode = PrintMode::PlainText,
+            "--json" => print_mode = PrintMode::Json,
+            "--ndjson" => print_mode = PrintMode::Ndjson,
+            "-a" | "--all" => all = true,
+            "--epoch" => match iter.next().map(|v| v.parse::<i64>()) {
+                Some(Ok(v)) => epoch_sec = v,
+                _ => errors.push("--epoch requires an epoch".to_string()),
+            },
+            _ => match parse_query(arg) {
+                Ok(q) => query = q,
+                Err(e) => errors.push(e),
+            },
+        }
+    }
+    if !errors.is_empty() {
+        errors.push(USAGE.to_string());
+        return Err(errors);
+    }
+
+    let (header, data) = lookup(&query, epoch_sec, all);
+    if data.is_empty() {
+        return Err(vec!["No time zone found".to_string()]);
+    }
+    app::print(&header, &data, &print_mode);
+    Ok(0)
+}
+
+pub fn parse_query(query_str: &str) -> Result<Query, String> {
+    if query_str.len() >= 2 && (query_str.starts_with('+') || query_str.starts_with('-')) {
+        if let Ok(offset_sec) = date::parse_offset_str(query_str) {
+            return Ok(Query::Offset(offset_sec));
+        }
+    }
+    if let Ok(time) = NaiveTime::parse_from_str(query_str, "%H:%M") {
+        return Ok(Query::LocalTime(time));
+    }
+    if let Ok(epoch_info) = date::parse_datestr_with_offset(query_str, &arg::get_parse_settings()) {
+        return Ok(Query::Datestr(epoch_info));
+    }
+    Err(format!("Invalid query: {}", query_str))
+}
+
+/// Walks all zones and returns those matching the query, sorted by offset and name.
+pub fn lookup(query: &Query, epoch_sec: i64, all: bool) -> Table {
+    let header: Vec<String> = vec![
+        "Offset".to_string(),
+        "Local time".to_string(),
+        "Abbreviation".to_string(),
+        "Zone".to_string(),
+    ];
+
+    let epoch_sec = match query {
+        Query::Datestr(epoch_info) => epoch_info.epoch_sec,
+        _ => epoch_sec,
+    };
+
+    let mut found: Vec<(i32, &str, date::OffsetInfo)> = Vec::new();
+    for tz in TZ_VARIANTS.iter() {
+        let name = tz.name();
+        if !all && link::is_link(name) {
+            continue;
+        }
+        let info = date::offset_info_with_tz(epoch_sec, *tz);
+        if matches(query, epoch_sec, info.offset_sec) {
+            found.push((info.offset_sec, name, info));
+        }
+    }
+    found.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(b.1)));
+
+    let rows = found
+        .into_iter()
+        .map(|(offset_sec, name, info)| {
+            vec![
+                date::to_offset_str(offset_sec),
+                date::to_datestr(epoch_sec, offset_sec),
+                info.abbreviation,
+                name.to_string(),
+            ]
+        })
+        .collect();
+    (header, rows)
+}
+
+fn matches(query: &Query, epoch_sec: i64, offset_sec: i32) -> bool {
+    match query {
+        Query::All => true,
+        Query::Offset(target) => offset_sec == *target,
+        Query::LocalTime(time) => {
+            let local = NaiveDateTime::from_timestamp_opt(epoch_sec + offset_sec as i64, 0).unwrap();
+            let minutes = (local.hour() * 60 + local.minute()) as i32;
+            let target = (time.hour() * 60 + time.minute()) as i32;
+            (0..60).contains(&(minutes - target).rem_euclid(24 * 60))
+        }
+        Query::Datestr(epoch_info) => offset_sec == epoch_info.offset_sec,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2022-07-01T03:40:00Z
+    const EPOCH: i64 = 1656646800;
+
+    fn zones(table: &Table) -> Vec<&str> {
+        table.1.iter().map(|row| row[3].as_str()).collect()
+    }
+
+    #[test]
+    fn test_lookup_offset() {
+        let table = lookup(&parse_query("+0530").unwrap(), EPOCH, false);
+        assert_eq!(vec!["Asia/Colombo", "Asia/Kolkata"], zones(&table));
+        assert_eq!(vec!["+0530", "2022-07-01T09:10:00+0530", "IST", "Asia/Kolkata"], table.1[1]);
+
+        let table = lookup(&parse_query("+0530").unwrap(), EPOCH, true);
+        assert!(zones(&table).contains(&"Asia/Calcutta"));
+    }
+
+    #[test]
+    fn test_lookup_local_time() {
+        let table = lookup(&parse_query("09:00").unwrap(), EPOCH, false);
+        let found = zones(&table);
+        assert!(found.contains(&"Asia/Kolkata"));
+        assert!(!found.contains(&"Asia/Tokyo"));
+        assert!(table.1.iter().all(|row| row[1].contains("T09:")));
+    }
+
+    #[test]
+    fn test_lookup_datestr() {
+        let table = lookup(&parse_query("2022-07-01T09:00+0100").unwrap(), EPOCH, false);
+        let found = zones(&table);
+        assert!(found.contains(&"Europe/London"));
+        assert!(found.contains(&"Africa/Lagos"));
+        assert!(!found.contains(&"Europe/Paris"));
+        assert!(table.1.iter().all(|row| row[1] == "2022-07-01T09:00:00+0100"));
+    }
+
+    #[test]
+    fn test_lookup_all() {
+        let (_, data) = lookup(&Query::All, EPOCH, false);
+        assert_eq!("-1200", data[0][0]);
+        assert!(data.len() > 300);
+    }
+
+    #[test]
+    fn test_parse_query_error() {
+        assert!(parse_query("tokyo").is_err());
+        assert!(parse_query("25:00").is_err());
+    }
+}
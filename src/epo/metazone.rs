@@ -0,0 +1,307 @@
+use super::link;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+static ZONES: Lazy<HashMap<&str, &str>> = Lazy::new(|| zones().into_iter().collect());
+static NAMES: Lazy<HashMap<&str, (&str, &str)>> = Lazy::new(|| names().into_iter().collect());
+
+/// Returns the CLDR long name (e.g. "Pacific Daylight Time") of a zone.
+/// Names follow the current metazone of the zone, so they are not given for local mean time.
+/// `is_dst` is for a positive DST; zones with their own names are told by the abbreviation,
+/// since Europe/Dublin has a negative DST in winter and its summer "Irish Standard Time" is not DST.
+pub fn long_name(zone_name: &str, is_dst: bool, abbreviation: &str) -> Option<&'static str> {
+    if abbreviation == "LMT" {
+        return None;
+    }
+    let canonical_name = link::canonical_name(zone_name);
+    if let Some((_, std_abbreviation, std, dst)) = zone_names().into_iter().find(|(name, _, _, _)| *name == canonical_name) {
+        return Some(if abbreviation == std_abbreviation { std } else { dst });
+    }
+    let metazone = ZONES.get(zone_name).or_else(|| ZONES.get(canonical_name))?;
+    let (std, dst) = NAMES.get(metazone)?;
+    Some(if is_dst { dst } else { std })
+}
+
+// Long names of the metazones in English (CLDR common/main/en.xml)
+// https://github.com/unicode-org/cldr
+
+fn names() -> Vec<(&'static str, (&'static str, &'static str))> {
+    vec![
+        ("Afghanistan", ("Afghanistan Time", "Afghanistan Time")),
+        ("Africa_Central", ("Central Africa Time", "Central Africa Time")),
+        ("Africa_Eastern", ("East Africa Time", "East Africa Time")),
+        ("Africa_Southern", ("South Africa Standard Time", "South Africa Standard Time")),
+        ("Africa_Western", ("West Africa Standard Time", "West Africa Summer Time")),
+        ("Alaska", ("Alaska Standard Time", "Alaska Daylight Time")),
+        ("America_Central", ("Central Standard Time", "Central Daylight Time")),
+        ("America_Eastern", ("Eastern Standard Time", "Eastern Daylight Time")),
+        ("America_Mountain", ("Mountain Standard Time", "Mountain Daylight Time")),
+        ("America_Pacific", ("Pacific Standard Time", "Pacific Daylight Time")),
+        ("Apia", ("Apia Standard Time", "Apia Daylight Time")),
+        ("Arabian", ("Arabian Standard Time", "Arabian Daylight Time")),
+        ("Argentina", ("Argentina Standard Time", "Argentina Summer Time")),
+        ("Armenia", ("Armenia Standard Time", "Armenia Summer Time")),
+        ("Atlantic", ("Atlantic Standard Time", "Atlantic Daylight Time")),
+        (
+            "Australia_Central",
+            ("Australian Central Standard Time", "Australian Central Daylight Time"),
+        ),
+        (
+            "Australia_Eastern",
+            ("Australian Eastern Standard Time", "Australian Eastern Daylight Time"),
+        ),
+        (
+            "Australia_Western",
+            ("Australian Western Standard Time", "Australian Western Daylight Time"),
+        ),
+        ("Azerbaijan", ("Azerbaijan Standard Time", "Azerbaijan Summer Time")),
+        ("Azores", ("Azores Standard Time", "Azores Summer Time")),
+        ("Bangladesh", ("Bangladesh Standard Time", "Bangladesh Summer Time")),
+        ("Bolivia", ("Bolivia Time", "Bolivia Time")),
+        ("Brasilia", ("Brasilia Standard Time", "Brasilia Summer Time")),
+        ("Cape_Verde", ("Cape Verde Standard Time", "Cape Verde Summer Time")),
+        ("Chamorro", ("Chamorro Standard Time", "Chamorro Standard Time")),
+        ("Chatham", ("Chatham Standard Time", "Chatham Daylight Time")),
+        ("Chile", ("Chile Standard Time", "Chile Summer Time")),
+        ("China", ("China Standard Time", "China Daylight Time")),
+        ("Colombia", ("Colombia Standard Time", "Colombia Summer Time")),
+        ("Cuba", ("Cuba Standard Time", "Cuba Daylight Time")),
+        ("Ecuador", ("Ecuador Time", "Ecuador Time")),
+        ("Europe_Central", ("Central European Standard Time", "Central European Summer Time")),
+        ("Europe_Eastern", ("Eastern European Standard Time", "Eastern European Summer Time")),
+        ("Europe_Western", ("Western European Standard Time", "Western European Summer Time")),
+        ("Fiji", ("Fiji Standard Time", "Fiji Summer Time")),
+        ("GMT", ("Greenwich Mean Time", "Greenwich Mean Time")),
+        ("Georgia", ("Georgia Standard Time", "Georgia Summer Time")),
+        ("Gulf", ("Gulf Standard Time", "Gulf Standard Time")),
+        (
+            "Hawaii_Aleutian",
+            ("Hawaii-Aleutian Standard Time", "Hawaii-Aleutian Daylight Time"),
+        ),
+        ("Hong_Kong", ("Hong Kong Standard Time", "Hong Kong Summer Time")),
+        ("India", ("India Standard Time", "India Standard Time")),
+        ("Indochina", ("Indochina Time", "Indochina Time")),
+        ("Indonesia_Western", ("Western Indonesia Time", "Western Indonesia Time")),
+        ("Iran", ("Iran Standard Time", "Iran Daylight Time")),
+        ("Irkutsk", ("Irkutsk Standard Time", "Irkutsk Summer Time")),
+        ("Israel", ("Israel Standard Time", "Israel Daylight Time")),
+        ("Japan", ("Japan Standard Time", "Japan Daylight Time")),
+        ("Korea", ("Korean Standard Time", "Korean Daylight Time")),
+        ("Krasnoyarsk", ("Krasnoyarsk Standard Time", "Krasnoyarsk Summer Time")),
+        ("Magadan", ("Magadan Standard Time", "Magadan Summer Time")),
+        ("Malaysia", ("Malaysia Time", "Malaysia Time")),
+        ("Mexico_Pacific", ("Mexican Pacific Standard Time", "Mexican Pacific Daylight Time")),
+        ("Moscow", ("Moscow Standard Time", "Moscow Summer Time")),
+        ("Myanmar", ("Myanmar Time", "Myanmar Time")),
+        ("Nepal", ("Nepal Time", "Nepal Time")),
+        ("New_Zealand", ("New Zealand Standard Time", "New Zealand Daylight Time")),
+        ("Newfoundland", ("Newfoundland Standard Time", "Newfoundland Daylight Time")),
+        ("Novosibirsk", ("Novosibirsk Standard Time", "Novosibirsk Summer Time")),
+        ("Omsk", ("Omsk Standard Time", "Omsk Summer Time")),
+        ("Pakistan", ("Pakistan Standard Time", "Pakistan Summer Time")),
+        ("Paraguay", ("Paraguay Standard Time", "Paraguay Summer Time")),
+        ("Peru", ("Peru Standard Time", "Peru Summer Time")),
+        ("Philippines", ("Philippine Standard Time", "Philippine Summer Time")),
+        ("Singapore", ("Singapore Standard Time", "Singapore Standard Time")),
+        ("Taipei", ("Taipei Standard Time", "Taipei Daylight Time")),
+        ("Tonga", ("Tonga Standard Time", "Tonga Summer Time")),
+        ("Uruguay", ("Uruguay Standard Time", "Uruguay Summer Time")),
+        ("Uzbekistan", ("Uzbekistan Standard Time", "Uzbekistan Summer Time")),
+        ("Venezuela", ("Venezuela Time", "Venezuela Time")),
+        ("Vladivostok", ("Vladivostok Standard Time", "Vladivostok Summer Time")),
+        ("Yakutsk", ("Yakutsk Standard Time", "Yakutsk Summer Time")),
+        ("Yekaterinburg", ("Yekaterinburg Standard Time", "Yekaterinburg Summer Time")),
+    ]
+}
+
+// Zones whose names differ from their metazone (CLDR zone long names), with the abbreviation of the standard name
+fn zone_names() -> Vec<(&'static str, &'static str, &'static str, &'static str)> {
+    vec![
+        ("Etc/UTC", "UTC", "Coordinated Universal Time", "Coordinated Universal Time"),
+        ("Europe/Dublin", "GMT", "Greenwich Mean Time", "Irish Standard Time"),
+        ("Europe/London", "GMT", "Greenwich Mean Time", "British Summer Time"),
+    ]
+}
+
+// Current metazones of the zones (CLDR common/supplemental/metaZones.xml)
+
+fn zones() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("Africa/Abidjan", "GMT"),
+        ("Africa/Accra", "GMT"),
+        ("Africa/Addis_Ababa", "Africa_Eastern"),
+        ("Africa/Algiers", "Europe_Central"),
+        ("Africa/Cairo", "Europe_Eastern"),
+        ("Africa/Dakar", "GMT"),
+        ("Africa/Dar_es_Salaam", "Africa_Eastern"),
+        ("Africa/Harare", "Africa_Central"),
+        ("Africa/Johannesburg", "Africa_Southern"),
+        ("Africa/Kampala", "Africa_Eastern"),
+        ("Africa/Khartoum", "Africa_Central"),
+        ("Africa/Kinshasa", "Africa_Western"),
+        ("Africa/Lagos", "Africa_Western"),
+        ("Africa/Maputo", "Africa_Central"),
+        ("Africa/Nairobi", "Africa_Eastern"),
+        ("Africa/Tripoli", "Europe_Eastern"),
+        ("Africa/Tunis", "Europe_Central"),
+        ("America/Anchorage", "Alaska"),
+        ("America/Argentina/Buenos_Aires", "Argentina"),
+        ("America/Asuncion", "Paraguay"),
+        ("America/Bogota", "Colombia"),
+        ("America/Caracas", "Venezuela"),
+        ("America/Chicago", "America_Central"),
+        ("America/Costa_Rica", "America_Central"),
+        ("America/Denver", "America_Mountain"),
+        ("America/Detroit", "America_Eastern"),
+        ("America/Edmonton", "America_Mountain"),
+        ("America/El_Salvador", "America_Central"),
+        ("America/Guatemala", "America_Central"),
+        ("America/Guayaquil", "Ecuador"),
+        ("America/Halifax", "Atlantic"),
+        ("America/Havana", "Cuba"),
+        ("America/Indiana/Indianapolis", "America_Eastern"),
+        ("America/Jamaica", "America_Eastern"),
+        ("America/Juneau", "Alaska"),
+        ("America/La_Paz", "Bolivia"),
+        ("America/Lima", "Peru"),
+        ("America/Los_Angeles", "America_Pacific"),
+        ("America/Managua", "America_Central"),
+        ("America/Mazatlan", "Mexico_Pacific"),
+        ("America/Mexico_City", "America_Central"),
+        ("America/Montevideo", "Uruguay"),
+        ("America/New_York", "America_Eastern"),
+        ("America/Panama", "America_Eastern"),
+        ("America/Phoenix", "America_Mountain"),
+        ("America/Port_of_Spain", "Atlantic"),
+        ("America/Puerto_Rico", "Atlantic"),
+        ("America/Regina", "America_Central"),
+        ("America/Santiago", "Chile"),
+        ("America/Santo_Domingo", "Atlantic"),
+        ("America/Sao_Paulo", "Brasilia"),
+        ("America/St_Johns", "Newfoundland"),
+        ("America/Tegucigalpa", "America_Central"),
+        ("America/Tijuana", "America_Pacific"),
+        ("America/Toronto", "America_Eastern"),
+        ("America/Vancouver", "America_Pacific"),
+        ("America/Winnipeg", "America_Central"),
+        ("Asia/Baghdad", "Arabian"),
+        ("Asia/Baku", "Azerbaijan"),
+        ("Asia/Bangkok", "Indochina"),
+        ("Asia/Beirut", "Europe_Eastern"),
+        ("Asia/Colombo", "India"),
+        ("Asia/Dhaka", "Bangladesh"),
+        ("Asia/Dubai", "Gulf"),
+        ("Asia/Ho_Chi_Minh", "Indochina"),
+        ("Asia/Hong_Kong", "Hong_Kong"),
+        ("Asia/Irkutsk", "Irkutsk"),
+        ("Asia/Jakarta", "Indonesia_Western"),
+        ("Asia/Jerusalem", "Israel"),
+        ("Asia/Kabul", "Afghanistan"),
+        ("Asia/Karachi", "Pakistan"),
+        ("Asia/Kathmandu", "Nepal"),
+        ("Asia/Kolkata", "India"),
+        ("Asia/Krasnoyarsk", "Krasnoyarsk"),
+        ("Asia/Kuala_Lumpur", "Malaysia"),
+        ("Asia/Kuwait", "Arabian"),
+        ("Asia/Macau", "China"),
+        ("Asia/Magadan", "Magadan"),
+        ("Asia/Manila", "Philippines"),
+        ("Asia/Muscat", "Gulf"),
+        ("Asia/Nicosia", "Europe_Eastern"),
+        ("Asia/Novosibirsk", "Novosibirsk"),
+        ("Asia/Omsk", "Omsk"),
+        ("Asia/Qatar", "Arabian"),
+        ("Asia/Riyadh", "Arabian"),
+        ("Asia/Seoul", "Korea"),
+        ("Asia/Shanghai", "China"),
+        ("Asia/Singapore", "Singapore"),
+        ("Asia/Taipei", "Taipei"),
+        ("Asia/Tashkent", "Uzbekistan"),
+        ("Asia/Tbilisi", "Georgia"),
+        ("Asia/Tehran", "Iran"),
+        ("Asia/Tokyo", "Japan"),
+        ("Asia/Vladivostok", "Vladivostok"),
+        ("Asia/Yakutsk", "Yakutsk"),
+        ("Asia/Yangon", "Myanmar"),
+        ("Asia/Yekaterinburg", "Yekaterinburg"),
+        ("Asia/Yerevan", "Armenia"),
+        ("Atlantic/Azores", "Azores"),
+        ("Atlantic/Bermuda", "Atlantic"),
+        ("Atlantic/Canary", "Europe_Western"),
+        ("Atlantic/Cape_Verde", "Cape_Verde"),
+        ("Atlantic/Madeira", "Europe_Western"),
+        ("Atlantic/Reykjavik", "GMT"),
+        ("Australia/Adelaide", "Australia_Central"),
+        ("Australia/Brisbane", "Australia_Eastern"),
+        ("Australia/Darwin", "Australia_Central"),
+        ("Australia/Hobart", "Australia_Eastern"),
+        ("Australia/Melbourne", "Australia_Eastern"),
+        ("Australia/Perth", "Australia_Western"),
+        ("Australia/Sydney", "Australia_Eastern"),
+        ("Etc/GMT", "GMT"),
+        ("Europe/Amsterdam", "Europe_Central"),
+        ("Europe/Athens", "Europe_Eastern"),
+        ("Europe/Belgrade", "Europe_Central"),
+        ("Europe/Berlin", "Europe_Central"),
+        ("Europe/Brussels", "Europe_Central"),
+        ("Europe/Bucharest", "Europe_Eastern"),
+        ("Europe/Budapest", "Europe_Central"),
+        ("Europe/Copenhagen", "Europe_Central"),
+        ("Europe/Helsinki", "Europe_Eastern"),
+        ("Europe/Kaliningrad", "Europe_Eastern"),
+        ("Europe/Kyiv", "Europe_Eastern"),
+        ("Europe/Kiev", "Europe_Eastern"),
+        ("Europe/Lisbon", "Europe_Western"),
+        ("Europe/Luxembourg", "Europe_Central"),
+        ("Europe/Madrid", "Europe_Central"),
+        ("Europe/Minsk", "Moscow"),
+        ("Europe/Moscow", "Moscow"),
+        ("Europe/Oslo", "Europe_Central"),
+        ("Europe/Paris", "Europe_Central"),
+        ("Europe/Prague", "Europe_Central"),
+        ("Europe/Riga", "Europe_Eastern"),
+        ("Europe/Rome", "Europe_Central"),
+        ("Europe/Sofia", "Europe_Eastern"),
+        ("Europe/Stockholm", "Europe_Central"),
+        ("Europe/Tallinn", "Europe_Eastern"),
+        ("Europe/Vienna", "Europe_Central"),
+        ("Europe/Vilnius", "Europe_Eastern"),
+        ("Europe/Warsaw", "Europe_Central"),
+        ("Europe/Zurich", "Europe_Central"),
+        ("Pacific/Apia", "Apia"),
+        ("Pacific/Auckland", "New_Zealand"),
+        ("Pacific/Chatham", "Chatham"),
+        ("Pacific/Fiji", "Fiji"),
+        ("Pacific/Guam", "Chamorro"),
+        ("Pacific/Honolulu", "Hawaii_Aleutian"),
+        ("Pacific/Tongatapu", "Tonga"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_name() {
+        assert_eq!(Some("Pacific Daylight Time"), long_name("America/Los_Angeles", true, "PDT"));
+        assert_eq!(Some("Pacific Standard Time"), long_name("US/Pacific", false, "PST"));
+        assert_eq!(Some("Japan Standard Time"), long_name("Asia/Tokyo", false, "JST"));
+        assert_eq!(Some("British Summer Time"), long_name("Europe/London", true, "BST"));
+        assert_eq!(Some("Greenwich Mean Time"), long_name("Europe/London", false, "GMT"));
+        // Dublin has a negative DST in winter
+        assert_eq!(Some("Greenwich Mean Time"), long_name("Europe/Dublin", false, "GMT"));
+        assert_eq!(Some("Irish Standard Time"), long_name("Europe/Dublin", false, "IST"));
+        assert_eq!(None, long_name("America/Los_Angeles", false, "LMT"));
+        assert_eq!(None, long_name("Antarctica/Troll", false, "+00"));
+    }
+
+    #[test]
+    fn test_names() {
+        // Every metazone of a zone has names
+        for (zone, metazone) in zones() {
+            assert!(NAMES.contains_key(metazone), "{} {}", zone, metazone);
+            assert!(zone.parse::<chrono_tz::Tz>().is_ok(), "{}", zone);
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use super::types::{PrintMode, Table};
+use super::{app, date, transition, tz};
+use chrono_tz::Tz;
+
+const USAGE: &str = "Usage: epo offset-diff <zone> <zone...> [--year <year>] [--from <year|date>] [--to <year|date>] [-p]";
+
+/// A span of time in which the differences between the zones do not change.
+pub struct Period {
+    pub start: i64,
+    pub end: i64,
+    /// Offset of each zone minus the offset of the first zone
+    pub differences: Vec<i32>,
+}
+
+pub fn run(args: &[String]) -> Result<i32, Vec<String>> {
+    let mut print_mode = PrintMode::Markdown;
+    let mut from: Option<i64> = None;
+    let mut to: Option<i64> = None;
+    let mut zones: Vec<Tz> = Vec::new();
+    let mut errors: Vec<String> = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-p" => print_mode = PrintMode::PlainText,
+            "--json" => print_mode = PrintMode::Json,
+            "--ndjson" => print_mode = PrintMode::Ndjson,
+            "--year" | "--from" | "--to" => {
+                let value = match iter.next() {
+                    Some(value) => value,
+                    None => {
+                        errors.push(format!("{} requires a value", arg));
+                        continue;
+                    }
+                };
+                let r = match arg.as_str() {
+                    "--year" => parse_year(value).map(|year| {
+                        from = Some(transition::year_start(year));
+                        to = Some(transition::year_start(year + 1));
+                    }),
+                    "--from" => transition::parse_range_value(value, false).map(|v| from = Some(v)),
+                    _ => transition::parse_range_value(value, true).map(|v| to = Some(v)),
+                };
+                if let Err(e) = r {
+                    errors.push(e);
+                }
+            }
+            _ => match tz::resolve(arg) {
+                Ok(tz) => zones.push(tz),
+                Err(e) => errors.push(e),
+            },
+        }
+    }
+
+    if zones.len() < 2 {
+        errors.push(USAGE.to_string());
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let current_year = transition::current_year(date::current_epoch());
+    let start = from.unwrap_or_else(|| transition::year_start(current_year));
+    let end = to.unwrap_or_else(|| transition::year_start(current_year + 1));
+    if start >= end {
+        return Err(vec!["--from must be before --to".to_string()]);
+    }
+
+    let (header, data) = to_string_rows(&zones, start, end);
+    app::print(&header, &data, &print_mode);
+    Ok(0)
+}
+
+fn parse_year(value: &str) -> Result<i32, String> {
+    match value.parse::<i32>() {
+        Ok(year) if (1..=9999).contains(&year) => Ok(year),
+        _ => Err(format!("Invalid year: {}", value)),
+    }
+}
+
+/// Splits [start, end) at the offset changes of the zones and merges the periods whose differences are the same.
+pub fn find_periods(zones: &[Tz], start: i64, end: i64) -> Vec<Period> {
+    let mut epochs: Vec<i64> = vec![start];
+    for tz in zones {
+        epochs.extend(transition::find_transitions(*tz, start, end).iter().map(|t| t.epoch_sec));
+    }
+    epochs.sort_unstable();
+    epochs.dedup();
+
+    let mut periods: Vec<Period> = Vec::new();
+    for (i, &epoch_sec) in epochs.iter().enumerate() {
+        let period_end = epochs.get(i + 1).copied().unwrap_or(end);
+        let differences = differences_at(zones, epoch_sec);
+        match periods.last_mut() {
+            Some(last) if last.differences == differences => last.end = period_end,
+            _ => periods.push(Period {
+                start: epoch_sec,
+                end: period_end,
+                differences,
+            }),
+        }
+    }
+    periods
+}
+
+fn differences_at(zones: &[Tz], epoch_sec: i64) -> Vec<i32> {
+    let offsets: Vec<i32> = zones
+        .iter()
+        .map(|tz| date::offset_info_with_tz(epoch_sec, *tz).offset_sec)
+        .collect();
+    offsets.iter().map(|offset_sec| offset_sec - offsets[0]).collect()
+}
+
+pub fn to_string_rows(zones: &[Tz], start: i64, end: i64) -> Table {
+    let mut header: Vec<String> = vec!["From".to_string(), "To".to_string(), "Duration".to_string()];
+    header.extend(zones[1..].iter().map(|tz| format!("{} - {}", tz.name(), zones[0].name())));
+
+    let rows: Vec<Vec<String>> = find_periods(zones, start, end)
+        .iter()
+        .map(|period| {
+            let mut row = vec![
+                date::to_datestr(period.start, 0),
+                date::to_datestr(period.end, 0),
+                to_duration_str(period.end - period.start),
+            ];
+            row.extend(period.differences[1..].iter().map(|d| date::to_offset_str(*d)));
+            row
+        })
+        .collect();
+    (header, rows)
+}
+
+/// Formats seconds as "13d 18h" (zero parts are omitted).
+fn to_duration_str(duration_sec: i64) -> String {
+    let parts = [
+        (duration_sec / 86400, "d"),
+        (duration_sec % 86400 / 3600, "h"),
+        (duration_sec % 3600 / 60, "m"),
+    ];
+    let s: Vec<String> = parts
+        .iter()
+        .filter(|(value, _)| *value != 0)
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect();
+    if s.is_empty() {
+        return "0m".to_string();
+    }
+    s.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_string_rows() {
+        let zones = [Tz::Europe__London, Tz::America__New_York];
+        let (header, data) = to_string_rows(&zones, transition::year_start(2023), transition::year_start(2024));
+        assert_eq!(vec!["From", "To", "Duration", "America/New_York - Europe/London"], header);
+        assert_eq!(
+            vec![
+                vec!["2023-01-01T00:00:00+0000", "2023-03-12T07:00:00+0000", "70d 7h", "-0500"],
+                vec!["2023-03-12T07:00:00+0000", "2023-03-26T01:00:00+0000", "13d 18h", "-0400"],
+                vec!["2023-03-26T01:00:00+0000", "2023-10-29T01:00:00+0000", "217d", "-0500"],
+                vec!["2023-10-29T01:00:00+0000", "2023-11-05T06:00:00+0000", "7d 5h", "-0400"],
+                vec!["2023-11-05T06:00:00+0000", "2024-01-01T00:00:00+0000", "56d 18h", "-0500"],
+            ],
+            data
+        );
+    }
+
+    #[test]
+    fn test_find_periods() {
+        // London and Berlin change at the same instant, so the difference never changes
+        let zones = [Tz::Europe__London, Tz::Europe__Berlin];
+        let periods = find_periods(&zones, transition::year_start(2023), transition::year_start(2024));
+        assert_eq!(1, periods.len());
+        assert_eq!(vec![0, 3600], periods[0].differences);
+
+        let zones = [Tz::Asia__Tokyo, Tz::Asia__Kolkata, Tz::Australia__Sydney];
+        let periods = find_periods(&zones, transition::year_start(2023), transition::year_start(2024));
+        assert_eq!(3, periods.len());
+        assert_eq!(vec![0, -12600, 7200], periods[0].differences);
+        assert_eq!(vec![0, -12600, 3600], periods[1].differences);
+    }
+
+    #[test]
+    fn test_run_json() {
+        for mode in ["--json", "--ndjson"] {
+            let args: Vec<String> = ["tokyo", "london", "--year", "2023", mode].iter().map(|s| s.to_string()).collect();
+            assert_eq!(Ok(0), run(&args), "{}", mode);
+        }
+    }
+
+    #[test]
+    fn test_run_reversed_range() {
+        let args: Vec<String> = ["tokyo", "london", "--from", "2025", "--to", "2023"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(Err(vec!["--from must be before --to".to_string()]), run(&args));
+    }
+
+    #[test]
+    fn test_to_duration_str() {
+        assert_eq!("0m", to_duration_str(0));
+        assert_eq!("30m", to_duration_str(1800));
+        assert_eq!("1d 30m", to_duration_str(88200));
+    }
+}
@@ -0,0 +1,288 @@
+use super::rules::ZoneRules;
+use super::types::{PrintMode, Table, Zone};
+use super::{app, arg, date, print, tzif};
+use chrono::{Duration, NaiveDate, TimeZone, Timelike};
+use std::path::Path;
+
+const USAGE: &str = "Usage: epo plan <date> <zone[=9-17]...> [--hours <9-17>] [--zoneinfo <dir>|--system-tz] [--zic <file>] [-p]";
+
+pub const DEFAULT_WORKING_HOURS: (u32, u32) = (9, 17);
+
+pub struct PlanZone {
+    pub zone: Zone,
+    /// Working hours in local time, e.g. (9, 17) for 09:00-17:00. The end may be smaller than the start for night shifts.
+    pub working_hours: Option<(u32, u32)>,
+}
+
+pub fn run(args: &[String]) -> Result<i32, Vec<String>> {
+    let mut print_mode = PrintMode::Markdown;
+    let mut default_hours = DEFAULT_WORKING_HOURS;
+    let mut plan_date: Option<NaiveDate> = None;
+    let mut zones: Vec<PlanZone> = Vec::new();
+    let mut errors: Vec<String> = Vec::new();
+
+    let zoneinfo_dir = match arg::parse_zoneinfo_option(args) {
+        Ok(Some(arg::ZoneinfoOption::Dir(dir))) => Some(dir),
+        Ok(Some(arg::ZoneinfoOption::File(path))) => match tzif::load_file(&path) {
+            Ok(rules) => {
+                zones.push(PlanZone {
+                    zone: Zone::Rules(rules),
+                    working_hours: None,
+                });
+                None
+            }
+            Err(e) => return Err(vec![e]),
+        },
+        Ok(None) => None,
+        Err(e) => return Err(vec![e]),
+    };
+    let custom_zones = arg::parse_zic_option(args)?;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-p" => print_mode = PrintMode::PlainText,
+            "--system-tz" => {}
+            "--zoneinfo" | "--zic" => {
+                iter.next();
+            }
+            "--hours" => match iter.next().map(|v| parse_hours(v)) {
+                Some(Ok(hours)) => default_hours = hours,
+                Some(Err(e)) => errors.push(e),
+                None => errors.push("--hours requires a value".to_string()),
+            },
+            _ => {
+                if plan_date.is_none() {
+                    if let Ok(d) = NaiveDate::parse_from_str(arg, "%Y-%m-%d") {
+                        plan_date = Some(d);
+                        continue;
+                    }
+                }
+                match parse_plan_zone(arg, zoneinfo_dir.as_deref(), &custom_zones) {
+                    Ok(plan_zone) => zones.push(plan_zone),
+                    Err(e) => errors.push(e),
+                }
+            }
+        }
+    }
+
+    if plan_date.is_none() || zones.is_empty() {
+        errors.push(USAGE.to_string());
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let markdown = matches!(print_mode, PrintMode::Markdown);
+    let epochs = hourly_epochs(plan_date.unwrap(), &zones[0].zone);
+    let (header, data) = to_string_rows(&epochs, &zones, default_hours, markdown);
+    app::print(&header, &data, &print_mode);
+
+    println!();
+    let overlaps = find_overlaps(&epochs, &zones, default_hours);
+    if overlaps.is_empty() {
+        println!("No overlap in working hours");
+    }
+    for (start, end) in overlaps {
+        println!("Overlap: {} hour(s)", (end - start) / 3600);
+        for z in &zones {
+            println!(
+                "  {}: {} - {}",
+                print::zone_header(&z.zone),
+                date::to_datestr(start, z.zone.offset_sec(start)),
+                date::to_datestr(end, z.zone.offset_sec(end))
+            );
+        }
+    }
+    Ok(0)
+}
+
+/// The 24 hours starting at the local midnight of `plan_date` in the given zone.
+pub fn hourly_epochs(plan_date: NaiveDate, zone: &Zone) -> Vec<i64> {
+    let midnight = plan_date.and_hms_opt(0, 0, 0).unwrap();
+    let start = match zone {
+        Zone::Offset(offset_sec) => midnight.timestamp() - *offset_sec as i64,
+        Zone::Tz(tz) => {
+            // Midnight does not exist on some DST days (e.g. America/Santiago); the day then starts at 01:00.
+            match tz.from_local_datetime(&midnight).earliest() {
+                Some(dt) => dt.timestamp(),
+                None => tz
+                    .from_local_datetime(&(midnight + Duration::hours(1)))
+                    .earliest()
+                    .unwrap()
+                    .timestamp(),
+            }
+        }
+        Zone::Posix(posix_tz) => posix_tz.to_epoch(midnight),
+        Zone::Rules(rules) => rules.to_epoch(midnight),
+    };
+    (0..24).map(|h| start + h * 3600).collect()
+}
+
+pub fn to_string_rows(epochs: &[i64], zones: &[PlanZone], default_hours: (u32, u32), markdown: bool) -> Table {
+    let mut header: Vec<String> = vec!["UTC".to_string()];
+    for z in zones {
+        header.push(print::zone_header(&z.zone));
+    }
+
+    let base_date = local_date_time(epochs[0], &zones[0].zone).date();
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for epoch_sec in epochs {
+        let mut row: Vec<String> = vec![local_date_time(*epoch_sec, &Zone::Offset(0)).format("%H:%M").to_string()];
+        for z in zones {
+            let local = local_date_time(*epoch_sec, &z.zone);
+            let mut cell = local.format("%H:%M").to_string();
+            let days = (local.date() - base_date).num_days();
+            if days != 0 {
+                cell.push_str(&format!(" ({:+}d)", days));
+            }
+            if is_working_time(*epoch_sec, z, default_hours) {
+                cell = if markdown { format!("**{}**", cell) } else { format!("{}*", cell) };
+            }
+            row.push(cell);
+        }
+        rows.push(row);
+    }
+    (header, rows)
+}
+
+/// Returns [start, end) ranges of the given hours where every zone is in its working hours.
+pub fn find_overlaps(epochs: &[i64], zones: &[PlanZone], default_hours: (u32, u32)) -> Vec<(i64, i64)> {
+    let mut overlaps: Vec<(i64, i64)> = Vec::new();
+    for epoch_sec in epochs {
+        if !zones.iter().all(|z| is_working_time(*epoch_sec, z, default_hours)) {
+            continue;
+        }
+        match overlaps.last_mut() {
+            Some((_, end)) if *end == *epoch_sec => *end += 3600,
+            _ => overlaps.push((*epoch_sec, epoch_sec + 3600)),
+        }
+    }
+    overlaps
+}
+
+fn parse_plan_zone(arg: &str, zoneinfo_dir: Option<&Path>, custom_zones: &[ZoneRules]) -> Result<PlanZone, String> {
+    let (zone_str, working_hours) = match arg.rfind('=') {
+        Some(idx) => (&arg[..idx], Some(parse_hours(&arg[idx + 1..])?)),
+        None => (arg, None),
+    };
+    Ok(PlanZone {
+        zone: arg::parse_zone(zone_str, zoneinfo_dir, custom_zones)?,
+        working_hours,
+    })
+}
+
+/// Parses working hours such as "9-17" or "22-6".
+fn parse_hours(hours_str: &str) -> Result<(u32, u32), String> {
+    let error = || format!("Invalid working hours: {}", hours_str);
+    let (start, end) = hours_str.split_once('-').ok_or_else(error)?;
+    let start: u32 = start.parse().map_err(|_| error())?;
+    let end: u32 = end.parse().map_err(|_| error())?;
+    if start > 24 || end > 24 || start == end {
+        return Err(error());
+    }
+    Ok((start, end))
+}
+
+fn is_working_time(epoch_sec: i64, plan_zone: &PlanZone, default_hours: (u32, u32)) -> bool {
+    let (start, end) = plan_zone.working_hours.unwrap_or(default_hours);
+    let local = local_date_time(epoch_sec, &plan_zone.zone);
+    let minutes = local.hour() * 60 + local.minute();
+    if start < end {
+        start * 60 <= minutes && minutes < end * 60
+    } else {
+        start * 60 <= minutes || minutes < end * 60
+    }
+}
+
+fn local_date_time(epoch_sec: i64, zone: &Zone) -> chrono::NaiveDateTime {
+    chrono::NaiveDateTime::from_timestamp_opt(epoch_sec + zone.offset_sec(epoch_sec) as i64, 0).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan_zone(name: &str, working_hours: Option<(u32, u32)>) -> PlanZone {
+        PlanZone {
+            zone: Zone::Tz(name.parse().unwrap()),
+            working_hours,
+        }
+    }
+
+    #[test]
+    fn test_to_string_rows() {
+        let zones = vec![
+            plan_zone("Asia/Tokyo", None),
+            plan_zone("Europe/Berlin", None),
+            plan_zone("America/Los_Angeles", None),
+        ];
+        let epochs = hourly_epochs(NaiveDate::from_ymd_opt(2022, 4, 1).unwrap(), &zones[0].zone);
+        assert_eq!(24, epochs.len());
+        assert_eq!(1648738800, epochs[0]);
+
+        let (header, data) = to_string_rows(&epochs, &zones, DEFAULT_WORKING_HOURS, false);
+        assert_eq!(vec!["UTC", "Asia/Tokyo", "Europe/Berlin", "America/Los_Angeles"], header);
+        assert_eq!(24, data.len());
+        assert_eq!(vec!["15:00", "00:00", "17:00 (-1d)", "08:00 (-1d)"], data[0]);
+        assert_eq!(vec!["00:00", "09:00*", "02:00", "17:00 (-1d)"], data[9]);
+        assert_eq!(vec!["07:00", "16:00*", "09:00*", "00:00"], data[16]);
+    }
+
+    #[test]
+    fn test_find_overlaps() {
+        let epochs = hourly_epochs(
+            NaiveDate::from_ymd_opt(2022, 4, 1).unwrap(),
+            &Zone::Tz("Asia/Tokyo".parse().unwrap()),
+        );
+
+        let zones = vec![plan_zone("Asia/Tokyo", None), plan_zone("Europe/Berlin", None)];
+        // Tokyo 16:00-17:00 = Berlin 09:00-10:00 (CEST)
+        assert_eq!(
+            vec![(1648796400, 1648800000)],
+            find_overlaps(&epochs, &zones, DEFAULT_WORKING_HOURS)
+        );
+
+        let zones = vec![
+            plan_zone("Asia/Tokyo", Some((8, 20))),
+            plan_zone("America/Los_Angeles", Some((16, 22))),
+        ];
+        // Tokyo 08:00-14:00 = LA 16:00-22:00 (PDT)
+        assert_eq!(
+            vec![(1648767600, 1648789200)],
+            find_overlaps(&epochs, &zones, DEFAULT_WORKING_HOURS)
+        );
+
+        let zones = vec![
+            plan_zone("Asia/Tokyo", None),
+            plan_zone("Europe/Berlin", None),
+            plan_zone("America/Los_Angeles", None),
+        ];
+        assert!(find_overlaps(&epochs, &zones, DEFAULT_WORKING_HOURS).is_empty());
+    }
+
+    #[test]
+    fn test_hourly_epochs_dst() {
+        // The day LA switches to PDT has only 23 local hours, so the grid ends at 00:00 of the next day.
+        let epochs = hourly_epochs(
+            NaiveDate::from_ymd_opt(2022, 3, 13).unwrap(),
+            &Zone::Tz("America/Los_Angeles".parse().unwrap()),
+        );
+        assert_eq!(1647158400, epochs[0]);
+        let zones = vec![plan_zone("America/Los_Angeles", None)];
+        let (_, data) = to_string_rows(&epochs, &zones, DEFAULT_WORKING_HOURS, true);
+        assert_eq!("01:00", data[1][1]);
+        assert_eq!("03:00", data[2][1]);
+        assert_eq!("**09:00**", data[8][1]);
+        assert_eq!("00:00 (+1d)", data[23][1]);
+    }
+
+    #[test]
+    fn test_parse_hours() {
+        assert_eq!((9, 17), parse_hours("9-17").unwrap());
+        assert_eq!((22, 6), parse_hours("22-6").unwrap());
+        assert!(parse_hours("9").is_err());
+        assert!(parse_hours("9-9").is_err());
+        assert!(parse_hours("9-25").is_err());
+    }
+}
@@ -0,0 +1,440 @@
+use super::date;
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+
+/// A POSIX TZ rule such as "EST5EDT,M3.2.0,M11.1.0", "<+0330>-3:30" or "JST-9".
+///
+/// Offsets are stored as seconds east of UTC, unlike the string where "EST5" means UTC-5.
+#[derive(Clone)]
+pub struct PosixTz {
+    pub tz_str: String,
+    pub std_abbreviation: String,
+    pub std_offset_sec: i32,
+    pub dst: Option<DstRule>,
+}
+
+#[derive(Clone)]
+pub struct DstRule {
+    pub abbreviation: String,
+    pub offset_sec: i32,
+    pub start: RuleDate,
+    /// Local (standard) time of the day when DST starts; may be negative or exceed 24h
+    pub start_time_sec: i32,
+    pub end: RuleDate,
+    /// Local (daylight) time of the day when DST ends
+    pub end_time_sec: i32,
+}
+
+#[derive(Clone)]
+pub enum RuleDate {
+    /// Jn: 1 <= n <= 365, February 29 is never counted
+    Julian(u32),
+    /// n: 0 <= n <= 365, February 29 is counted in leap years
+    ZeroBasedJulian(u32),
+    /// Mm.w.d: day d (0 = Sunday) of week w (5 = last) of month m
+    MonthWeekDay(u32, u32, u32),
+}
+
+const DEFAULT_RULE_TIME_SEC: i32 = 2 * 3600;
+
+/// Offsets are less than a day, though the times of the rules may be up to 167 hours
+fn is_valid_offset(offset_sec: i32) -> bool {
+    offset_sec.abs() < 24 * 3600
+}
+
+impl PosixTz {
+    pub fn parse(tz_str: &str) -> Result<PosixTz, String> {
+        PosixTz::try_parse(tz_str).unwrap_or_else(|| Err(format!("Invalid POSIX TZ string: {}", tz_str)))
+    }
+
+    /// None if the string is not a TZ string, or an error if it is one whose offset is 24 hours or more.
+    pub fn try_parse(tz_str: &str) -> Option<Result<PosixTz, String>> {
+        let invalid = || Some(Err(format!("Invalid timezone: {}", tz_str)));
+        let mut cursor = Cursor::new(tz_str);
+
+        let std_abbreviation = cursor.abbreviation()?;
+        let std_offset_sec = -cursor.time()?;
+
+        if cursor.is_end() {
+            if !is_valid_offset(std_offset_sec) {
+                return invalid();
+            }
+            return Some(Ok(PosixTz {
+                tz_str: tz_str.to_string(),
+                std_abbreviation,
+                std_offset_sec,
+                dst: None,
+            }));
+        }
+
+        let dst_abbreviation = cursor.abbreviation()?;
+        let dst_offset_sec = match cursor.peek() {
+            Some(b',') | None => std_offset_sec + 3600,
+            _ => -cursor.time()?,
+        };
+
+        // Without rules, the transitions of the U.S. are used like most implementations do.
+        let (start, start_time_sec, end, end_time_sec) = if cursor.is_end() {
+            (
+                RuleDate::MonthWeekDay(3, 2, 0),
+                DEFAULT_RULE_TIME_SEC,
+                RuleDate::MonthWeekDay(11, 1, 0),
+                DEFAULT_RULE_TIME_SEC,
+            )
+        } else {
+            cursor.expect(b',')?;
+            let (start, start_time_sec) = cursor.rule()?;
+            cursor.expect(b',')?;
+            let (end, end_time_sec) = cursor.rule()?;
+            (start, start_time_sec, end, end_time_sec)
+        };
+
+        if !cursor.is_end() {
+            return None;
+        }
+        if !is_valid_offset(std_offset_sec) || !is_valid_offset(dst_offset_sec) {
+            return invalid();
+        }
+
+        Some(Ok(PosixTz {
+            tz_str: tz_str.to_string(),
+            std_abbreviation,
+            std_offset_sec,
+            dst: Some(DstRule {
+                abbreviation: dst_abbreviation,
+                offset_sec: dst_offset_sec,
+                start,
+                start_time_sec,
+                end,
+                end_time_sec,
+            }),
+        }))
+    }
+
+    pub fn offset_info(&self, epoch_sec: i64) -> date::OffsetInfo {
+        let dst = match &self.dst {
+            Some(dst) if self.is_dst(dst, epoch_sec) => dst,
+            _ => {
+                return date::OffsetInfo {
+                    offset_sec: self.std_offset_sec,
+                    dst_sec: 0,
+                    abbreviation: self.std_abbreviation.clone(),
+                }
+            }
+        };
+        date::OffsetInfo {
+            offset_sec: dst.offset_sec,
+            dst_sec: dst.offset_sec - self.std_offset_sec,
+            abbreviation: dst.abbreviation.clone(),
+        }
+    }
+
+    pub fn to_epoch(&self, date_time: NaiveDateTime) -> i64 {
+        date::local_to_epoch(date_time, |epoch_sec| self.offset_info(epoch_sec).offset_sec)
+    }
+
+    /// The span around `epoch_sec` in which the offset stays the same.
+    /// It ends at a DST change or at the turn of the (standard local) year, whichever comes first.
+    pub fn offset_span(&self, epoch_sec: i64) -> (i64, i64) {
+        let dst = match &self.dst {
+            Some(dst) => dst,
+            None => return (i64::MIN, i64::MAX),
+        };
+        let year = self.local_year(epoch_sec);
+        let year_start = |year: i32| {
+            let local = NaiveDate::from_ymd_opt(year, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+            local.timestamp() - self.std_offset_sec as i64
+        };
+        let (start, end) = self.dst_range(dst, year);
+
+        let bounds = [year_start(year), start, end, year_start(year + 1)];
+        let lower = bounds.iter().filter(|b| **b <= epoch_sec).max().copied().unwrap_or(i64::MIN);
+        let upper = bounds.iter().filter(|b| **b > epoch_sec).min().copied().unwrap_or(i64::MAX);
+        (lower.max(bounds[0]), upper.min(bounds[3]))
+    }
+
+    fn local_year(&self, epoch_sec: i64) -> i32 {
+        NaiveDateTime::from_timestamp_opt(epoch_sec + self.std_offset_sec as i64, 0)
+            .unwrap()
+            .year()
+    }
+
+    /// DST start and end of the year as epochs.
+    fn dst_range(&self, dst: &DstRule, year: i32) -> (i64, i64) {
+        let start = day_start_sec(year, &dst.start) + dst.start_time_sec as i64 - self.std_offset_sec as i64;
+        let end = day_start_sec(year, &dst.end) + dst.end_time_sec as i64 - dst.offset_sec as i64;
+        (start, end)
+    }
+
+    fn is_dst(&self, dst: &DstRule, epoch_sec: i64) -> bool {
+        let (start, end) = self.dst_range(dst, self.local_year(epoch_sec));
+
+        if start < end {
+            start <= epoch_sec && epoch_sec < end
+        } else {
+            // Southern hemisphere: DST spans the new year
+            !(end <= epoch_sec && epoch_sec < start)
+        }
+    }
+}
+
+/// Returns 00:00 of the day as seconds from the epoch (as if the local time was UTC).
+fn day_start_sec(year: i32, rule_date: &RuleDate) -> i64 {
+    let jan1 = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+    let is_leap = NaiveDate::from_ymd_opt(year, 2, 29).is_some();
+
+    let date = match rule_date {
+        RuleDate::Julian(n) => {
+            let skip_feb29 = is_leap && *n >= 60;
+            jan1 + chrono::Duration::days(*n as i64 - 1 + if skip_feb29 { 1 } else { 0 })
+        }
+        RuleDate::ZeroBasedJulian(n) => jan1 + chrono::Duration::days(*n as i64),
+        RuleDate::MonthWeekDay(m, w, d) => {
+            let first = NaiveDate::from_ymd_opt(year, *m, 1).unwrap();
+            let first_weekday = first.weekday().num_days_from_sunday();
+            let mut day = 1 + (d + 7 - first_weekday) % 7 + (w - 1) * 7;
+            while NaiveDate::from_ymd_opt(year, *m, day).is_none() {
+                day -= 7;
+            }
+            NaiveDate::from_ymd_opt(year, *m, day).unwrap()
+        }
+    };
+    date.and_hms_opt(0, 0, 0).unwrap().timestamp()
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(s: &'a str) -> Cursor<'a> {
+        Cursor {
+            bytes: s.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn is_end(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, b: u8) -> Option<()> {
+        if self.peek() != Some(b) {
+            return None;
+        }
+        self.pos += 1;
+        Some(())
+    }
+
+    /// "EST" or a quoted form such as "<+0330>"
+    fn abbreviation(&mut self) -> Option<String> {
+        let start = self.pos;
+        if self.peek() == Some(b'<') {
+            self.pos += 1;
+            while self.peek()?.is_ascii_alphanumeric() || self.peek()? == b'+' || self.peek()? == b'-' {
+                self.pos += 1;
+            }
+            self.expect(b'>')?;
+            let name = String::from_utf8_lossy(&self.bytes[start + 1..self.pos - 1]).to_string();
+            return if name.len() >= 3 { Some(name) } else { None };
+        }
+
+        while self.peek().is_some_and(|b| b.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        if self.pos - start < 3 {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&self.bytes[start..self.pos]).to_string())
+    }
+
+    /// [+-]hh[:mm[:ss]] as seconds
+    fn time(&mut self) -> Option<i32> {
+        let sign = match self.peek()? {
+            b'+' => {
+                self.pos += 1;
+                1
+            }
+            b'-' => {
+                self.pos += 1;
+                -1
+            }
+            _ => 1,
+        };
+        let hours = self.number(3)?;
+        let mut sec = hours * 3600;
+        if self.expect(b':').is_some() {
+            sec += self.number(2)? * 60;
+            if self.expect(b':').is_some() {
+                sec += self.number(2)?;
+            }
+        }
+        if hours > 167 {
+            return None;
+        }
+        Some(sign * sec)
+    }
+
+    /// Jn, n or Mm.w.d followed by an optional /time
+    fn rule(&mut self) -> Option<(RuleDate, i32)> {
+        let rule_date = match self.peek()? {
+            b'J' => {
+                self.pos += 1;
+                let n = self.number(3)? as u32;
+                if !(1..=365).contains(&n) {
+                    return None;
+                }
+                RuleDate::Julian(n)
+            }
+            b'M' => {
+                self.pos += 1;
+                let m = self.number(2)? as u32;
+                self.expect(b'.')?;
+                let w = self.number(1)? as u32;
+                self.expect(b'.')?;
+                let d = self.number(1)? as u32;
+                if !(1..=12).contains(&m) || !(1..=5).contains(&w) || d > 6 {
+                    return None;
+                }
+                RuleDate::MonthWeekDay(m, w, d)
+            }
+            _ => {
+                let n = self.number(3)? as u32;
+                if n > 365 {
+                    return None;
+                }
+                RuleDate::ZeroBasedJulian(n)
+            }
+        };
+
+        let time_sec = if self.expect(b'/').is_some() {
+            self.time()?
+        } else {
+            DEFAULT_RULE_TIME_SEC
+        };
+        Some((rule_date, time_sec))
+    }
+
+    fn number(&mut self, max_digits: usize) -> Option<i32> {
+        let start = self.pos;
+        while self.pos - start < max_digits && self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return None;
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos]).ok()?.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_offset(tz: &PosixTz, epoch_sec: i64, offset_sec: i32, abbreviation: &str) {
+        let info = tz.offset_info(epoch_sec);
+        assert_eq!(offset_sec, info.offset_sec, "{}", epoch_sec);
+        assert_eq!(abbreviation, info.abbreviation, "{}", epoch_sec);
+    }
+
+    #[test]
+    fn test_parse_fixed() {
+        let tz = PosixTz::parse("JST-9").unwrap();
+        assert_offset(&tz, 0, 9 * 3600, "JST");
+
+        let tz = PosixTz::parse("<+0330>-3:30").unwrap();
+        assert_offset(&tz, 0, 3 * 3600 + 1800, "+0330");
+
+        let tz = PosixTz::parse("<-03>3").unwrap();
+        assert_offset(&tz, 0, -3 * 3600, "-03");
+    }
+
+    #[test]
+    fn test_parse_dst() {
+        let tz = PosixTz::parse("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        // 2022-03-13T07:00:00Z = 02:00 EST
+        assert_offset(&tz, 1647154799, -5 * 3600, "EST");
+        assert_offset(&tz, 1647154800, -4 * 3600, "EDT");
+        // 2022-11-06T06:00:00Z = 02:00 EDT
+        assert_offset(&tz, 1667714399, -4 * 3600, "EDT");
+        assert_offset(&tz, 1667714400, -5 * 3600, "EST");
+
+        let tz = PosixTz::parse("PST8PDT").unwrap();
+        assert_offset(&tz, 1656633600, -7 * 3600, "PDT");
+        assert_offset(&tz, 1640995200, -8 * 3600, "PST");
+    }
+
+    #[test]
+    fn test_parse_southern() {
+        let tz = PosixTz::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+        // 2022-04-03T03:00:00+1100 = 2022-04-02T16:00:00Z
+        assert_offset(&tz, 1648915199, 11 * 3600, "AEDT");
+        assert_offset(&tz, 1648915200, 10 * 3600, "AEST");
+        // 2022-10-02T02:00:00+1000 = 2022-10-01T16:00:00Z
+        assert_offset(&tz, 1664639999, 10 * 3600, "AEST");
+        assert_offset(&tz, 1664640000, 11 * 3600, "AEDT");
+        assert_offset(&tz, 1640995200, 11 * 3600, "AEDT");
+    }
+
+    #[test]
+    fn test_parse_julian_and_extended_time() {
+        // Greenland: DST starts at -1:00 local (v3 extension)
+        let tz = PosixTz::parse("<-02>2<-01>,M3.5.0/-1,M10.5.0/0").unwrap();
+        // 2023-03-25T23:00:00-0200 = 2023-03-26T01:00:00Z
+        assert_offset(&tz, 1679792399, -2 * 3600, "-02");
+        assert_offset(&tz, 1679792400, -3600, "-01");
+
+        let tz = PosixTz::parse("XXX3YYY,J60/0,300/0").unwrap();
+        // J60 is always March 1
+        assert_offset(&tz, 1646103600, -2 * 3600, "YYY");
+        assert_offset(&tz, 1646103599, -3 * 3600, "XXX");
+    }
+
+    #[test]
+    fn test_to_epoch() {
+        let tz = PosixTz::parse("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        let dt = NaiveDate::from_ymd_opt(2022, 7, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+        assert_eq!(1656691200, tz.to_epoch(dt));
+        let dt = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+        assert_eq!(1641056400, tz.to_epoch(dt));
+        // 01:30 happens twice on 2022-11-06; the first one (EDT) is used
+        let dt = NaiveDate::from_ymd_opt(2022, 11, 6).unwrap().and_hms_opt(1, 30, 0).unwrap();
+        assert_eq!(1667712600, tz.to_epoch(dt));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        for s in [
+            "",
+            "J",
+            "JST",
+            "JS-9",
+            "EST5EDT,M3.2.0",
+            "EST5EDT,M13.2.0,M11.1.0",
+            "EST5EDT,M3.2.0,M11.1.0x",
+            "<+1>1",
+        ] {
+            assert!(PosixTz::parse(s).is_err(), "{}", s);
+        }
+
+        // Offsets are less than a day, while the times of the rules may be longer
+        assert_eq!(
+            Err("Invalid timezone: ABC-24".to_string()),
+            PosixTz::parse("ABC-24").map(|tz| tz.tz_str)
+        );
+        assert_eq!(
+            Err("Invalid timezone: ABC24".to_string()),
+            PosixTz::parse("ABC24").map(|tz| tz.tz_str)
+        );
+        assert_eq!(
+            Err("Invalid timezone: ABC-23DEF-24".to_string()),
+            PosixTz::parse("ABC-23DEF-24").map(|tz| tz.tz_str)
+        );
+        assert!(PosixTz::parse("ABC-23:59:59").is_ok());
+        assert!(PosixTz::parse("XXX3YYY,M3.2.0/167,M11.1.0/-167").is_ok());
+    }
+}
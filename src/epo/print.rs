@@ -1,58 +1,324 @@
-use super::date;
-use super::types::Zone;
-use chrono::{FixedOffset, TimeZone};
-use chrono_tz::Tz;
-use std::io::{stdout, BufWriter, Write};
+use super::types::{Column, EpochArg, Input, OutputOptions, PrintMode, Zone};
+use super::zone::OffsetCache;
+use super::{date, metazone, plan, windows};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc, Weekday};
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::io::{self, stdout, BufWriter, Write};
+use std::iter::Peekable;
+use std::thread;
+use unicode_width::UnicodeWidthChar;
+
+const COLOR_OFFSET_CHANGE: &str = "1;33";
+const COLOR_OTHER_DATE: &str = "36";
+const COLOR_OFF_HOURS: &str = "2";
+
+/// Width of "2022-04-17T21:09:49+0900"
+const ISO_DATE_WIDTH: usize = 24;
+/// 0001-01-02T00:00:00Z and 9999-12-31T00:00:00Z
+const FOUR_DIGIT_YEARS: (i64, i64) = (-62135510400, 253402214400);
+/// Rows converted by a thread at a time
+const CHUNK_ROWS: usize = 4096;
+
+pub fn to_string_rows_from_epochs(
+    epochs: impl IntoIterator<Item = i64>,
+    timezones: &[Zone],
+    output: &OutputOptions,
+) -> (Vec<String>, Vec<Vec<String>>) {
+    let (headers, chunks) = epoch_rows(epochs.into_iter(), timezones, output);
+    (headers, chunks.flat_map(|chunk| chunk.to_rows()).collect())
+}
+
+/// The Epoch -> Date table of every input in the order of the arguments, led by the input and its kind.
+/// Dates without an offset are read in the zone at `local_zone`, which their kind names.
+pub fn to_string_rows_from_inputs(
+    inputs: &[Input],
+    epoch_args: &[EpochArg],
+    date_infos: &[date::DateInfo],
+    timezones: &[Zone],
+    local_zone: usize,
+    output: &OutputOptions,
+) -> (Vec<String>, Vec<Vec<String>>) {
+    let local_date_kind = match timezones.get(local_zone) {
+        Some(zone) => format!("local date ({})", zone_header(zone)),
+        None => "local date (UTC)".to_string(),
+    };
+    // Each epoch of the inputs with the input and its kind
+    let mut epochs: Vec<(i64, &str, &str)> = Vec::new();
+    for input in inputs {
+        match input {
+            Input::Epoch(i) | Input::DateWithOffset(i) => {
+                let kind = if let Input::Epoch(_) = input { "epoch" } else { "date with offset" };
+                let datestr = epoch_args[*i].datestr.as_str();
+                epochs.extend(epoch_args[*i].epochs.iter().map(|epoch_sec| (epoch_sec, datestr, kind)));
+            }
+            Input::Date(i) => {
+                let epoch_sec = local_epoch(date_infos[*i].date_time, timezones, local_zone);
+                epochs.push((epoch_sec, date_infos[*i].datestr.as_str(), &local_date_kind));
+            }
+        }
+    }
+    let (epoch_header, epoch_rows) = to_string_rows_from_epochs(epochs.iter().map(|e| e.0), timezones, output);
+
+    let mut header: Vec<String> = vec!["Input".to_string(), "Kind".to_string()];
+    header.extend(epoch_header);
+    let rows = epochs
+        .iter()
+        .zip(epoch_rows)
+        .map(|((_, datestr, kind), epoch_row)| {
+            let mut row: Vec<String> = vec![datestr.to_string(), kind.to_string()];
+            row.extend(epoch_row);
+            row
+        })
+        .collect();
+    (header, rows)
+}
+
+/// The epoch of a date without an offset read in the zone at `local_zone`, or in UTC without zones.
+pub fn local_epoch(date_time: NaiveDateTime, timezones: &[Zone], local_zone: usize) -> i64 {
+    timezones.get(local_zone).map_or(date_time.timestamp(), |t| t.to_epoch(date_time))
+}
+
+/// Rows of the Epoch -> Date table, made a chunk at a time so that they can be printed without keeping them all.
+/// Chunks are converted by several threads at once and come out in the order of the epochs.
+/// The epochs are taken from their source only as the chunks are made.
+pub struct EpochRows<'a> {
+    epochs: Peekable<Box<dyn Iterator<Item = i64> + 'a>>,
+    /// The last epoch taken
+    previous: Option<i64>,
+    jobs: usize,
+    maker: RowMaker<'a>,
+    chunks: VecDeque<RowChunk>,
+}
+
+/// What every thread needs to format the rows
+struct RowMaker<'a> {
+    timezones: &'a [Zone],
+    formats: Vec<Option<&'a date::DateFormat>>,
+    output: &'a OutputOptions,
+    epoch_columns: Vec<Column>,
+    zone_columns: Vec<Column>,
+    columns: usize,
+    now: i64,
+    reference: i64,
+}
+
+/// Rows formatted into one buffer instead of a String per cell
+pub struct RowChunk {
+    text: String,
+    cell_ends: Vec<usize>,
+    columns: usize,
+}
+
+pub fn epoch_rows<'a>(
+    epochs: impl Iterator<Item = i64> + 'a,
+    timezones: &'a [Zone],
+    output: &'a OutputOptions,
+) -> (Vec<String>, EpochRows<'a>) {
+    let mut epochs = (Box::new(epochs) as Box<dyn Iterator<Item = i64> + 'a>).peekable();
+    let (epoch_columns, zone_columns): (Vec<Column>, Vec<Column>) = output.columns.iter().partition(|c| !is_zone_column(**c));
 
-pub fn to_string_rows_from_epochs(epoch_infos: &Vec<date::EpochInfo>, timezones: &Vec<Zone>) -> (Vec<String>, Vec<Vec<String>>) {
     let mut headers: Vec<String> = vec!["Epoch".to_string()];
+    headers.extend(epoch_columns.iter().map(|c| column_header(*c).to_string()));
     for t in timezones {
-        match t {
-            Zone::Offset(offset_sec) => headers.push(date::to_offset_str(*offset_sec)),
-            Zone::Tzname(tzname) => headers.push(tzname.to_string()),
+        headers.push(to_header(t, output));
+        headers.extend(zone_columns.iter().map(|c| column_header(*c).to_string()));
+    }
+
+    let jobs = match output.jobs {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        jobs => jobs,
+    };
+    let maker = RowMaker {
+        timezones,
+        formats: timezones.iter().map(|t| zone_format(t, output)).collect(),
+        output,
+        epoch_columns,
+        zone_columns,
+        columns: headers.len(),
+        now: date::current_epoch(),
+        reference: output.reference.or(epochs.peek().copied()).unwrap_or(0),
+    };
+    let rows = EpochRows {
+        epochs,
+        previous: None,
+        jobs,
+        maker,
+        chunks: VecDeque::new(),
+    };
+    (headers, rows)
+}
+
+impl EpochRows<'_> {
+    /// Converts the next chunk for every job.
+    fn convert(&mut self) {
+        let epochs: Vec<i64> = self.epochs.by_ref().take(CHUNK_ROWS.saturating_mul(self.jobs)).collect();
+        // Each chunk with the epoch before it
+        let ranges: Vec<(&[i64], Option<i64>)> = epochs
+            .chunks(CHUNK_ROWS)
+            .scan(self.previous, |previous, chunk| {
+                Some((chunk, previous.replace(chunk[chunk.len() - 1])))
+            })
+            .collect();
+        let maker = &self.maker;
+        let make = move |(chunk, previous): (&[i64], Option<i64>)| maker.make_chunk(chunk, previous);
+        if ranges.len() == 1 {
+            self.chunks.push_back(make(ranges[0]));
+        } else {
+            thread::scope(|scope| {
+                let handles: Vec<_> = ranges.iter().map(|range| scope.spawn(move || make(*range))).collect();
+                self.chunks.extend(handles.into_iter().map(|handle| handle.join().unwrap()));
+            });
         }
+        self.previous = epochs.last().copied().or(self.previous);
     }
+}
 
-    let mut rows: Vec<Vec<String>> = vec![];
+impl Iterator for EpochRows<'_> {
+    type Item = RowChunk;
 
-    for date in epoch_infos {
-        let mut row: Vec<String> = vec![date.epoch_sec.to_string()];
-        for t in timezones {
-            let s = match t {
-                Zone::Offset(offset_sec) => date::to_datestr(date.epoch_sec, *offset_sec),
-                Zone::Tzname(tzname) => date::to_datestr_with_tz(date.epoch_sec, tzname),
-            };
-            row.push(s);
+    fn next(&mut self) -> Option<RowChunk> {
+        if self.chunks.is_empty() && self.epochs.peek().is_some() {
+            self.convert();
         }
-        rows.push(row);
+        self.chunks.pop_front()
     }
-    (headers, rows)
 }
 
-pub fn to_string_rows_from_dates(date_infos: &Vec<date::DateInfo>, timezones: &Vec<Zone>) -> (Vec<String>, Vec<Vec<String>>) {
+impl RowMaker<'_> {
+    /// The epoch before the chunk, if any, is what the first row is compared with (offset changes and deltas).
+    fn make_chunk(&self, epochs: &[i64], previous: Option<i64>) -> RowChunk {
+        let output = self.output;
+        let mut offset_caches: Vec<OffsetCache> = self.timezones.iter().map(OffsetCache::new).collect();
+        let mut previous_dates: Vec<Option<DateTime<FixedOffset>>> = offset_caches
+            .iter_mut()
+            .map(|cache| previous.map(|p| to_date_time(p, cache.offset_info(p).offset_sec)))
+            .collect();
+        let mut previous_epoch = previous;
+
+        let cells = epochs.len() * self.columns;
+        let mut chunk = RowChunk {
+            text: String::with_capacity(cells * ISO_DATE_WIDTH),
+            cell_ends: Vec::with_capacity(cells),
+            columns: self.columns,
+        };
+        for &epoch_sec in epochs {
+            write!(chunk.text, "{}", epoch_sec).unwrap();
+            chunk.end_cell();
+            for c in &self.epoch_columns {
+                chunk
+                    .text
+                    .push_str(&epoch_column_value(*c, epoch_sec, previous_epoch, self.now, self.reference));
+                chunk.end_cell();
+            }
+            let mut first_date: Option<NaiveDate> = None;
+            for (i, t) in self.timezones.iter().enumerate() {
+                let offset_info = offset_caches[i].offset_info(epoch_sec);
+                let dt = to_date_time(epoch_sec, offset_info.offset_sec);
+                let first_date = *first_date.get_or_insert(dt.date_naive());
+                let colors = if output.color {
+                    let previous_offset = previous_dates[i].map(|p| p.offset().local_minus_utc());
+                    cell_colors(&dt, previous_offset, first_date)
+                } else {
+                    Vec::new()
+                };
+                if !colors.is_empty() {
+                    write!(chunk.text, "\x1b[{}m", colors.join(";")).unwrap();
+                }
+                match self.formats[i] {
+                    Some(format) => chunk
+                        .text
+                        .push_str(&to_datestr_with_format(epoch_sec, t, offset_info.offset_sec, format)),
+                    None => date::write_datestr(&mut chunk.text, &dt),
+                }
+                if output.abbreviation || output.long_name {
+                    append_zone_names(&mut chunk.text, t, offset_info, output);
+                }
+                if !colors.is_empty() {
+                    chunk.text.push_str("\x1b[0m");
+                }
+                chunk.end_cell();
+                for c in &self.zone_columns {
+                    chunk.text.push_str(&zone_column_value(*c, &dt, previous_dates[i].as_ref()));
+                    chunk.end_cell();
+                }
+                previous_dates[i] = Some(dt);
+            }
+            previous_epoch = Some(epoch_sec);
+        }
+        chunk
+    }
+}
+
+impl RowChunk {
+    fn end_cell(&mut self) {
+        self.cell_ends.push(self.text.len());
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.cell_ends.len() / self.columns
+    }
+
+    pub fn row(&self, index: usize) -> impl Iterator<Item = &str> {
+        (index * self.columns..(index + 1) * self.columns).map(move |i| {
+            let start = if i == 0 { 0 } else { self.cell_ends[i - 1] };
+            &self.text[start..self.cell_ends[i]]
+        })
+    }
+
+    pub fn to_rows(&self) -> Vec<Vec<String>> {
+        (0..self.row_count())
+            .map(|i| self.row(i).map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+}
+
+/// Widths of the Epoch -> Date table known without making the rows: every date is ISO 8601 with a four-digit year.
+/// None if the cells can have other widths (formats, zone names, extra columns).
+pub fn fixed_column_widths(epoch_args: &[EpochArg], header: &[String], output: &OutputOptions, mode: &PrintMode) -> Option<Vec<usize>> {
+    if output.abbreviation || output.long_name || output.format.is_some() || !output.zone_formats.is_empty() || !output.columns.is_empty() {
+        return None;
+    }
+    let (min, max) = epoch_args
+        .iter()
+        .filter_map(|e| e.epochs.bounds())
+        .reduce(|(min, max), (start, end)| (min.min(start), max.max(end)))?;
+    // A day of margin for the offsets
+    if min < FOUR_DIGIT_YEARS.0 || max > FOUR_DIGIT_YEARS.1 {
+        return None;
+    }
+    let epoch_width = min.to_string().len().max(max.to_string().len());
+    let widths = header
+        .iter()
+        .enumerate()
+        .map(|(i, cell)| {
+            let cell_width = if i == 0 { epoch_width } else { ISO_DATE_WIDTH };
+            display_width(&escape_cell(cell, mode)).max(cell_width)
+        })
+        .collect();
+    Some(widths)
+}
+
+pub fn to_string_rows_from_dates(
+    date_infos: &Vec<date::DateInfo>,
+    timezones: &Vec<Zone>,
+    output: &OutputOptions,
+) -> (Vec<String>, Vec<Vec<String>>) {
     let mut headers: Vec<String> = vec!["Date".to_string()];
     for t in timezones {
-        match t {
-            Zone::Offset(offset_sec) => headers.push(date::to_offset_str(*offset_sec)),
-            Zone::Tzname(tzname) => headers.push(tzname.to_string()),
-        }
+        headers.push(to_header(t, output));
     }
     let mut rows: Vec<Vec<String>> = vec![];
 
     for date in date_infos {
-        let mut row: Vec<String> = vec![date::to_datestr_from_ndt(date.date_time)];
+        let datestr = match &output.format {
+            Some(format) => date::to_naive_datestr_with_format(date.date_time, format),
+            None => date::to_datestr_from_ndt(date.date_time),
+        };
+        let mut row: Vec<String> = vec![datestr];
         for t in timezones {
-            match t {
-                Zone::Offset(offset_sec) => {
-                    let dt = FixedOffset::east(*offset_sec).from_local_datetime(&date.date_time).unwrap();
-                    row.push(dt.timestamp().to_string());
-                }
-                Zone::Tzname(tzname) => {
-                    let tz: Tz = tzname.parse().unwrap();
-                    let dt = tz.from_local_datetime(&date.date_time).unwrap();
-                    row.push(dt.timestamp().to_string());
-                }
-            }
+            row.push(t.to_epoch(date.date_time).to_string());
         }
         rows.push(row);
     }
@@ -60,44 +326,392 @@ pub fn to_string_rows_from_dates(date_infos: &Vec<date::DateInfo>, timezones: &V
     (headers, rows)
 }
 
+/// SGR parameters of a date cell: the offset changed since the previous row, the date differs from the first zone,
+/// or the local time is on a weekend or out of working hours.
+fn cell_colors(dt: &DateTime<FixedOffset>, previous_offset: Option<i32>, first_date: NaiveDate) -> Vec<&'static str> {
+    let mut colors: Vec<&'static str> = Vec::new();
+    // Dim comes first so that bold wins where both apply
+    let (start, end) = plan::DEFAULT_WORKING_HOURS;
+    if matches!(dt.weekday(), Weekday::Sat | Weekday::Sun) || dt.hour() < start || dt.hour() >= end {
+        colors.push(COLOR_OFF_HOURS);
+    }
+    if dt.date_naive() != first_date {
+        colors.push(COLOR_OTHER_DATE);
+    }
+    if previous_offset.is_some_and(|offset_sec| offset_sec != dt.offset().local_minus_utc()) {
+        colors.push(COLOR_OFFSET_CHANGE);
+    }
+    colors
+}
+
+/// Width of a cell on the terminal; ANSI escape sequences take no space and East Asian wide characters take two columns.
+pub fn display_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // CSI sequences end with a byte in 0x40..=0x7e
+            chars.by_ref().skip(1).find(|c| ('@'..='~').contains(c));
+            continue;
+        }
+        width += c.width().unwrap_or(0);
+    }
+    width
+}
+
+/// Numbers of the leading columns of the Epoch -> Date table (Epoch and the columns after it)
+/// and of the columns of each zone (the date and the columns after it).
+pub fn column_groups(output: &OutputOptions) -> (usize, usize) {
+    let zone_columns = output.columns.iter().filter(|c| is_zone_column(**c)).count();
+    (1 + output.columns.len() - zone_columns, 1 + zone_columns)
+}
+
+/// Weekdays, weeks and the like differ between zones; the other columns are the same for every zone.
+fn is_zone_column(column: Column) -> bool {
+    matches!(
+        column,
+        Column::Weekday | Column::IsoWeek | Column::UsWeek | Column::DayOfYear | Column::Quarter | Column::Rfc2822 | Column::CalendarDelta
+    )
+}
+
+fn column_header(column: Column) -> &'static str {
+    match column {
+        Column::Weekday => "Weekday",
+        Column::IsoWeek => "ISO week",
+        Column::UsWeek => "US week",
+        Column::DayOfYear => "Day",
+        Column::Quarter => "Quarter",
+        Column::Relative => "Relative",
+        Column::Milliseconds => "Milliseconds",
+        Column::Microseconds => "Microseconds",
+        Column::Nanoseconds => "Nanoseconds",
+        Column::Hex => "Hex",
+        Column::Rfc2822 => "RFC 2822",
+        Column::Delta => "Delta",
+        Column::DeltaTime => "Delta time",
+        Column::Elapsed => "Elapsed",
+        Column::ElapsedTime => "Elapsed time",
+        Column::CalendarDelta => "Calendar delta",
+    }
+}
+
+fn to_date_time(epoch_sec: i64, offset_sec: i32) -> DateTime<FixedOffset> {
+    Utc.timestamp_opt(epoch_sec, 0)
+        .unwrap()
+        .with_timezone(&FixedOffset::east_opt(offset_sec).unwrap())
+}
+
+/// Columns of an epoch; the deltas are empty on the first row.
+fn epoch_column_value(column: Column, epoch_sec: i64, previous: Option<i64>, now: i64, reference: i64) -> String {
+    match column {
+        Column::Relative => date::to_relative_str(epoch_sec, now),
+        Column::Delta => previous.map_or("".to_string(), |p| format!("{:+}", epoch_sec - p)),
+        Column::DeltaTime => previous.map_or("".to_string(), |p| date::to_duration_str(epoch_sec - p)),
+        Column::Elapsed => format!("{:+}", epoch_sec - reference),
+        Column::ElapsedTime => date::to_duration_str(epoch_sec - reference),
+        Column::Milliseconds => (epoch_sec as i128 * 1_000).to_string(),
+        Column::Microseconds => (epoch_sec as i128 * 1_000_000).to_string(),
+        Column::Nanoseconds => (epoch_sec as i128 * 1_000_000_000).to_string(),
+        Column::Hex if epoch_sec < 0 => format!("-0x{:x}", epoch_sec.unsigned_abs()),
+        Column::Hex => format!("0x{:x}", epoch_sec),
+        _ => "".to_string(),
+    }
+}
+
+/// Columns of a date in a zone; `previous` is the date of the row above in the same zone.
+fn zone_column_value(column: Column, dt: &DateTime<FixedOffset>, previous: Option<&DateTime<FixedOffset>>) -> String {
+    match column {
+        Column::Weekday => dt.format("%A").to_string(),
+        // ISO 8601 week-numbering year, which differs from the calendar year around New Year
+        Column::IsoWeek => dt.format("%G-W%V").to_string(),
+        // Weeks start on Sunday; days before the first Sunday are in week 00
+        Column::UsWeek => dt.format("%U").to_string(),
+        Column::DayOfYear => dt.format("%j").to_string(),
+        Column::Quarter => format!("Q{}", (dt.month() - 1) / 3 + 1),
+        Column::Rfc2822 => dt.format("%a, %d %b %Y %H:%M:%S %z").to_string(),
+        Column::CalendarDelta => previous.map_or("".to_string(), |p| date::to_calendar_delta_str(p.naive_local(), dt.naive_local())),
+        _ => "".to_string(),
+    }
+}
+
+/// The format of a column given with zone=format, or the one given with --format.
+fn zone_format<'a>(zone: &Zone, output: &'a OutputOptions) -> Option<&'a date::DateFormat> {
+    output.zone_formats.get(&zone_header(zone)).or(output.format.as_ref())
+}
+
+/// Zones of chrono-tz know their abbreviations (%Z); the others are formatted with the offset.
+fn to_datestr_with_format(epoch_sec: i64, zone: &Zone, offset_sec: i32, format: &date::DateFormat) -> String {
+    match zone {
+        Zone::Tz(tz) => date::to_datestr_with_tz_format(epoch_sec, *tz, format),
+        _ => date::to_datestr_with_format(epoch_sec, offset_sec, format),
+    }
+}
+
+/// Appends the abbreviation (e.g. "PDT") and the long name (e.g. "(Pacific Daylight Time)") to a date.
+/// Long names are given for the zones of the embedded data.
+fn append_zone_names(datestr: &mut String, zone: &Zone, offset_info: &date::OffsetInfo, output: &OutputOptions) {
+    let zone_name = match zone {
+        Zone::Offset(_) => return,
+        Zone::Tz(tz) => Some(tz.name()),
+        _ => None,
+    };
+
+    if output.abbreviation {
+        datestr.push(' ');
+        datestr.push_str(&offset_info.abbreviation);
+    }
+    if output.long_name {
+        let long_name = zone_name.and_then(|name| metazone::long_name(name, offset_info.dst_sec > 0, &offset_info.abbreviation));
+        if let Some(long_name) = long_name {
+            write!(datestr, " ({})", long_name).unwrap();
+        }
+    }
+}
+
+fn to_header(zone: &Zone, output: &OutputOptions) -> String {
+    let header = zone_header(zone);
+    if !output.windows_id {
+        return header;
+    }
+    let windows_id = match zone {
+        Zone::Tz(tz) => windows::to_windows_id(tz.name()),
+        Zone::Rules(rules) => windows::to_windows_id(&rules.name),
+        _ => None,
+    };
+    match windows_id {
+        Some(windows_id) => format!("{} ({})", header, windows_id),
+        None => header,
+    }
+}
+
+pub fn zone_header(zone: &Zone) -> String {
+    match zone {
+        Zone::Offset(offset_sec) => date::to_offset_str(*offset_sec),
+        Zone::Tz(tz) => tz.name().to_string(),
+        Zone::Posix(posix_tz) => posix_tz.tz_str.to_string(),
+        Zone::Rules(rules) => rules.header(),
+    }
+}
+
+/// Prints a table in the style of the mode (Markdown for the modes which are not table styles).
 #[allow(unused_must_use)]
-pub fn print_markdown_table(header: &[String], data: &[Vec<String>]) {
+pub fn print_table(header: &[String], data: &[Vec<String>], mode: &PrintMode) {
     let out = stdout();
     let mut buf = BufWriter::new(out.lock());
+    write_table(&mut buf, header, data, mode);
+}
+
+fn write_table(out: &mut impl Write, header: &[String], data: &[Vec<String>], mode: &PrintMode) -> io::Result<()> {
+    // Cells are escaped before measuring so that every style shares the same widths
+    let header: Vec<String> = header.iter().map(|cell| escape_cell(cell, mode).into_owned()).collect();
+    let data: Vec<Vec<String>> = data
+        .iter()
+        .map(|row| row.iter().map(|cell| escape_cell(cell, mode).into_owned()).collect())
+        .collect();
+    let max_lengths = calc_max_column_length(&header, &data);
+    write_head(out, &header, &max_lengths, mode)?;
+    for row in &data {
+        write_body_row(out, row, &max_lengths, mode)?;
+    }
+    write_foot(out, &max_lengths, mode)
+}
+
+/// Prints rows as they are made; the widths must be known beforehand.
+/// Stops when the output is closed (e.g. by head) instead of making the rest of the rows.
+#[allow(unused_must_use)]
+pub fn print_table_stream(header: &[String], chunks: impl Iterator<Item = RowChunk>, max_lengths: &[usize], mode: &PrintMode) {
+    let out = stdout();
+    let mut buf = BufWriter::new(out.lock());
+    let header: Vec<Cow<str>> = header.iter().map(|cell| escape_cell(cell, mode)).collect();
+    write_head(&mut buf, &header, max_lengths, mode);
+    for chunk in chunks {
+        for i in 0..chunk.row_count() {
+            if write_body_row(&mut buf, chunk.row(i).map(|cell| escape_cell(cell, mode)), max_lengths, mode).is_err() {
+                return;
+            }
+        }
+    }
+    write_foot(&mut buf, max_lengths, mode);
+}
+
+/// Writes the escaped header in the style of the mode, with the lines and the markup before the rows.
+fn write_head<S: AsRef<str>>(out: &mut impl Write, header: &[S], max_lengths: &[usize], mode: &PrintMode) -> io::Result<()> {
+    match mode {
+        PrintMode::Box => {
+            write_rule(out, max_lengths, ["┌", "─", "┬", "┐"])?;
+            write_row(out, header, max_lengths, ["│ ", " │ ", " │"])?;
+            write_rule(out, max_lengths, ["├", "─", "┼", "┤"])
+        }
+        PrintMode::Org => {
+            write_row(out, header, max_lengths, ["| ", " | ", " |"])?;
+            write_rule(out, max_lengths, ["|", "-", "+", "|"])
+        }
+        PrintMode::Rst => {
+            write_rule(out, max_lengths, ["+", "-", "+", "+"])?;
+            write_row(out, header, max_lengths, ["| ", " | ", " |"])?;
+            write_rule(out, max_lengths, ["+", "=", "+", "+"])
+        }
+        PrintMode::AsciiDoc => {
+            writeln!(out, "[cols=\"{}\",options=\"header\"]", vec![">"; header.len()].join(","))?;
+            writeln!(out, "|===")?;
+            write_row(out, header, max_lengths, ["| ", " | ", ""])
+        }
+        PrintMode::Latex => {
+            writeln!(out, "\\begin{{tabular}}{{{}}}", "r".repeat(header.len()))?;
+            writeln!(out, "\\hline")?;
+            write_row(out, header, max_lengths, ["", " & ", " \\\\"])?;
+            writeln!(out, "\\hline")
+        }
+        PrintMode::Html => {
+            writeln!(out, "<table>")?;
+            writeln!(out, "  <thead>")?;
+            write_html_row(out, header, "th")?;
+            writeln!(out, "  </thead>")?;
+            writeln!(out, "  <tbody>")
+        }
+        _ => {
+            write_row(out, header, max_lengths, ["| ", " | ", " |"])?;
+            writeln!(out, "{}", generate_header_line(max_lengths))
+        }
+    }
+}
 
-    let max_lengths = calc_max_column_length(header, data);
+/// Writes a row of escaped cells in the style of the mode.
+fn write_body_row<S: AsRef<str>>(
+    out: &mut impl Write,
+    row: impl IntoIterator<Item = S>,
+    max_lengths: &[usize],
+    mode: &PrintMode,
+) -> io::Result<()> {
+    match mode {
+        PrintMode::Box => write_row(out, row, max_lengths, ["│ ", " │ ", " │"]),
+        PrintMode::Rst => {
+            write_row(out, row, max_lengths, ["| ", " | ", " |"])?;
+            write_rule(out, max_lengths, ["+", "-", "+", "+"])
+        }
+        PrintMode::AsciiDoc => write_row(out, row, max_lengths, ["| ", " | ", ""]),
+        PrintMode::Latex => write_row(out, row, max_lengths, ["", " & ", " \\\\"]),
+        PrintMode::Html => write_html_row(out, row, "td"),
+        _ => write_row(out, row, max_lengths, ["| ", " | ", " |"]),
+    }
+}
 
-    for (i, cell) in header.iter().enumerate() {
-        let width = max_lengths[i];
-        write!(buf, "| {:>width$} ", cell);
+/// Writes the lines and the markup after the rows.
+fn write_foot(out: &mut impl Write, max_lengths: &[usize], mode: &PrintMode) -> io::Result<()> {
+    match mode {
+        PrintMode::Box => write_rule(out, max_lengths, ["└", "─", "┴", "┘"]),
+        PrintMode::AsciiDoc => writeln!(out, "|==="),
+        PrintMode::Latex => {
+            writeln!(out, "\\hline")?;
+            writeln!(out, "\\end{{tabular}}")
+        }
+        PrintMode::Html => {
+            writeln!(out, "  </tbody>")?;
+            writeln!(out, "</table>")
+        }
+        _ => Ok(()),
     }
-    writeln!(buf, "|");
-    writeln!(buf, "{}", generate_header_line(&max_lengths));
-    for (_, row) in data.iter().enumerate() {
-        for (i, cell) in row.iter().enumerate() {
-            let width = max_lengths[i];
-            write!(buf, "| {:>width$} ", cell);
+}
+
+/// Writes right-aligned cells between the left edge, the separators and the right edge.
+fn write_row<S: AsRef<str>>(
+    out: &mut impl Write,
+    row: impl IntoIterator<Item = S>,
+    max_lengths: &[usize],
+    [left, separator, right]: [&str; 3],
+) -> io::Result<()> {
+    write!(out, "{}", left)?;
+    for (i, cell) in row.into_iter().enumerate() {
+        let cell = cell.as_ref();
+        if i > 0 {
+            write!(out, "{}", separator)?;
         }
-        writeln!(buf, "|");
+        let padding = max_lengths[i].saturating_sub(display_width(cell));
+        write!(out, "{:padding$}{}", "", cell)?;
+    }
+    writeln!(out, "{}", right)
+}
+
+/// Writes a horizontal line from the left corner, the fill, the crossings and the right corner.
+fn write_rule(out: &mut impl Write, max_lengths: &[usize], [left, fill, crossing, right]: [&str; 4]) -> io::Result<()> {
+    let cells: Vec<String> = max_lengths.iter().map(|max_length| fill.repeat(max_length + 2)).collect();
+    writeln!(out, "{}{}{}", left, cells.join(crossing), right)
+}
+
+fn write_html_row<S: AsRef<str>>(out: &mut impl Write, row: impl IntoIterator<Item = S>, tag: &str) -> io::Result<()> {
+    write!(out, "    <tr>")?;
+    for cell in row {
+        write!(out, "<{tag}>{}</{tag}>", cell.as_ref())?;
+    }
+    writeln!(out, "</tr>")
+}
+
+/// Escapes the characters which have a meaning in the style (e.g. "|" in Markdown, "_" in LaTeX).
+/// Cells without such characters are borrowed as they are.
+fn escape_cell<'a>(cell: &'a str, mode: &PrintMode) -> Cow<'a, str> {
+    let special: &[char] = match mode {
+        PrintMode::Markdown | PrintMode::AsciiDoc | PrintMode::Org => &['|'],
+        PrintMode::Latex => &['\\', '~', '^', '&', '%', '$', '#', '_', '{', '}'],
+        PrintMode::Html => &['&', '<', '>', '"'],
+        _ => &[],
+    };
+    if !cell.contains(special) {
+        return Cow::Borrowed(cell);
     }
+    let escaped = match mode {
+        PrintMode::Markdown | PrintMode::AsciiDoc => cell.replace('|', "\\|"),
+        PrintMode::Org => cell.replace('|', "\\vert{}"),
+        PrintMode::Latex => cell
+            .chars()
+            .map(|c| match c {
+                '\\' => "\\textbackslash{}".to_string(),
+                '~' => "\\textasciitilde{}".to_string(),
+                '^' => "\\textasciicircum{}".to_string(),
+                '&' | '%' | '$' | '#' | '_' | '{' | '}' => format!("\\{}", c),
+                _ => c.to_string(),
+            })
+            .collect(),
+        _ => cell
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;"),
+    };
+    Cow::Owned(escaped)
 }
 
 #[allow(unused_must_use)]
-pub fn print_as_plaintext(data: &[Vec<String>], delimiter: &str) {
+pub fn print_as_plaintext<R: AsRef<[String]>>(data: impl IntoIterator<Item = R>, delimiter: &str) {
     let out = stdout();
     let mut buf = BufWriter::new(out.lock());
+    for row in data {
+        write_plaintext_row(&mut buf, row.as_ref(), delimiter);
+    }
+}
 
-    for (_, row) in data.iter().enumerate() {
-        for (i, cell) in row.iter().enumerate() {
-            if i > 0 {
-                write!(buf, "{}", delimiter);
+/// Prints rows as they are made without aligning them, until the output is closed.
+pub fn print_stream_as_plaintext(chunks: impl Iterator<Item = RowChunk>, delimiter: &str) {
+    let out = stdout();
+    let mut buf = BufWriter::new(out.lock());
+    for chunk in chunks {
+        for i in 0..chunk.row_count() {
+            if write_plaintext_row(&mut buf, chunk.row(i), delimiter).is_err() {
+                return;
             }
-            write!(buf, "{}", cell);
         }
-        writeln!(buf);
     }
 }
 
+fn write_plaintext_row<S: AsRef<str>>(out: &mut impl Write, row: impl IntoIterator<Item = S>, delimiter: &str) -> io::Result<()> {
+    for (i, cell) in row.into_iter().enumerate() {
+        if i > 0 {
+            write!(out, "{}", delimiter)?;
+        }
+        write!(out, "{}", cell.as_ref())?;
+    }
+    writeln!(out)
+}
+
 fn generate_header_line(max_lengths: &[usize]) -> String {
     let mut header_line = "".to_string();
     for max_length in max_lengths.iter() {
@@ -109,19 +723,19 @@ fn generate_header_line(max_lengths: &[usize]) -> String {
     header_line
 }
 
-fn calc_max_column_length(header: &[String], data: &[Vec<String>]) -> Vec<usize> {
-    let mut max_len: Vec<usize> = vec![0; data[0].len()];
+pub fn calc_max_column_length(header: &[String], data: &[Vec<String>]) -> Vec<usize> {
+    let mut max_len: Vec<usize> = vec![0; header.len()];
 
     for (i, col) in header.iter().enumerate() {
-        if max_len[i] < col.len() {
-            max_len[i] = col.len();
+        if max_len[i] < display_width(col) {
+            max_len[i] = display_width(col);
         }
     }
 
     for row in data {
         for (i, col) in row.iter().enumerate() {
-            if max_len[i] < col.len() {
-                max_len[i] = col.len();
+            if max_len[i] < display_width(col) {
+                max_len[i] = display_width(col);
             }
         }
     }
@@ -131,15 +745,522 @@ fn calc_max_column_length(header: &[String], data: &[Vec<String>]) -> Vec<usize>
 
 #[cfg(test)]
 mod tests {
+    use super::super::posix::PosixTz;
     use super::*;
 
     #[test]
     fn test_to_string_rows_empty() {
-        let epochs: Vec<date::EpochInfo> = Vec::new();
+        let epochs: [i64; 0] = [];
         let timezones: Vec<Zone> = Vec::new();
 
-        let (h, d) = to_string_rows_from_epochs(&epochs, &timezones);
+        let (h, d) = to_string_rows_from_epochs(epochs, &timezones, &OutputOptions::default());
         assert_eq!(1, h.len());
         assert_eq!(0, d.len());
     }
+
+    #[test]
+    fn test_to_string_rows_posix() {
+        let epochs = [1647154799, 1647154800];
+        let timezones = vec![Zone::Posix(PosixTz::parse("EST5EDT,M3.2.0,M11.1.0").unwrap())];
+
+        let (h, d) = to_string_rows_from_epochs(epochs, &timezones, &OutputOptions::default());
+        assert_eq!(vec!["Epoch", "EST5EDT,M3.2.0,M11.1.0"], h);
+        assert_eq!("2022-03-13T01:59:59-0500", d[0][1]);
+        assert_eq!("2022-03-13T03:00:00-0400", d[1][1]);
+
+        let dates = vec![date::DateInfo {
+            date_time: chrono::NaiveDate::from_ymd_opt(2022, 7, 1).unwrap().and_hms_opt(12, 0, 0).unwrap(),
+            datestr: "".to_string(),
+        }];
+        let (_, d) = to_string_rows_from_dates(&dates, &timezones, &OutputOptions::default());
+        assert_eq!("1656691200", d[0][1]);
+    }
+
+    #[test]
+    fn test_to_string_rows_zone_names() {
+        let epochs = [1656633600, -2717668800];
+        let timezones = vec![
+            Zone::Tz("America/Los_Angeles".parse().unwrap()),
+            Zone::Tz("America/Phoenix".parse().unwrap()),
+            Zone::Offset(0),
+        ];
+        let output = OutputOptions {
+            abbreviation: true,
+            long_name: true,
+            windows_id: true,
+            ..Default::default()
+        };
+
+        let (h, d) = to_string_rows_from_epochs(epochs, &timezones, &output);
+        assert_eq!(
+            vec![
+                "Epoch",
+                "America/Los_Angeles (Pacific Standard Time)",
+                "America/Phoenix (US Mountain Standard Time)",
+                "+0000"
+            ],
+            h
+        );
+        assert_eq!("2022-06-30T17:00:00-0700 PDT (Pacific Daylight Time)", d[0][1]);
+        assert_eq!("2022-06-30T17:00:00-0700 MST (Mountain Standard Time)", d[0][2]);
+        assert_eq!("2022-07-01T00:00:00+0000", d[0][3]);
+        assert_eq!("1883-11-18T04:07:02-0752 LMT", d[1][1]);
+    }
+
+    #[test]
+    fn test_to_string_rows_negative_dst() {
+        let epochs = [1656633600, 1640995200];
+        let timezones = vec![Zone::Tz("Europe/Dublin".parse().unwrap())];
+        let output = OutputOptions {
+            abbreviation: true,
+            long_name: true,
+            ..Default::default()
+        };
+
+        let (_, d) = to_string_rows_from_epochs(epochs, &timezones, &output);
+        assert_eq!("2022-07-01T01:00:00+0100 IST (Irish Standard Time)", d[0][1]);
+        assert_eq!("2022-01-01T00:00:00+0000 GMT (Greenwich Mean Time)", d[1][1]);
+    }
+
+    #[test]
+    fn test_to_string_rows_format() {
+        let epochs = [1650197389];
+        let timezones = vec![Zone::Tz("America/Los_Angeles".parse().unwrap()), Zone::Offset(32400)];
+        let mut output = OutputOptions {
+            format: Some(date::parse_format_str("rfc3339").unwrap()),
+            ..Default::default()
+        };
+        output
+            .zone_formats
+            .insert("America/Los_Angeles".to_string(), date::parse_format_str("%H:%M %Z").unwrap());
+
+        let (_, d) = to_string_rows_from_epochs(epochs, &timezones, &output);
+        assert_eq!(vec!["1650197389", "05:09 PDT", "2022-04-17T21:09:49+09:00"], d[0]);
+
+        let dates = vec![date::DateInfo {
+            date_time: chrono::NaiveDate::from_ymd_opt(2022, 4, 17)
+                .unwrap()
+                .and_hms_opt(21, 9, 49)
+                .unwrap(),
+            datestr: "".to_string(),
+        }];
+        let (_, d) = to_string_rows_from_dates(&dates, &timezones, &output);
+        assert_eq!(vec!["2022-04-17T21:09:49", "1650254989", "1650197389"], d[0]);
+    }
+
+    #[test]
+    fn test_to_string_rows_columns() {
+        let epochs = [1672617600];
+        let timezones = vec![Zone::Offset(32400), Zone::Offset(-36000)];
+        let output = OutputOptions {
+            columns: vec![Column::Weekday, Column::Hex, Column::IsoWeek, Column::Milliseconds],
+            ..Default::default()
+        };
+
+        let (h, d) = to_string_rows_from_epochs(epochs, &timezones, &output);
+        assert_eq!(
+            vec![
+                "Epoch",
+                "Hex",
+                "Milliseconds",
+                "+0900",
+                "Weekday",
+                "ISO week",
+                "-1000",
+                "Weekday",
+                "ISO week"
+            ],
+            h
+        );
+        // 2023-01-02T00:00:00Z is Monday of week 1 in Tokyo, and still Sunday of the last week of 2022 in Honolulu
+        assert_eq!(
+            vec![
+                "1672617600",
+                "0x63b21e80",
+                "1672617600000",
+                "2023-01-02T09:00:00+0900",
+                "Monday",
+                "2023-W01",
+                "2023-01-01T14:00:00-1000",
+                "Sunday",
+                "2022-W52"
+            ],
+            d[0]
+        );
+    }
+
+    #[test]
+    fn test_to_string_rows_deltas() {
+        // Noon on both sides of the DST change of 2022-03-13 in New York, which is 23 hours apart
+        let epochs = [1647104400, 1647187200, 1647100800];
+        let timezones = vec![Zone::Tz("America/New_York".parse().unwrap())];
+        let output = OutputOptions {
+            columns: vec![
+                Column::Delta,
+                Column::DeltaTime,
+                Column::Elapsed,
+                Column::ElapsedTime,
+                Column::CalendarDelta,
+            ],
+            ..Default::default()
+        };
+
+        let (h, d) = to_string_rows_from_epochs(epochs, &timezones, &output);
+        assert_eq!(
+            vec![
+                "Epoch",
+                "Delta",
+                "Delta time",
+                "Elapsed",
+                "Elapsed time",
+                "America/New_York",
+                "Calendar delta"
+            ],
+            h
+        );
+        assert_eq!(vec!["1647104400", "", "", "+0", "+00:00:00", "2022-03-12T12:00:00-0500", ""], d[0]);
+        assert_eq!(
+            vec![
+                "1647187200",
+                "+82800",
+                "+23:00:00",
+                "+82800",
+                "+23:00:00",
+                "2022-03-13T12:00:00-0400",
+                "+1 day"
+            ],
+            d[1]
+        );
+        assert_eq!(
+            vec![
+                "1647100800",
+                "-86400",
+                "-1d 00:00:00",
+                "-3600",
+                "-01:00:00",
+                "2022-03-12T11:00:00-0500",
+                "-1 day 01:00:00"
+            ],
+            d[2]
+        );
+
+        let output = OutputOptions {
+            reference: Some(1647187200),
+            ..output
+        };
+        let (_, d) = to_string_rows_from_epochs(epochs, &timezones, &output);
+        assert_eq!("-82800", d[0][3]);
+        assert_eq!("+0", d[1][3]);
+    }
+
+    #[test]
+    fn test_to_string_rows_from_inputs() {
+        let epochs = vec![
+            EpochArg {
+                epochs: date::Epochs::List(vec![0]),
+                datestr: "0".to_string(),
+            },
+            EpochArg {
+                epochs: date::Epochs::List(vec![1650197389]),
+                datestr: "2022-04-17T21:09:49+0900".to_string(),
+            },
+            EpochArg {
+                epochs: date::Epochs::Range(2, 0),
+                datestr: "range(2, 0)".to_string(),
+            },
+        ];
+        let dates = vec![date::DateInfo {
+            date_time: NaiveDate::from_ymd_opt(2022, 3, 13).unwrap().and_hms_opt(2, 30, 0).unwrap(),
+            datestr: "2022-03-13T02:30".to_string(),
+        }];
+        let inputs = vec![Input::DateWithOffset(1), Input::Date(0), Input::Epoch(0), Input::Epoch(2)];
+        let timezones = vec![Zone::Tz("America/Los_Angeles".parse().unwrap()), Zone::Offset(32400)];
+
+        let (h, d) = to_string_rows_from_inputs(&inputs, &epochs, &dates, &timezones, 0, &OutputOptions::default());
+        assert_eq!(vec!["Input", "Kind", "Epoch", "America/Los_Angeles", "+0900"], h);
+        assert_eq!(
+            vec![
+                "2022-04-17T21:09:49+0900",
+                "date with offset",
+                "1650197389",
+                "2022-04-17T05:09:49-0700",
+                "2022-04-17T21:09:49+0900"
+            ],
+            d[0]
+        );
+        // In the gap of Los Angeles; shifted by the offset before it
+        assert_eq!(
+            vec![
+                "2022-03-13T02:30",
+                "local date (America/Los_Angeles)",
+                "1647167400",
+                "2022-03-13T03:30:00-0700",
+                "2022-03-13T19:30:00+0900"
+            ],
+            d[1]
+        );
+        assert_eq!(
+            vec!["0", "epoch", "0", "1969-12-31T16:00:00-0800", "1970-01-01T09:00:00+0900"],
+            d[2]
+        );
+        // A row for each epoch of a script
+        assert_eq!(5, d.len());
+
+        let (_, d) = to_string_rows_from_inputs(&inputs, &epochs, &dates, &timezones, 1, &OutputOptions::default());
+        assert_eq!(vec!["2022-03-13T02:30", "local date (+0900)", "1647106200"], d[1][..3]);
+        assert_eq!(vec!["range(2, 0)", "epoch", "2"], d[3][..3]);
+        assert_eq!(vec!["range(2, 0)", "epoch", "1"], d[4][..3]);
+    }
+
+    #[test]
+    fn test_zone_column_value() {
+        let dt = FixedOffset::east_opt(0).unwrap().with_ymd_and_hms(2022, 4, 17, 12, 9, 49).unwrap();
+        assert_eq!("2022-W15", zone_column_value(Column::IsoWeek, &dt, None));
+        assert_eq!("16", zone_column_value(Column::UsWeek, &dt, None));
+        assert_eq!("107", zone_column_value(Column::DayOfYear, &dt, None));
+        assert_eq!("Q2", zone_column_value(Column::Quarter, &dt, None));
+        assert_eq!("Sun, 17 Apr 2022 12:09:49 +0000", zone_column_value(Column::Rfc2822, &dt, None));
+        assert_eq!("", zone_column_value(Column::CalendarDelta, &dt, None));
+        assert_eq!("-0x10", epoch_column_value(Column::Hex, -16, None, 0, 0));
+        assert_eq!("-16000000000", epoch_column_value(Column::Nanoseconds, -16, None, 0, 0));
+        assert_eq!("16s ago", epoch_column_value(Column::Relative, -16, None, 0, 0));
+        assert_eq!("", epoch_column_value(Column::Delta, -16, None, 0, 0));
+        assert_eq!("-16", epoch_column_value(Column::Delta, -16, Some(0), 0, 0));
+        assert_eq!("-00:00:16", epoch_column_value(Column::DeltaTime, -16, Some(0), 0, 0));
+        assert_eq!("+1d 00:00:00", epoch_column_value(Column::ElapsedTime, 86400, None, 0, 0));
+        assert_eq!("-100", epoch_column_value(Column::Elapsed, -16, None, 0, 84));
+    }
+
+    fn render_table(header: &[&str], data: &[Vec<&str>], mode: &PrintMode) -> String {
+        let header: Vec<String> = header.iter().map(|s| s.to_string()).collect();
+        let data: Vec<Vec<String>> = data.iter().map(|row| row.iter().map(|s| s.to_string()).collect()).collect();
+        let mut out: Vec<u8> = Vec::new();
+        write_table(&mut out, &header, &data, mode).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_write_table() {
+        let header = ["Epoch", "a|b"];
+        let data = [vec!["1", "x"]];
+        assert_eq!(
+            "| Epoch | a\\|b |\n| ----- | ---- |\n|     1 |    x |\n",
+            render_table(&header, &data, &PrintMode::Markdown)
+        );
+        assert_eq!(
+            "┌───────┬─────┐\n│ Epoch │ a|b │\n├───────┼─────┤\n│     1 │   x │\n└───────┴─────┘\n",
+            render_table(&header, &data, &PrintMode::Box)
+        );
+        assert_eq!(
+            "| Epoch | a\\vert{}b |\n|-------+-----------|\n|     1 |         x |\n",
+            render_table(&header, &data, &PrintMode::Org)
+        );
+        assert_eq!(
+            "+-------+-----+\n| Epoch | a|b |\n+=======+=====+\n|     1 |   x |\n+-------+-----+\n",
+            render_table(&header, &data, &PrintMode::Rst)
+        );
+        assert_eq!(
+            "[cols=\">,>\",options=\"header\"]\n|===\n| Epoch | a\\|b\n|     1 |    x\n|===\n",
+            render_table(&header, &data, &PrintMode::AsciiDoc)
+        );
+    }
+
+    #[test]
+    fn test_write_table_escape() {
+        let header = ["Epoch", "America/Los_Angeles"];
+        let data = [vec!["1", "<&>"]];
+        assert_eq!(
+            "\\begin{tabular}{rr}\n\\hline\nEpoch & America/Los\\_Angeles \\\\\n\\hline\n    1 &                 <\\&> \\\\\n\\hline\n\\end{tabular}\n",
+            render_table(&header, &data, &PrintMode::Latex)
+        );
+        assert_eq!(
+            "<table>\n  <thead>\n    <tr><th>Epoch</th><th>America/Los_Angeles</th></tr>\n  </thead>\n  <tbody>\n    <tr><td>1</td><td>&lt;&amp;&gt;</td></tr>\n  </tbody>\n</table>\n",
+            render_table(&header, &data, &PrintMode::Html)
+        );
+    }
+
+    #[test]
+    fn test_to_string_rows_color() {
+        // Los Angeles leaves DST on 2023-11-05
+        let epochs = [1699117200, 1699207200];
+        let timezones = vec![
+            Zone::Tz("America/Los_Angeles".parse().unwrap()),
+            Zone::Tz("Asia/Tokyo".parse().unwrap()),
+        ];
+        let output = OutputOptions {
+            color: true,
+            ..Default::default()
+        };
+        let (_, d) = to_string_rows_from_epochs(epochs, &timezones, &output);
+        assert_eq!(
+            vec![
+                "1699117200",
+                "\x1b[2m2023-11-04T10:00:00-0700\x1b[0m",
+                "\x1b[2;36m2023-11-05T02:00:00+0900\x1b[0m"
+            ],
+            d[0]
+        );
+        assert_eq!(
+            vec![
+                "1699207200",
+                "\x1b[2;1;33m2023-11-05T10:00:00-0800\x1b[0m",
+                "\x1b[2;36m2023-11-06T03:00:00+0900\x1b[0m"
+            ],
+            d[1]
+        );
+    }
+
+    #[test]
+    fn test_cell_colors() {
+        let first_date = NaiveDate::from_ymd_opt(2023, 11, 6).unwrap();
+        let dt = FixedOffset::east_opt(0).unwrap().with_ymd_and_hms(2023, 11, 6, 9, 0, 0).unwrap();
+        assert!(cell_colors(&dt, Some(0), first_date).is_empty());
+        assert_eq!(vec![COLOR_OFFSET_CHANGE], cell_colors(&dt, Some(3600), first_date));
+        let dt = FixedOffset::east_opt(0).unwrap().with_ymd_and_hms(2023, 11, 6, 17, 0, 0).unwrap();
+        assert_eq!(vec![COLOR_OFF_HOURS], cell_colors(&dt, None, first_date));
+        let dt = FixedOffset::east_opt(0).unwrap().with_ymd_and_hms(2023, 11, 7, 12, 0, 0).unwrap();
+        assert_eq!(vec![COLOR_OTHER_DATE], cell_colors(&dt, None, first_date));
+    }
+
+    #[test]
+    fn test_display_width() {
+        assert_eq!(5, display_width("abcde"));
+        assert_eq!(5, display_width("\x1b[1;33mabcde\x1b[0m"));
+        assert_eq!(8, display_width("日本時間"));
+        let header = ["A".to_string()];
+        let data = [vec!["\x1b[2mab\x1b[0m".to_string()]];
+        let mut out: Vec<u8> = Vec::new();
+        write_table(&mut out, &header, &data, &PrintMode::Markdown).unwrap();
+        assert_eq!("|  A |\n| -- |\n| \x1b[2mab\x1b[0m |\n", String::from_utf8(out).unwrap());
+    }
+
+    #[test]
+    fn test_fixed_column_widths() {
+        let epochs = [-1, 0, 1650000000, 253402214400];
+        let epoch_args = |epochs: &[i64]| {
+            vec![EpochArg {
+                epochs: date::Epochs::List(epochs.to_vec()),
+                datestr: "".to_string(),
+            }]
+        };
+        let timezones = vec![
+            Zone::Tz("Asia/Tokyo".parse().unwrap()),
+            Zone::Posix(PosixTz::parse("EST5EDT,M3.2.0,M11.1.0").unwrap()),
+        ];
+        let output = OutputOptions::default();
+        let (header, data) = to_string_rows_from_epochs(epochs, &timezones, &output);
+        assert_eq!(
+            Some(calc_max_column_length(&header, &data)),
+            fixed_column_widths(&epoch_args(&epochs), &header, &output, &PrintMode::Markdown)
+        );
+        // Ranges are measured by their bounds without making the epochs
+        let range = vec![EpochArg {
+            epochs: date::Epochs::Range(253402214400, -2),
+            datestr: "".to_string(),
+        }];
+        assert_eq!(
+            Some(calc_max_column_length(&header, &data)),
+            fixed_column_widths(&range, &header, &output, &PrintMode::Markdown)
+        );
+
+        // Five-digit years and names do not have a fixed width
+        let mut epochs = epochs;
+        epochs[0] = 253402300800;
+        assert_eq!(
+            None,
+            fixed_column_widths(&epoch_args(&epochs), &header, &output, &PrintMode::Markdown)
+        );
+        let output = OutputOptions {
+            abbreviation: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            None,
+            fixed_column_widths(&epoch_args(&epochs[1..]), &header, &output, &PrintMode::Markdown)
+        );
+    }
+
+    #[test]
+    fn test_epoch_rows_jobs() {
+        // Hourly across the DST changes of 2022 so that the offset changes fall in different chunks
+        let epochs = (0..CHUNK_ROWS as i64 * 3).map(|i| 1640995200 + i * 3600);
+        let timezones = vec![Zone::Tz("America/New_York".parse().unwrap()), Zone::Offset(3600)];
+        let output = |jobs: usize| OutputOptions {
+            color: true,
+            abbreviation: true,
+            jobs,
+            ..Default::default()
+        };
+
+        let (_, sequential) = to_string_rows_from_epochs(epochs.clone(), &timezones, &output(1));
+        let (_, parallel) = to_string_rows_from_epochs(epochs.clone(), &timezones, &output(4));
+        assert_eq!(CHUNK_ROWS * 3, parallel.len());
+        assert_eq!(sequential, parallel);
+        // 2022-03-13T07:00:00Z
+        let dst_start = (1647154800 - 1640995200) / 3600;
+        assert_eq!("\x1b[2;1;33m2022-03-13T03:00:00-0400 EDT\x1b[0m", parallel[dst_start][1]);
+        // The first row of the second chunk is compared with the last row of the first
+        assert_eq!(sequential[CHUNK_ROWS][1], parallel[CHUNK_ROWS][1]);
+        assert!(!parallel[CHUNK_ROWS][1].contains("1;33"));
+    }
+
+    #[test]
+    fn test_epoch_rows_jobs_deltas() {
+        let epochs = (0..CHUNK_ROWS as i64 * 3).map(|i| 1640995200 + i * 3600);
+        let timezones = vec![Zone::Tz("America/New_York".parse().unwrap())];
+        let output = |jobs: usize| OutputOptions {
+            columns: vec![Column::Delta, Column::CalendarDelta],
+            jobs,
+            ..Default::default()
+        };
+
+        let (_, sequential) = to_string_rows_from_epochs(epochs.clone(), &timezones, &output(1));
+        let (_, parallel) = to_string_rows_from_epochs(epochs, &timezones, &output(4));
+        assert_eq!(sequential, parallel);
+        // An hour apart, but two on the clock across the DST change
+        let dst_start = (1647154800 - 1640995200) / 3600;
+        assert_eq!(vec!["+3600", "+02:00:00"], [&parallel[dst_start][1], &parallel[dst_start][3]]);
+        // The first row of a chunk is compared with the last row of the one before
+        assert_eq!(vec!["+3600", "+01:00:00"], [&parallel[CHUNK_ROWS][1], &parallel[CHUNK_ROWS][3]]);
+        assert_eq!(vec!["", ""], [&parallel[0][1], &parallel[0][3]]);
+    }
+
+    #[test]
+    fn test_row_chunk() {
+        let chunk = RowChunk {
+            text: "1a2bc".to_string(),
+            cell_ends: vec![1, 2, 3, 5],
+            columns: 2,
+        };
+        assert_eq!(2, chunk.row_count());
+        assert_eq!(vec!["2", "bc"], chunk.row(1).collect::<Vec<&str>>());
+        assert_eq!(vec![vec!["1", "a"], vec!["2", "bc"]], chunk.to_rows());
+    }
+
+    /// cargo test --release -- --ignored --nocapture bench_
+    #[test]
+    #[ignore]
+    fn bench_epoch_rows() {
+        let epochs = (0..1_000_000).map(|i| 1640995200 + i * 60);
+        let timezones = vec![
+            Zone::Offset(0),
+            Zone::Tz("Asia/Tokyo".parse().unwrap()),
+            Zone::Tz("America/New_York".parse().unwrap()),
+        ];
+        for jobs in [1, 0] {
+            let output = OutputOptions {
+                jobs,
+                ..Default::default()
+            };
+            let started = std::time::Instant::now();
+            let (header, chunks) = epoch_rows(epochs.clone(), &timezones, &output);
+            let mut out: Vec<u8> = Vec::new();
+            for chunk in chunks {
+                for i in 0..chunk.row_count() {
+                    write_body_row(&mut out, chunk.row(i), &vec![0; header.len()], &PrintMode::Markdown).unwrap();
+                }
+            }
+            println!("{} bytes with --jobs {}: {:?}", out.len(), jobs, started.elapsed());
+        }
+    }
 }
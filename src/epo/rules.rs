@@ -0,0 +1,84 @@
+use super::{date, posix};
+use chrono::NaiveDateTime;
+
+#[derive(Clone)]
+pub struct LocalTimeType {
+    pub offset_sec: i32,
+    pub is_dst: bool,
+    pub abbreviation: String,
+}
+
+/// Zone rules loaded at runtime (e.g. from a TZif file) instead of the data compiled into chrono-tz.
+#[derive(Clone)]
+pub struct ZoneRules {
+    pub name: String,
+    /// Where the rules came from, e.g. "/usr/share/zoneinfo"
+    pub source: String,
+    /// Sorted transition times
+    pub transitions: Vec<i64>,
+    /// Index into `types` for each transition
+    pub transition_types: Vec<usize>,
+    pub types: Vec<LocalTimeType>,
+    /// Rule for the times after the last transition
+    pub footer: Option<posix::PosixTz>,
+}
+
+impl ZoneRules {
+    pub fn header(&self) -> String {
+        format!("{} [{}]", self.name, self.source)
+    }
+
+    pub fn offset_info(&self, epoch_sec: i64) -> date::OffsetInfo {
+        let idx = self.transitions.partition_point(|t| *t <= epoch_sec);
+        // The footer also applies when there are no transitions at all (RFC 8536 3.3).
+        if idx == self.transitions.len() {
+            if let Some(footer) = &self.footer {
+                return footer.offset_info(epoch_sec);
+            }
+        }
+        if idx == 0 {
+            return self.type_info(0, 0);
+        }
+        let type_idx = self.transition_types[idx - 1];
+        self.type_info(type_idx, idx - 1)
+    }
+
+    /// The span around `epoch_sec` between two transitions, in which the offset stays the same.
+    pub fn offset_span(&self, epoch_sec: i64) -> (i64, i64) {
+        let idx = self.transitions.partition_point(|t| *t <= epoch_sec);
+        let start = if idx == 0 { i64::MIN } else { self.transitions[idx - 1] };
+        let end = self.transitions.get(idx).copied().unwrap_or(i64::MAX);
+        match &self.footer {
+            Some(footer) if idx == self.transitions.len() => {
+                let (footer_start, footer_end) = footer.offset_span(epoch_sec);
+                (start.max(footer_start), end.min(footer_end))
+            }
+            _ => (start, end),
+        }
+    }
+
+    pub fn to_epoch(&self, date_time: NaiveDateTime) -> i64 {
+        date::local_to_epoch(date_time, |epoch_sec| self.offset_info(epoch_sec).offset_sec)
+    }
+
+    /// TZif only tells whether a type is DST; its amount is the difference from the preceding standard time.
+    fn type_info(&self, type_idx: usize, transition_idx: usize) -> date::OffsetInfo {
+        let t = &self.types[type_idx];
+        let dst_sec = if t.is_dst {
+            let std_offset_sec = self.transition_types[..transition_idx]
+                .iter()
+                .rev()
+                .map(|i| &self.types[*i])
+                .find(|t| !t.is_dst)
+                .map_or(t.offset_sec - 3600, |t| t.offset_sec);
+            t.offset_sec - std_offset_sec
+        } else {
+            0
+        };
+        date::OffsetInfo {
+            offset_sec: t.offset_sec,
+            dst_sec,
+            abbreviation: t.abbreviation.clone(),
+        }
+    }
+}
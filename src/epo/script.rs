@@ -26,7 +26,35 @@ const range = (start, end) => {
 };
 ";
 
-pub fn eval(js_code: &str) -> Result<Vec<i64>, String> {
+/// Evaluates an epoch, an array of epochs or range().
+/// range() alone is not evaluated by boa, which takes minutes to make an array of a million numbers.
+pub fn eval(js_code: &str) -> Result<date::Epochs, String> {
+    if let Some(args) = range_args(js_code) {
+        return match eval_values(&format!("[{}]", args))?[..] {
+            [end] => Ok(date::Epochs::Range(0, end)),
+            [start, end] => Ok(date::Epochs::Range(start, end)),
+            _ => Err(format!("Invalid JavaScript code: {}", js_code)),
+        };
+    }
+    eval_values(js_code).map(date::Epochs::List)
+}
+
+/// The arguments of the code if it is a single call of range()
+fn range_args(js_code: &str) -> Option<&str> {
+    let args = js_code.trim().strip_prefix("range(")?.strip_suffix(')')?;
+    let mut depth = 0;
+    for c in args.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return None,
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    (depth == 0).then_some(args)
+}
+
+fn eval_values(js_code: &str) -> Result<Vec<i64>, String> {
     let mut context = Context::default();
 
     context.register_global_property("now", date::current_epoch(), Attribute::all());
@@ -104,60 +132,70 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    fn epochs(js_code: &str) -> Vec<i64> {
+        eval(js_code).unwrap().iter().collect()
+    }
+
     #[test]
     fn test_simple_number() {
-        assert_eq!(vec!(0), eval("0").unwrap());
-        assert_eq!(vec!(1651256673), eval("1651256673").unwrap());
-        assert_eq!(vec!(8210298326399), eval("8210298326399").unwrap());
+        assert_eq!(vec!(0), epochs("0"));
+        assert_eq!(vec!(1651256673), epochs("1651256673"));
+        assert_eq!(vec!(8210298326399), epochs("8210298326399"));
     }
     #[test]
     fn test_numbers() {
-        assert_eq!(vec!(0, 1, 2), eval("[0,1,2]").unwrap());
-        assert_eq!(
-            vec!(-8210298326398, -8210298326399),
-            eval("[-8210298326398,-8210298326399]").unwrap()
-        );
-        assert_eq!(vec!(8210298326398, 8210298326399), eval("[8210298326398,8210298326399]").unwrap());
+        assert_eq!(vec!(0, 1, 2), epochs("[0,1,2]"));
+        assert_eq!(vec!(-8210298326398, -8210298326399), epochs("[-8210298326398,-8210298326399]"));
+        assert_eq!(vec!(8210298326398, 8210298326399), epochs("[8210298326398,8210298326399]"));
 
-        assert_eq!(vec!(0, 1, 2), eval("[0.9,1.1,2.9]").unwrap());
+        assert_eq!(vec!(0, 1, 2), epochs("[0.9,1.1,2.9]"));
     }
 
     #[test]
     fn test_scripts() {
-        assert_eq!(vec!(997002999), eval("999*999*999").unwrap());
+        assert_eq!(vec!(997002999), epochs("999*999*999"));
 
-        assert_eq!(
-            vec!(1651256673, 1651256674, 1651256675),
-            eval("[0,1,2].map(a=>1651256673+a)").unwrap()
-        );
+        assert_eq!(vec!(1651256673, 1651256674, 1651256675), epochs("[0,1,2].map(a=>1651256673+a)"));
 
         assert_eq!(
             vec!(1651256673, 1651256674, 1651256675, 1651256676, 1651256677),
-            eval("[...Array(5).keys()].map(a=>1651256673+a)").unwrap()
+            epochs("[...Array(5).keys()].map(a=>1651256673+a)")
         );
     }
 
     #[test]
     fn test_range() {
-        assert_eq!(
-            vec!(1651256673, 1651256674, 1651256675),
-            eval("range(0, 3).map(a=>1651256673+a)").unwrap()
-        );
-        assert_eq!(
-            vec!(1651256673, 1651256674, 1651256675),
-            eval("range(3).map(a=>1651256673+a)").unwrap()
-        );
+        assert_eq!(vec!(1651256673, 1651256674, 1651256675), epochs("range(0, 3).map(a=>1651256673+a)"));
+        assert_eq!(vec!(1651256673, 1651256674, 1651256675), epochs("range(3).map(a=>1651256673+a)"));
         assert_eq!(
             vec!(1651256673, 1651256672, 1651256671),
-            eval("range(0, -3).map(a=>1651256673+a)").unwrap()
+            epochs("range(0, -3).map(a=>1651256673+a)")
         );
+        assert_eq!(vec!(1651256673, 1651256672, 1651256671), epochs("range(-3).map(a=>1651256673+a)"));
+
+        assert_eq!(0_usize, epochs("range(100, 100).map(a=>1651256673+a)").len());
+        assert_eq!(0_usize, epochs("range(-100, -100).map(a=>1651256673+a)").len());
+    }
+
+    #[test]
+    fn test_range_alone() {
+        assert_eq!(date::Epochs::Range(0, 1000000), eval("range(1e6)").unwrap());
         assert_eq!(
-            vec!(1651256673, 1651256672, 1651256671),
-            eval("range(-3).map(a=>1651256673+a)").unwrap()
+            date::Epochs::Range(1651256673, 1651256670),
+            eval(" range(1651256673, 1651256673 - 3) ").unwrap()
         );
+        assert_eq!(vec!(1651256673, 1651256672, 1651256671), epochs("range(1651256673, 1651256670)"));
+        assert_eq!(vec!(0, -1, -2), epochs("range(-3)"));
+        assert_eq!(0_usize, epochs("range(100, 100)").len());
 
-        assert_eq!(0_usize, eval("range(100, 100).map(a=>1651256673+a)").unwrap().len());
-        assert_eq!(0_usize, eval("range(-100, -100).map(a=>1651256673+a)").unwrap().len());
+        assert_eq!(
+            date::Epochs::List(vec!(0, 1, 2, 3)),
+            eval("range(0, 2).concat(range(2, 4))").unwrap()
+        );
+        assert_eq!(date::Epochs::List(vec!(1, 2)), eval("range(1, 3)||(0)").unwrap());
+        assert!(eval("range(1, 2, 3)").is_err());
+        assert!(eval("range(8210298326400)").is_err());
     }
 
     #[test]
@@ -0,0 +1,246 @@
+use super::types::{PrintMode, Table};
+use super::{app, date, tz};
+use chrono::{Datelike, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+
+const USAGE: &str = "Usage: epo transitions <zone...> [--from <year|date>] [--to <year|date>] [--within <duration>] [-p]";
+
+/// Offsets are sampled at this interval; two changes closer than this would be reported as one.
+const SCAN_STEP_SEC: i64 = 86400;
+
+/// --within looks this far ahead at most, which keeps the end a date and the scan short.
+const MAX_WITHIN_SEC: i64 = 36500 * 86400;
+
+pub struct Transition {
+    pub epoch_sec: i64,
+    pub before: date::OffsetInfo,
+    pub after: date::OffsetInfo,
+}
+
+pub fn run(args: &[String]) -> Result<i32, Vec<String>> {
+    let mut print_mode = PrintMode::Markdown;
+    let mut from: Option<i64> = None;
+    let mut to: Option<i64> = None;
+    let mut within: Option<i64> = None;
+    let mut zones: Vec<Tz> = Vec::new();
+    let mut errors: Vec<String> = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-p" => print_mode = PrintMode::PlainText,
+            "--json" => print_mode = PrintMode::Json,
+            "--ndjson" => print_mode = PrintMode::Ndjson,
+            "--from" | "--to" | "--within" => {
+                let value = match iter.next() {
+                    Some(value) => value,
+                    None => {
+                        errors.push(format!("{} requires a value", arg));
+                        continue;
+                    }
+                };
+                let r = match arg.as_str() {
+                    "--from" => parse_range_value(value, false).map(|v| from = Some(v)),
+                    "--to" => parse_range_value(value, true).map(|v| to = Some(v)),
+                    _ => date::parse_duration_str(value).map(|v| within = Some(v)),
+                };
+                if let Err(e) = r {
+                    errors.push(e);
+                }
+            }
+            _ => match tz::resolve(arg) {
+                Ok(tz) => zones.push(tz),
+                Err(e) => errors.push(e),
+            },
+        }
+    }
+
+    if zones.is_empty() {
+        errors.push(USAGE.to_string());
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let now = date::current_epoch();
+    let (start, end) = match within {
+        Some(duration_sec) => match now.checked_add(duration_sec).filter(|_| duration_sec <= MAX_WITHIN_SEC) {
+            Some(end) => (now, end),
+            None => return Err(vec![format!("--within must be {}d or less", MAX_WITHIN_SEC / 86400)]),
+        },
+        None => {
+            let start = from.unwrap_or_else(|| year_start(current_year(now)));
+            let end = to.unwrap_or_else(|| year_start(current_year(now) + 1));
+            if start >= end {
+                return Err(vec!["--from must be before --to".to_string()]);
+            }
+            (start, end)
+        }
+    };
+
+    let (header, data) = to_string_rows(&zones, start, end);
+    if within.is_some() {
+        if data.is_empty() {
+            return Ok(0);
+        }
+        app::print(&header, &data, &print_mode);
+        return Ok(2);
+    }
+    app::print(&header, &data, &print_mode);
+    Ok(0)
+}
+
+/// Finds every change of the UTC offset in [start, end).
+pub fn find_transitions(tz: Tz, start: i64, end: i64) -> Vec<Transition> {
+    find_offset_changes(start, end, |epoch_sec| date::offset_info_with_tz(epoch_sec, tz))
+}
+
+/// Same as `find_transitions` for any source of offsets (e.g. TZif files).
+pub fn find_offset_changes(start: i64, end: i64, offset_at: impl Fn(i64) -> date::OffsetInfo) -> Vec<Transition> {
+    let mut result: Vec<Transition> = Vec::new();
+
+    let mut prev_epoch = start;
+    let mut prev = offset_at(start);
+    while prev_epoch < end {
+        let epoch = (prev_epoch + SCAN_STEP_SEC).min(end);
+        let current = offset_at(epoch);
+        if current.offset_sec != prev.offset_sec {
+            let epoch_sec = find_change(&offset_at, prev_epoch, epoch, prev.offset_sec);
+            if epoch_sec < end {
+                let after = offset_at(epoch_sec);
+                result.push(Transition {
+                    epoch_sec,
+                    before: prev,
+                    after,
+                });
+            }
+        }
+        prev_epoch = epoch;
+        prev = current;
+    }
+    result
+}
+
+pub fn to_string_rows(zones: &[Tz], start: i64, end: i64) -> Table {
+    let header: Vec<String> = vec![
+        "Zone".to_string(),
+        "UTC".to_string(),
+        "Before".to_string(),
+        "After".to_string(),
+        "Abbreviation".to_string(),
+        "Delta".to_string(),
+    ];
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for tz in zones {
+        for t in find_transitions(*tz, start, end) {
+            rows.push(vec![
+                tz.name().to_string(),
+                date::to_datestr(t.epoch_sec, 0),
+                date::to_datestr(t.epoch_sec, t.before.offset_sec),
+                date::to_datestr(t.epoch_sec, t.after.offset_sec),
+                format!("{} -> {}", t.before.abbreviation, t.after.abbreviation),
+                date::to_offset_str(t.after.offset_sec - t.before.offset_sec),
+            ]);
+        }
+    }
+    (header, rows)
+}
+
+/// Binary search for the first second in (low, high] whose offset is not `offset_sec`.
+fn find_change(offset_at: &impl Fn(i64) -> date::OffsetInfo, mut low: i64, mut high: i64, offset_sec: i32) -> i64 {
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if offset_at(mid).offset_sec == offset_sec {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    high
+}
+
+/// "2022" means the beginning of 2022 (or of 2023 when it is the end of a range), dates are taken as UTC.
+pub fn parse_range_value(value: &str, is_end: bool) -> Result<i64, String> {
+    if value.len() == 4 {
+        if let Ok(year) = value.parse::<i32>() {
+            return Ok(year_start(if is_end { year + 1 } else { year }));
+        }
+    }
+    match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(d) => Ok(d.and_hms_opt(0, 0, 0).unwrap().timestamp()),
+        Err(_) => Err(format!("Invalid year or date: {}", value)),
+    }
+}
+
+pub fn current_year(epoch_sec: i64) -> i32 {
+    Utc.timestamp_opt(epoch_sec, 0).unwrap().year()
+}
+
+pub fn year_start(year: i32) -> i64 {
+    Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap().timestamp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_transitions() {
+        let r = find_transitions(Tz::America__Los_Angeles, year_start(2022), year_start(2023));
+        assert_eq!(2, r.len());
+        assert_eq!(1647165600, r[0].epoch_sec);
+        assert_eq!("PST", r[0].before.abbreviation);
+        assert_eq!("PDT", r[0].after.abbreviation);
+        assert_eq!(1667725200, r[1].epoch_sec);
+        assert_eq!(-7 * 3600, r[1].before.offset_sec);
+        assert_eq!(-8 * 3600, r[1].after.offset_sec);
+
+        assert_eq!(0, find_transitions(Tz::Asia__Tokyo, year_start(2022), year_start(2025)).len());
+        assert_eq!(6, find_transitions(Tz::Europe__London, year_start(2022), year_start(2025)).len());
+    }
+
+    #[test]
+    fn test_to_string_rows() {
+        let (header, data) = to_string_rows(&[Tz::America__Los_Angeles], year_start(2022), year_start(2023));
+        assert_eq!(6, header.len());
+        assert_eq!(
+            vec![
+                "America/Los_Angeles",
+                "2022-03-13T10:00:00+0000",
+                "2022-03-13T02:00:00-0800",
+                "2022-03-13T03:00:00-0700",
+                "PST -> PDT",
+                "+0100"
+            ],
+            data[0]
+        );
+    }
+
+    #[test]
+    fn test_parse_range_value() {
+        assert_eq!(1640995200, parse_range_value("2022", false).unwrap());
+        assert_eq!(1672531200, parse_range_value("2022", true).unwrap());
+        assert_eq!(1648771200, parse_range_value("2022-04-01", false).unwrap());
+        assert!(parse_range_value("x", false).is_err());
+    }
+
+    #[test]
+    fn test_run_invalid_range() {
+        let run_with = |args: &[&str]| run(&args.iter().map(|s| s.to_string()).collect::<Vec<String>>());
+        assert_eq!(
+            Err(vec!["--from must be before --to".to_string()]),
+            run_with(&["tokyo", "--from", "2025", "--to", "2023"])
+        );
+        assert_eq!(
+            Err(vec!["--from must be before --to".to_string()]),
+            run_with(&["tokyo", "--from", "2023-05-01", "--to", "2023-05-01"])
+        );
+        for within in ["36501d", "9999999999999d", "1317624576693539401s"] {
+            assert_eq!(
+                Err(vec!["--within must be 36500d or less".to_string()]),
+                run_with(&["tokyo", "--within", within])
+            );
+        }
+    }
+}
@@ -1,18 +1,116 @@
-use super::date;
+use super::{date, posix, rules};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+
+pub type Table = (Vec<String>, Vec<Vec<String>>);
 
 pub struct Settings {
-    pub epochs: Vec<date::EpochInfo>,
+    pub epochs: Vec<EpochArg>,
     pub dates: Vec<date::DateInfo>,
+    /// Epochs and dates in the order of the arguments
+    pub inputs: Vec<Input>,
     pub timezones: Vec<Zone>,
-    pub time_mode: TimeMode,
+    /// Index of the zone local dates are read in for --unified: the first zone named in the arguments
+    pub local_zone: usize,
     pub print_mode: PrintMode,
+    pub output: OutputOptions,
     pub help: bool,
     pub version: bool,
+    pub warnings: Vec<String>,
+}
+
+/// The epochs of an epoch, a script or a date with an offset given on the command line.
+pub struct EpochArg {
+    pub epochs: date::Epochs,
+    /// The argument as it was given
+    pub datestr: String,
 }
 
+/// An argument given on the command line: an index of Settings::epochs or Settings::dates.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Input {
+    /// An epoch, or the epochs of a script
+    Epoch(usize),
+    /// A date with an offset (e.g. "2022-04-17T21:09:49+0900")
+    DateWithOffset(usize),
+    /// A local date, which is a different epoch in each zone
+    Date(usize),
+}
+
+/// A column of the table, resolved once when the arguments are parsed.
 pub enum Zone {
     Offset(i32),
-    Tzname(String),
+    /// A zone of the data compiled into chrono-tz
+    Tz(Tz),
+    Posix(posix::PosixTz),
+    Rules(rules::ZoneRules),
+}
+
+/// What each cell shows in addition to the date.
+#[derive(Default)]
+pub struct OutputOptions {
+    /// Zone abbreviation such as "PDT" or "LMT" (%Z)
+    pub abbreviation: bool,
+    /// CLDR long name such as "Pacific Daylight Time"
+    pub long_name: bool,
+    /// Windows ID such as "Pacific Standard Time" in headers
+    pub windows_id: bool,
+    /// Format of the dates instead of ISO 8601 (--format)
+    pub format: Option<date::DateFormat>,
+    /// Formats of single columns (e.g. tokyo=rfc2822), keyed by the zone header
+    pub zone_formats: HashMap<String, date::DateFormat>,
+    /// Extra columns (--columns)
+    pub columns: Vec<Column>,
+    /// Highlight offset changes, date rollovers and off-hours with ANSI colors (--color)
+    pub color: bool,
+    /// How tables wider than the terminal are printed (--layout)
+    pub layout: Layout,
+    /// Threads converting epochs (--jobs); 0 is one per CPU
+    pub jobs: usize,
+    /// Epoch the elapsed columns count from instead of the first row (--ref)
+    pub reference: Option<i64>,
+    /// One table of every epoch and date in the order of the arguments (--unified)
+    pub unified: bool,
+}
+
+/// Layout of the Epoch -> Date and Date -> Epoch tables.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Layout {
+    /// Wrap or transpose only when the table is wider than the terminal
+    #[default]
+    Auto,
+    /// One table however wide it is
+    Wide,
+    /// Tables of as many zones as fit, each repeating the Epoch (or Date) column
+    Wrap,
+    /// One table per row with a line per zone
+    Transpose,
+}
+
+/// Extra columns of the Epoch -> Date table.
+/// Columns that depend on the zone are added after each zone, the others after the epoch.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Column {
+    Weekday,
+    IsoWeek,
+    UsWeek,
+    DayOfYear,
+    Quarter,
+    Relative,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+    Hex,
+    Rfc2822,
+    /// Seconds since the previous row
+    Delta,
+    /// Time since the previous row (e.g. "+1d 02:03:04")
+    DeltaTime,
+    /// Seconds since the reference (--ref, or the first row)
+    Elapsed,
+    ElapsedTime,
+    /// Days and time on the local clock since the previous row, so that a day across a DST change is "+1 day"
+    CalendarDelta,
 }
 
 pub enum TimeMode {
@@ -23,4 +121,13 @@ pub enum TimeMode {
 pub enum PrintMode {
     Markdown,
     PlainText,
+    Json,
+    Ndjson,
+    /// Table styles selected by --style
+    Box,
+    Org,
+    Rst,
+    AsciiDoc,
+    Latex,
+    Html,
 }
@@ -1,9 +1,13 @@
+use super::windows;
+use chrono_tz::Tz;
 use once_cell::sync::Lazy;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 
 struct Name {
     full_name: String,
-    location_lower: String,
-    full_name_lower: String,
+    location_key: String,
+    full_name_key: String,
 }
 
 static DATA: Lazy<Vec<Vec<Name>>> = Lazy::new(|| {
@@ -37,15 +41,62 @@ static DATA: Lazy<Vec<Vec<Name>>> = Lazy::new(|| {
     ]
 });
 
-pub fn search(search_name: &str) -> Vec<String> {
-    let lower_search_name = &search_name.to_ascii_lowercase();
-    let r = lower_search_name.chars().next();
+static LOCALIZED_DATA: Lazy<Vec<(String, &str)>> = Lazy::new(|| {
+    localized()
+        .into_iter()
+        .map(|(localized_name, full_name)| (normalize(localized_name), full_name))
+        .collect()
+});
 
-    if r == None {
+pub fn search(search_name: &str) -> Vec<String> {
+    let search_key = &normalize(search_name);
+    if search_key.is_empty() {
         return vec![];
     }
 
-    let first_letter = r.unwrap();
+    if let Some((_, full_name)) = LOCALIZED_DATA.iter().find(|(key, _)| key == search_key) {
+        return vec![full_name.to_string()];
+    }
+
+    let found = search_by_name(search_key);
+    if !found.is_empty() {
+        return found;
+    }
+
+    // Windows IDs and Rails names such as "Tokyo Standard Time" or "Eastern Time (US & Canada)"
+    if let Some(iana) = windows::to_iana(search_name, None) {
+        return vec![iana.to_string()];
+    }
+
+    search_by_localized_name(search_key)
+}
+
+/// Resolves a zone the same way as the main command: exact name first, then a unique search result.
+pub fn resolve(search_name: &str) -> Result<Tz, String> {
+    if let Ok(tz) = search_name.parse::<Tz>() {
+        return Ok(tz);
+    }
+
+    let founds = search(search_name);
+    match founds.len() {
+        0 => Err(format!("No time zone found: {}", search_name)),
+        1 => Ok(founds[0].parse().unwrap()),
+        _ => Err(format!("Ambiguous timezone({})", founds.join(","))),
+    }
+}
+
+/// Makes a key for loose matching: "São Paulo", "sao-paulo" and "SAO_PAULO" all become "sao_paulo".
+pub fn normalize(name: &str) -> String {
+    name.trim()
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .map(|c| if c == ' ' || c == '-' { '_' } else { c })
+        .collect()
+}
+
+fn search_by_name(search_key: &str) -> Vec<String> {
+    let first_letter = search_key.chars().next().unwrap();
     let c = (first_letter as i32) - ('a' as i32);
     if !(0..26).contains(&c) {
         return vec![];
@@ -53,8 +104,8 @@ pub fn search(search_name: &str) -> Vec<String> {
 
     let mut found: Vec<String> = Vec::new();
 
-    if search_name.contains('/') {
-        match search_by_full_name(search_name) {
+    if search_key.contains('/') {
+        match search_by_full_name(search_key) {
             SearchResult::ExactMatch(name) => {
                 return vec![name];
             }
@@ -64,7 +115,7 @@ pub fn search(search_name: &str) -> Vec<String> {
         }
     }
 
-    match search_by_location(lower_search_name) {
+    match search_by_location(search_key) {
         SearchResult::ExactMatch(name) => {
             return vec![name];
         }
@@ -81,15 +132,15 @@ enum SearchResult {
     PartialMatch(Vec<String>),
 }
 
-fn search_by_full_name(lower_search_name: &str) -> SearchResult {
+fn search_by_full_name(search_key: &str) -> SearchResult {
     let mut found: Vec<String> = Vec::new();
     for c in 0..26 {
         let data = &DATA[c as usize];
         for name in data {
-            if !name.full_name_lower.starts_with(lower_search_name) {
+            if !name.full_name_key.starts_with(search_key) {
                 continue;
             }
-            if name.full_name_lower == lower_search_name {
+            if name.full_name_key == search_key {
                 return SearchResult::ExactMatch(name.full_name.clone());
             }
             found.push(name.full_name.clone());
@@ -98,9 +149,9 @@ fn search_by_full_name(lower_search_name: &str) -> SearchResult {
     SearchResult::PartialMatch(found)
 }
 
-fn search_by_location(lower_search_name: &str) -> SearchResult {
-    let r = lower_search_name.chars().next();
-    if r == None {
+fn search_by_location(search_key: &str) -> SearchResult {
+    let r = search_key.chars().next();
+    if r.is_none() {
         return SearchResult::PartialMatch(vec![]);
     }
     let first_letter = r.unwrap();
@@ -109,10 +160,10 @@ fn search_by_location(lower_search_name: &str) -> SearchResult {
     let data = &DATA[c as usize];
     let mut found: Vec<String> = Vec::new();
     for name in data {
-        if !name.location_lower.starts_with(lower_search_name) {
+        if !name.location_key.starts_with(search_key) {
             continue;
         }
-        if name.full_name_lower == lower_search_name {
+        if name.full_name_key == search_key {
             return SearchResult::ExactMatch(name.full_name.clone());
         }
         found.push(name.full_name.clone());
@@ -120,19 +171,29 @@ fn search_by_location(lower_search_name: &str) -> SearchResult {
     SearchResult::PartialMatch(found)
 }
 
+fn search_by_localized_name(search_key: &str) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    for (key, full_name) in LOCALIZED_DATA.iter() {
+        if key.starts_with(search_key) && !found.iter().any(|f| f == full_name) {
+            found.push(full_name.to_string());
+        }
+    }
+    found
+}
+
 fn new_name(full_tz_name: &str) -> Name {
     let full_name = full_tz_name.to_string();
-    let full_name_lower = full_tz_name.to_lowercase();
+    let full_name_key = normalize(full_tz_name);
 
-    let location_lower = match full_tz_name.rfind('/') {
-        Some(idx) => full_tz_name[(idx + 1)..].to_lowercase(),
-        None => full_name_lower.clone(),
+    let location_key = match full_name_key.rfind('/') {
+        Some(idx) => full_name_key[(idx + 1)..].to_string(),
+        None => full_name_key.clone(),
     };
 
     Name {
         full_name,
-        location_lower,
-        full_name_lower,
+        location_key,
+        full_name_key,
     }
 }
 
@@ -888,6 +949,94 @@ fn z() -> Vec<Name> {
     ]
 }
 
+// Exemplar city names in other languages (CLDR).
+// Names that become an IANA location after normalize() (e.g. "Zürich", "París") are not listed.
+
+fn localized() -> Vec<(&'static str, &'static str)> {
+    vec![
+        // Japanese
+        ("東京", "Asia/Tokyo"),
+        ("ソウル", "Asia/Seoul"),
+        ("上海", "Asia/Shanghai"),
+        ("香港", "Asia/Hong_Kong"),
+        ("台北", "Asia/Taipei"),
+        ("シンガポール", "Asia/Singapore"),
+        ("バンコク", "Asia/Bangkok"),
+        ("ジャカルタ", "Asia/Jakarta"),
+        ("マニラ", "Asia/Manila"),
+        ("コルカタ", "Asia/Kolkata"),
+        ("ドバイ", "Asia/Dubai"),
+        ("モスクワ", "Europe/Moscow"),
+        ("ロンドン", "Europe/London"),
+        ("パリ", "Europe/Paris"),
+        ("ベルリン", "Europe/Berlin"),
+        ("ローマ", "Europe/Rome"),
+        ("マドリード", "Europe/Madrid"),
+        ("アムステルダム", "Europe/Amsterdam"),
+        ("チューリッヒ", "Europe/Zurich"),
+        ("ウィーン", "Europe/Vienna"),
+        ("ニューヨーク", "America/New_York"),
+        ("シカゴ", "America/Chicago"),
+        ("デンバー", "America/Denver"),
+        ("ロサンゼルス", "America/Los_Angeles"),
+        ("フェニックス", "America/Phoenix"),
+        ("アンカレッジ", "America/Anchorage"),
+        ("ホノルル", "Pacific/Honolulu"),
+        ("トロント", "America/Toronto"),
+        ("バンクーバー", "America/Vancouver"),
+        ("メキシコシティ", "America/Mexico_City"),
+        ("サンパウロ", "America/Sao_Paulo"),
+        ("ブエノスアイレス", "America/Argentina/Buenos_Aires"),
+        ("シドニー", "Australia/Sydney"),
+        ("メルボルン", "Australia/Melbourne"),
+        ("オークランド", "Pacific/Auckland"),
+        ("カイロ", "Africa/Cairo"),
+        ("ヨハネスブルグ", "Africa/Johannesburg"),
+        // German
+        ("Wien", "Europe/Vienna"),
+        ("Kopenhagen", "Europe/Copenhagen"),
+        ("Moskau", "Europe/Moscow"),
+        ("Rom", "Europe/Rome"),
+        ("Lissabon", "Europe/Lisbon"),
+        ("Brüssel", "Europe/Brussels"),
+        ("Warschau", "Europe/Warsaw"),
+        ("Prag", "Europe/Prague"),
+        ("Athen", "Europe/Athens"),
+        ("Bukarest", "Europe/Bucharest"),
+        ("Belgrad", "Europe/Belgrade"),
+        ("Kiew", "Europe/Kiev"),
+        ("Luxemburg", "Europe/Luxembourg"),
+        ("Nikosia", "Asia/Nicosia"),
+        ("Tiflis", "Asia/Tbilisi"),
+        ("Teheran", "Asia/Tehran"),
+        ("Taschkent", "Asia/Tashkent"),
+        ("Singapur", "Asia/Singapore"),
+        ("Kairo", "Africa/Cairo"),
+        ("Mexiko-Stadt", "America/Mexico_City"),
+        // Spanish
+        ("Nueva York", "America/New_York"),
+        ("Londres", "Europe/London"),
+        ("Roma", "Europe/Rome"),
+        ("Moscú", "Europe/Moscow"),
+        ("Tokio", "Asia/Tokyo"),
+        ("Seúl", "Asia/Seoul"),
+        ("Ciudad de México", "America/Mexico_City"),
+        ("Ciudad de Guatemala", "America/Guatemala"),
+        ("La Habana", "America/Havana"),
+        ("Bruselas", "Europe/Brussels"),
+        ("Viena", "Europe/Vienna"),
+        ("Lisboa", "Europe/Lisbon"),
+        ("Atenas", "Europe/Athens"),
+        ("Estocolmo", "Europe/Stockholm"),
+        ("Copenhague", "Europe/Copenhagen"),
+        ("Varsovia", "Europe/Warsaw"),
+        ("Praga", "Europe/Prague"),
+        ("El Cairo", "Africa/Cairo"),
+        ("Johannesburgo", "Africa/Johannesburg"),
+        ("Sídney", "Australia/Sydney"),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -898,4 +1047,30 @@ mod tests {
         assert_eq!(1, r.len());
         assert_eq!("Asia/Tokyo", r[0]);
     }
+
+    #[test]
+    fn test_search_normalized() {
+        assert_eq!(vec!["America/Sao_Paulo"], search("São Paulo"));
+        assert_eq!(vec!["Europe/Zurich"], search("Zürich"));
+        assert_eq!(vec!["America/New_York"], search("new york"));
+        assert_eq!(vec!["America/Port-au-Prince"], search("port-au-prince"));
+        assert_eq!(vec!["America/Port-au-Prince"], search("Port au Prince"));
+        assert_eq!(vec!["America/Los_Angeles"], search("america/los angeles"));
+    }
+
+    #[test]
+    fn test_search_localized() {
+        assert_eq!(vec!["Asia/Tokyo"], search("東京"));
+        assert_eq!(vec!["America/New_York"], search("ニューヨーク"));
+        assert_eq!(vec!["Europe/Vienna"], search("Wien"));
+        assert_eq!(vec!["America/Mexico_City"], search("Ciudad de México"));
+        assert_eq!(vec!["Europe/Copenhagen"], search("kopen"));
+    }
+
+    #[test]
+    fn test_search_nothing() {
+        assert!(search("").is_empty());
+        assert!(search("   ").is_empty());
+        assert!(search("1x").is_empty());
+    }
 }
@@ -0,0 +1,267 @@
+use super::posix::PosixTz;
+use super::rules::{LocalTimeType, ZoneRules};
+use std::path::{Path, PathBuf};
+
+const SYSTEM_ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
+
+const HEADER_LEN: usize = 44;
+
+struct Header {
+    version: u8,
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+impl Header {
+    fn data_len(&self, time_size: usize) -> usize {
+        self.timecnt * time_size
+            + self.timecnt
+            + self.typecnt * 6
+            + self.charcnt
+            + self.leapcnt * (time_size + 4)
+            + self.isstdcnt
+            + self.isutcnt
+    }
+}
+
+/// The zoneinfo directory of the system ($TZDIR if set).
+pub fn system_dir() -> PathBuf {
+    match std::env::var("TZDIR") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(SYSTEM_ZONEINFO_DIR),
+    }
+}
+
+/// Loads a zone such as "Asia/Tokyo" from a zoneinfo directory.
+pub fn load_from_dir(dir: &Path, name: &str) -> Result<ZoneRules, String> {
+    if name.is_empty() || name.starts_with('/') || name.split('/').any(|p| p == "..") {
+        return Err(format!("Invalid time zone name: {}", name));
+    }
+    let path = dir.join(name);
+    let bytes = std::fs::read(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    parse(&bytes, name, &dir.display().to_string()).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Loads a single TZif file. The file name is used as the zone name.
+pub fn load_file(path: &Path) -> Result<ZoneRules, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let name = path.file_name().map_or("".to_string(), |n| n.to_string_lossy().to_string());
+    parse(&bytes, &name, &path.display().to_string()).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Parses TZif data (RFC 8536, versions 1 to 3).
+pub fn parse(bytes: &[u8], name: &str, source: &str) -> Result<ZoneRules, String> {
+    let header = parse_header(bytes)?;
+    if header.version == 0 {
+        let (rules, _) = parse_data(&bytes[HEADER_LEN..], &header, 4, name, source)?;
+        return Ok(rules);
+    }
+
+    // Version 2+ files repeat the data with 64-bit times after the version 1 data, then a footer.
+    let v2_start = HEADER_LEN + header.data_len(4);
+    let v2_bytes = bytes.get(v2_start..).ok_or("Truncated data")?;
+    let v2_header = parse_header(v2_bytes)?;
+    let (mut rules, data_len) = parse_data(&v2_bytes[HEADER_LEN..], &v2_header, 8, name, source)?;
+    rules.footer = parse_footer(&v2_bytes[HEADER_LEN + data_len..])?;
+    Ok(rules)
+}
+
+fn parse_header(bytes: &[u8]) -> Result<Header, String> {
+    if bytes.len() < HEADER_LEN || &bytes[0..4] != b"TZif" {
+        return Err("Not a TZif file".to_string());
+    }
+    let version = match bytes[4] {
+        0 => 0,
+        b'2' => 2,
+        b'3' | b'4' => 3,
+        v => return Err(format!("Unsupported TZif version: {}", v)),
+    };
+    let count = |i: usize| u32::from_be_bytes(bytes[20 + i * 4..24 + i * 4].try_into().unwrap()) as usize;
+    Ok(Header {
+        version,
+        isutcnt: count(0),
+        isstdcnt: count(1),
+        leapcnt: count(2),
+        timecnt: count(3),
+        typecnt: count(4),
+        charcnt: count(5),
+    })
+}
+
+fn parse_data(bytes: &[u8], header: &Header, time_size: usize, name: &str, source: &str) -> Result<(ZoneRules, usize), String> {
+    let data_len = header.data_len(time_size);
+    if bytes.len() < data_len {
+        return Err("Truncated data".to_string());
+    }
+    if header.typecnt == 0 {
+        return Err("No local time types".to_string());
+    }
+
+    let mut pos = 0;
+    let mut transitions: Vec<i64> = Vec::with_capacity(header.timecnt);
+    for _ in 0..header.timecnt {
+        let t = if time_size == 4 {
+            i32::from_be_bytes(bytes[pos..pos + 4].try_into().unwrap()) as i64
+        } else {
+            i64::from_be_bytes(bytes[pos..pos + 8].try_into().unwrap())
+        };
+        transitions.push(t);
+        pos += time_size;
+    }
+
+    let transition_types: Vec<usize> = bytes[pos..pos + header.timecnt].iter().map(|i| *i as usize).collect();
+    pos += header.timecnt;
+    if transition_types.iter().any(|i| *i >= header.typecnt) {
+        return Err("Invalid local time type index".to_string());
+    }
+
+    let chars_start = pos + header.typecnt * 6;
+    let chars = &bytes[chars_start..chars_start + header.charcnt];
+    let mut types: Vec<LocalTimeType> = Vec::with_capacity(header.typecnt);
+    for _ in 0..header.typecnt {
+        let offset_sec = i32::from_be_bytes(bytes[pos..pos + 4].try_into().unwrap());
+        let is_dst = bytes[pos + 4] != 0;
+        let idx = bytes[pos + 5] as usize;
+        let abbreviation = match chars.get(idx..) {
+            Some(rest) => {
+                let end = rest.iter().position(|c| *c == 0).unwrap_or(rest.len());
+                String::from_utf8_lossy(&rest[..end]).to_string()
+            }
+            None => return Err("Invalid abbreviation index".to_string()),
+        };
+        types.push(LocalTimeType {
+            offset_sec,
+            is_dst,
+            abbreviation,
+        });
+        pos += 6;
+    }
+
+    let rules = ZoneRules {
+        name: name.to_string(),
+        source: source.to_string(),
+        transitions,
+        transition_types,
+        types,
+        footer: None,
+    };
+    Ok((rules, data_len))
+}
+
+fn parse_footer(bytes: &[u8]) -> Result<Option<PosixTz>, String> {
+    if bytes.len() < 2 || bytes[0] != b'\n' {
+        return Ok(None);
+    }
+    let end = bytes[1..].iter().position(|c| *c == b'\n').ok_or("Invalid footer")? + 1;
+    if end == 1 {
+        return Ok(None);
+    }
+    let tz_str = std::str::from_utf8(&bytes[1..end]).map_err(|_| "Invalid footer")?;
+    PosixTz::parse(tz_str).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a version 2 TZif file. The version 1 block is left empty as modern zic does with "-b slim".
+    pub fn build_tzif(transitions: &[(i64, u8)], types: &[(i32, bool, &str)], footer: &str) -> Vec<u8> {
+        let mut chars: Vec<u8> = Vec::new();
+        let mut type_bytes: Vec<u8> = Vec::new();
+        for (offset_sec, is_dst, abbreviation) in types {
+            type_bytes.extend(offset_sec.to_be_bytes());
+            type_bytes.push(*is_dst as u8);
+            type_bytes.push(chars.len() as u8);
+            chars.extend(abbreviation.as_bytes());
+            chars.push(0);
+        }
+
+        let header = |timecnt: usize, typecnt: usize, charcnt: usize| {
+            let mut h: Vec<u8> = b"TZif2".to_vec();
+            h.extend([0; 15]);
+            for count in [0, 0, 0, timecnt, typecnt, charcnt] {
+                h.extend((count as u32).to_be_bytes());
+            }
+            h
+        };
+
+        let mut bytes = header(0, 1, 1);
+        bytes.extend([0, 0, 0, 0, 0, 0, 0]);
+        bytes.extend(header(transitions.len(), types.len(), chars.len()));
+        for (t, _) in transitions {
+            bytes.extend(t.to_be_bytes());
+        }
+        for (_, i) in transitions {
+            bytes.push(*i);
+        }
+        bytes.extend(type_bytes);
+        bytes.extend(chars);
+        bytes.extend(format!("\n{}\n", footer).as_bytes());
+        bytes
+    }
+
+    fn los_angeles_2022() -> Vec<u8> {
+        build_tzif(
+            &[(1647165600, 1), (1667725200, 0)],
+            &[(-8 * 3600, false, "PST"), (-7 * 3600, true, "PDT")],
+            "PST8PDT,M3.2.0,M11.1.0",
+        )
+    }
+
+    #[test]
+    fn test_parse() {
+        let rules = parse(&los_angeles_2022(), "America/Los_Angeles", "test").unwrap();
+        assert_eq!("America/Los_Angeles [test]", rules.header());
+        assert_eq!(2, rules.transitions.len());
+
+        let info = rules.offset_info(1647165599);
+        assert_eq!((-8 * 3600, 0, "PST"), (info.offset_sec, info.dst_sec, info.abbreviation.as_str()));
+        let info = rules.offset_info(1647165600);
+        assert_eq!(
+            (-7 * 3600, 3600, "PDT"),
+            (info.offset_sec, info.dst_sec, info.abbreviation.as_str())
+        );
+        let info = rules.offset_info(1667725200);
+        assert_eq!((-8 * 3600, 0, "PST"), (info.offset_sec, info.dst_sec, info.abbreviation.as_str()));
+
+        // After the last transition, the footer is used
+        assert_eq!("PDT", rules.offset_info(1688169600).abbreviation);
+        assert_eq!("PST", rules.offset_info(1703980800).abbreviation);
+        // Before the first transition, the first type is used
+        assert_eq!("PST", rules.offset_info(0).abbreviation);
+    }
+
+    #[test]
+    fn test_parse_footer_only() {
+        // A file without transitions relies only on its footer.
+        let bytes = build_tzif(&[], &[(-5 * 3600, false, "EST")], "EST5EDT,M3.2.0,M11.1.0");
+        let rules = parse(&bytes, "EST5EDT", "test").unwrap();
+        assert!(rules.transitions.is_empty());
+
+        let info = rules.offset_info(1688169600);
+        assert_eq!(
+            (-4 * 3600, 3600, "EDT"),
+            (info.offset_sec, info.dst_sec, info.abbreviation.as_str())
+        );
+        let info = rules.offset_info(1703980800);
+        assert_eq!((-5 * 3600, 0, "EST"), (info.offset_sec, info.dst_sec, info.abbreviation.as_str()));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(parse(b"", "x", "test").is_err());
+        assert!(parse(b"TZif2", "x", "test").is_err());
+        let bytes = los_angeles_2022();
+        assert!(parse(&bytes[..bytes.len() - 40], "x", "test").is_err());
+    }
+
+    #[test]
+    fn test_load_from_dir_invalid_name() {
+        assert!(load_from_dir(Path::new("/usr/share/zoneinfo"), "../etc/passwd").is_err());
+        assert!(load_from_dir(Path::new("/usr/share/zoneinfo"), "/etc/passwd").is_err());
+    }
+}
@@ -0,0 +1,220 @@
+use super::types::{PrintMode, Table};
+use super::{app, date, link, print, tz};
+use chrono::{Datelike, TimeZone, Utc};
+use chrono_tz::{Tz, TZ_VARIANTS};
+
+const USAGE: &str = "Usage: epo tz list [region...] [offset...] [-a] | epo tz search <name> | epo tz info <name>";
+
+pub fn run(args: &[String]) -> Result<i32, Vec<String>> {
+    let mut print_mode = PrintMode::Markdown;
+    let mut all = false;
+    let mut values: Vec<&str> = Vec::new();
+
+    for arg in args {
+        match arg.as_str() {
+            "-p" => print_mode = PrintMode::PlainText,
+            "--json" => print_mode = PrintMode::Json,
+            "--ndjson" => print_mode = PrintMode::Ndjson,
+            "-a" | "--all" => all = true,
+            _ => values.push(arg),
+        }
+    }
+
+    let now = date::current_epoch();
+    let (subcommand, params) = match values.split_first() {
+        Some((subcommand, params)) => (*subcommand, params),
+        None => return Err(vec![USAGE.to_string()]),
+    };
+
+    match subcommand {
+        "list" => {
+            let (header, data) = list(params, all, now)?;
+            app::print(&header, &data, &print_mode);
+        }
+        "search" => {
+            let (header, data) = search(&params.join(" "), now)?;
+            app::print(&header, &data, &print_mode);
+        }
+        "info" => {
+            let data = info(&params.join(" "), now)?;
+            match print_mode {
+                // One object whose keys are the names of the values
+                PrintMode::Json | PrintMode::Ndjson => {
+                    let header: Vec<String> = data.iter().map(|row| row[0].clone()).collect();
+                    let values: Vec<String> = data.iter().map(|row| row[1].clone()).collect();
+                    app::print(&header, &[values], &print_mode);
+                }
+                _ => print::print_as_plaintext(&data, ": "),
+            }
+        }
+        _ => return Err(vec![USAGE.to_string()]),
+    }
+    Ok(0)
+}
+
+/// Lists zones whose region (e.g. "europe") or current offset (e.g. "+0900") matches any of the filters.
+pub fn list(filters: &[&str], all: bool, epoch_sec: i64) -> Result<Table, Vec<String>> {
+    let mut regions: Vec<String> = Vec::new();
+    let mut offsets: Vec<i32> = Vec::new();
+    for filter in filters {
+        match date::parse_offset_str(filter) {
+            Ok(offset_sec) => offsets.push(offset_sec),
+            Err(_) => regions.push(filter.to_lowercase()),
+        }
+    }
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for tz in TZ_VARIANTS.iter() {
+        let name = tz.name();
+        if !all && link::is_link(name) {
+            continue;
+        }
+        let region = match name.find('/') {
+            Some(idx) => name[..idx].to_lowercase(),
+            None => "".to_string(),
+        };
+        if !regions.is_empty() && !regions.contains(&region) {
+            continue;
+        }
+        let info = date::offset_info_with_tz(epoch_sec, *tz);
+        if !offsets.is_empty() && !offsets.contains(&info.offset_sec) {
+            continue;
+        }
+        rows.push(to_row(name, &info));
+    }
+
+    if rows.is_empty() {
+        return Err(vec![format!("No time zone found: {}", filters.join(" "))]);
+    }
+    Ok((make_header(), rows))
+}
+
+pub fn search(search_name: &str, epoch_sec: i64) -> Result<Table, Vec<String>> {
+    let founds = tz::search(search_name);
+    if founds.is_empty() {
+        return Err(vec![format!("No time zone found: {}", search_name)]);
+    }
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for name in founds {
+        let tz: Tz = name.parse().unwrap();
+        rows.push(to_row(&name, &date::offset_info_with_tz(epoch_sec, tz)));
+    }
+    Ok((make_header(), rows))
+}
+
+pub fn info(search_name: &str, epoch_sec: i64) -> Result<Vec<Vec<String>>, Vec<String>> {
+    let tz = tz::resolve(search_name).map_err(|e| vec![e])?;
+    let name = tz.name();
+    let canonical_name = link::canonical_name(name);
+    let aliases = link::aliases(canonical_name);
+
+    let current = date::offset_info_with_tz(epoch_sec, tz);
+    let year = Utc.timestamp_opt(epoch_sec, 0).unwrap().year();
+    let daylight_offset = find_daylight_offset(tz, year);
+
+    let rows = vec![
+        vec!["Name".to_string(), name.to_string()],
+        vec!["Canonical".to_string(), canonical_name.to_string()],
+        vec![
+            "Aliases".to_string(),
+            if aliases.is_empty() { "-".to_string() } else { aliases.join(", ") },
+        ],
+        vec!["Offset".to_string(), date::to_offset_str(current.offset_sec)],
+        vec!["Abbreviation".to_string(), current.abbreviation.to_string()],
+        vec!["DST".to_string(), if current.dst_sec != 0 { "yes" } else { "no" }.to_string()],
+        vec![
+            "Standard offset".to_string(),
+            date::to_offset_str(current.offset_sec - current.dst_sec),
+        ],
+        vec![
+            "Daylight offset".to_string(),
+            match daylight_offset {
+                Some(offset_sec) => date::to_offset_str(offset_sec),
+                None => "-".to_string(),
+            },
+        ],
+    ];
+    Ok(rows)
+}
+
+fn find_daylight_offset(tz: Tz, year: i32) -> Option<i32> {
+    let start = Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap().timestamp();
+    let end = Utc.with_ymd_and_hms(year + 1, 1, 1, 0, 0, 0).unwrap().timestamp();
+
+    (start..end)
+        .step_by(86400)
+        .map(|epoch_sec| date::offset_info_with_tz(epoch_sec, tz))
+        .find(|info| info.dst_sec != 0)
+        .map(|info| info.offset_sec)
+}
+
+fn make_header() -> Vec<String> {
+    vec!["Zone".to_string(), "Offset".to_string(), "Abbreviation".to_string()]
+}
+
+fn to_row(name: &str, info: &date::OffsetInfo) -> Vec<String> {
+    vec![
+        name.to_string(),
+        date::to_offset_str(info.offset_sec),
+        info.abbreviation.to_string(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2022-07-01T00:00:00Z
+    const SUMMER: i64 = 1656633600;
+
+    #[test]
+    fn test_list() {
+        let (header, data) = list(&["asia", "+0900"], false, SUMMER).unwrap();
+        assert_eq!(3, header.len());
+        assert!(data.iter().any(|row| row[0] == "Asia/Tokyo"));
+        assert!(data.iter().all(|row| row[0].starts_with("Asia/") && row[1] == "+0900"));
+        assert!(!data.iter().any(|row| row[0] == "Asia/Ho_Chi_Minh"));
+
+        let (_, data) = list(&["asia", "+0700"], false, SUMMER).unwrap();
+        assert!(!data.iter().any(|row| row[0] == "Asia/Saigon"));
+        let (_, data) = list(&["asia", "+0700"], true, SUMMER).unwrap();
+        assert!(data.iter().any(|row| row[0] == "Asia/Saigon"));
+
+        assert!(list(&["nowhere"], false, SUMMER).is_err());
+    }
+
+    #[test]
+    fn test_search() {
+        let (_, data) = search("new", SUMMER).unwrap();
+        assert!(data
+            .iter()
+            .any(|row| row[0] == "America/New_York" && row[1] == "-0400" && row[2] == "EDT"));
+        assert!(search("xyz", SUMMER).is_err());
+    }
+
+    #[test]
+    fn test_info() {
+        let data = info("los_angeles", SUMMER).unwrap();
+        let values: Vec<&str> = data.iter().map(|row| row[1].as_str()).collect();
+        assert_eq!(
+            vec![
+                "America/Los_Angeles",
+                "America/Los_Angeles",
+                "US/Pacific",
+                "-0700",
+                "PDT",
+                "yes",
+                "-0800",
+                "-0700"
+            ],
+            values
+        );
+
+        let data = info("Japan", SUMMER).unwrap();
+        let values: Vec<&str> = data.iter().map(|row| row[1].as_str()).collect();
+        assert_eq!(vec!["Japan", "Asia/Tokyo", "Japan", "+0900", "JST", "no", "+0900", "-"], values);
+
+        assert!(info("new", SUMMER).is_err());
+    }
+}
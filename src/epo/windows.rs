@@ -0,0 +1,464 @@
+use super::{link, tz};
+
+/// The territory CLDR uses for the default zone of a Windows ID.
+const DEFAULT_TERRITORY: &str = "001";
+
+/// Maps a Windows ID (e.g. "Tokyo Standard Time") or a Rails/ActiveSupport name (e.g. "Eastern Time (US & Canada)") to an IANA zone.
+/// `territory` (e.g. "CA") picks the zone of that territory for Windows IDs shared by several zones.
+pub fn to_iana(name: &str, territory: Option<&str>) -> Option<&'static str> {
+    let key = tz::normalize(name);
+    let mappings: Vec<(&str, &str, &str)> = windows_zones()
+        .into_iter()
+        .filter(|(windows_id, _, _)| tz::normalize(windows_id) == key)
+        .collect();
+    if !mappings.is_empty() {
+        let territory = territory.unwrap_or(DEFAULT_TERRITORY);
+        let found = mappings
+            .iter()
+            .find(|(_, t, _)| t.eq_ignore_ascii_case(territory))
+            .or_else(|| mappings.iter().find(|(_, t, _)| *t == DEFAULT_TERRITORY))?;
+        return Some(found.2);
+    }
+
+    rails_zones()
+        .into_iter()
+        .find(|(rails_name, _)| tz::normalize(rails_name) == key)
+        .map(|(_, iana)| iana)
+}
+
+/// Whether a territory (e.g. "CA", case-insensitive) has a zone of its own for a Windows ID.
+pub fn is_territory(territory: &str) -> bool {
+    windows_zones()
+        .iter()
+        .any(|(_, t, _)| *t != DEFAULT_TERRITORY && t.eq_ignore_ascii_case(territory))
+}
+
+/// Returns the Windows ID of an IANA zone (e.g. "Pacific Standard Time" for "America/Vancouver").
+pub fn to_windows_id(iana: &str) -> Option<&'static str> {
+    let canonical_name = link::canonical_name(iana);
+    let mappings: Vec<(&str, &str, &str)> = windows_zones()
+        .into_iter()
+        .filter(|(_, _, zone)| link::canonical_name(zone) == canonical_name)
+        .collect();
+    mappings
+        .iter()
+        .find(|(_, territory, _)| *territory == DEFAULT_TERRITORY)
+        .or_else(|| mappings.first())
+        .map(|(windows_id, _, _)| *windows_id)
+}
+
+// Windows IDs (CLDR common/supplemental/windowsZones.xml)
+// "001" is the default zone of the ID; other territories are listed where they are commonly needed.
+
+fn windows_zones() -> Vec<(&'static str, &'static str, &'static str)> {
+    vec![
+        ("Dateline Standard Time", "001", "Etc/GMT+12"),
+        ("UTC-11", "001", "Etc/GMT+11"),
+        ("Aleutian Standard Time", "001", "America/Adak"),
+        ("Hawaiian Standard Time", "001", "Pacific/Honolulu"),
+        ("Marquesas Standard Time", "001", "Pacific/Marquesas"),
+        ("Alaskan Standard Time", "001", "America/Anchorage"),
+        ("UTC-09", "001", "Etc/GMT+9"),
+        ("Pacific Standard Time (Mexico)", "001", "America/Tijuana"),
+        ("UTC-08", "001", "Etc/GMT+8"),
+        ("Pacific Standard Time", "001", "America/Los_Angeles"),
+        ("Pacific Standard Time", "CA", "America/Vancouver"),
+        ("Pacific Standard Time", "US", "America/Los_Angeles"),
+        ("US Mountain Standard Time", "001", "America/Phoenix"),
+        ("US Mountain Standard Time", "MX", "America/Hermosillo"),
+        ("US Mountain Standard Time", "US", "America/Phoenix"),
+        ("Mountain Standard Time (Mexico)", "001", "America/Mazatlan"),
+        ("Mountain Standard Time", "001", "America/Denver"),
+        ("Mountain Standard Time", "CA", "America/Edmonton"),
+        ("Mountain Standard Time", "US", "America/Denver"),
+        ("Yukon Standard Time", "001", "America/Whitehorse"),
+        ("Central America Standard Time", "001", "America/Guatemala"),
+        ("Central America Standard Time", "CR", "America/Costa_Rica"),
+        ("Central America Standard Time", "GT", "America/Guatemala"),
+        ("Central America Standard Time", "HN", "America/Tegucigalpa"),
+        ("Central America Standard Time", "NI", "America/Managua"),
+        ("Central America Standard Time", "SV", "America/El_Salvador"),
+        ("Central Standard Time", "001", "America/Chicago"),
+        ("Central Standard Time", "CA", "America/Winnipeg"),
+        ("Central Standard Time", "MX", "America/Matamoros"),
+        ("Central Standard Time", "US", "America/Chicago"),
+        ("Easter Island Standard Time", "001", "Pacific/Easter"),
+        ("Central Standard Time (Mexico)", "001", "America/Mexico_City"),
+        ("Canada Central Standard Time", "001", "America/Regina"),
+        ("SA Pacific Standard Time", "001", "America/Bogota"),
+        ("SA Pacific Standard Time", "CO", "America/Bogota"),
+        ("SA Pacific Standard Time", "EC", "America/Guayaquil"),
+        ("SA Pacific Standard Time", "JM", "America/Jamaica"),
+        ("SA Pacific Standard Time", "PA", "America/Panama"),
+        ("SA Pacific Standard Time", "PE", "America/Lima"),
+        ("Eastern Standard Time (Mexico)", "001", "America/Cancun"),
+        ("Eastern Standard Time", "001", "America/New_York"),
+        ("Eastern Standard Time", "BS", "America/Nassau"),
+        ("Eastern Standard Time", "CA", "America/Toronto"),
+        ("Eastern Standard Time", "US", "America/New_York"),
+        ("Haiti Standard Time", "001", "America/Port-au-Prince"),
+        ("Cuba Standard Time", "001", "America/Havana"),
+        ("US Eastern Standard Time", "001", "America/Indiana/Indianapolis"),
+        ("Turks And Caicos Standard Time", "001", "America/Grand_Turk"),
+        ("Paraguay Standard Time", "001", "America/Asuncion"),
+        ("Atlantic Standard Time", "001", "America/Halifax"),
+        ("Atlantic Standard Time", "BM", "Atlantic/Bermuda"),
+        ("Atlantic Standard Time", "CA", "America/Halifax"),
+        ("Atlantic Standard Time", "GL", "America/Thule"),
+        ("Venezuela Standard Time", "001", "America/Caracas"),
+        ("Central Brazilian Standard Time", "001", "America/Cuiaba"),
+        ("SA Western Standard Time", "001", "America/La_Paz"),
+        ("SA Western Standard Time", "BO", "America/La_Paz"),
+        ("SA Western Standard Time", "DO", "America/Santo_Domingo"),
+        ("SA Western Standard Time", "PR", "America/Puerto_Rico"),
+        ("Pacific SA Standard Time", "001", "America/Santiago"),
+        ("Newfoundland Standard Time", "001", "America/St_Johns"),
+        ("Tocantins Standard Time", "001", "America/Araguaina"),
+        ("E. South America Standard Time", "001", "America/Sao_Paulo"),
+        ("SA Eastern Standard Time", "001", "America/Cayenne"),
+        ("Argentina Standard Time", "001", "America/Argentina/Buenos_Aires"),
+        ("Greenland Standard Time", "001", "America/Nuuk"),
+        ("Montevideo Standard Time", "001", "America/Montevideo"),
+        ("Magallanes Standard Time", "001", "America/Punta_Arenas"),
+        ("Saint Pierre Standard Time", "001", "America/Miquelon"),
+        ("Bahia Standard Time", "001", "America/Bahia"),
+        ("UTC-02", "001", "Etc/GMT+2"),
+        ("Azores Standard Time", "001", "Atlantic/Azores"),
+        ("Cape Verde Standard Time", "001", "Atlantic/Cape_Verde"),
+        ("UTC", "001", "Etc/UTC"),
+        ("GMT Standard Time", "001", "Europe/London"),
+        ("GMT Standard Time", "GB", "Europe/London"),
+        ("GMT Standard Time", "IE", "Europe/Dublin"),
+        ("GMT Standard Time", "PT", "Europe/Lisbon"),
+        ("Greenwich Standard Time", "001", "Atlantic/Reykjavik"),
+        ("Sao Tome Standard Time", "001", "Africa/Sao_Tome"),
+        ("Morocco Standard Time", "001", "Africa/Casablanca"),
+        ("W. Europe Standard Time", "001", "Europe/Berlin"),
+        ("W. Europe Standard Time", "AT", "Europe/Vienna"),
+        ("W. Europe Standard Time", "CH", "Europe/Zurich"),
+        ("W. Europe Standard Time", "DE", "Europe/Berlin"),
+        ("W. Europe Standard Time", "IT", "Europe/Rome"),
+        ("W. Europe Standard Time", "NL", "Europe/Amsterdam"),
+        ("W. Europe Standard Time", "NO", "Europe/Oslo"),
+        ("W. Europe Standard Time", "SE", "Europe/Stockholm"),
+        ("Central Europe Standard Time", "001", "Europe/Budapest"),
+        ("Romance Standard Time", "001", "Europe/Paris"),
+        ("Romance Standard Time", "BE", "Europe/Brussels"),
+        ("Romance Standard Time", "DK", "Europe/Copenhagen"),
+        ("Romance Standard Time", "ES", "Europe/Madrid"),
+        ("Romance Standard Time", "FR", "Europe/Paris"),
+        ("Central European Standard Time", "001", "Europe/Warsaw"),
+        ("W. Central Africa Standard Time", "001", "Africa/Lagos"),
+        ("Jordan Standard Time", "001", "Asia/Amman"),
+        ("GTB Standard Time", "001", "Europe/Bucharest"),
+        ("Middle East Standard Time", "001", "Asia/Beirut"),
+        ("Egypt Standard Time", "001", "Africa/Cairo"),
+        ("E. Europe Standard Time", "001", "Europe/Chisinau"),
+        ("Syria Standard Time", "001", "Asia/Damascus"),
+        ("West Bank Standard Time", "001", "Asia/Hebron"),
+        ("South Africa Standard Time", "001", "Africa/Johannesburg"),
+        ("FLE Standard Time", "001", "Europe/Kyiv"),
+        ("Israel Standard Time", "001", "Asia/Jerusalem"),
+        ("South Sudan Standard Time", "001", "Africa/Juba"),
+        ("Kaliningrad Standard Time", "001", "Europe/Kaliningrad"),
+        ("Sudan Standard Time", "001", "Africa/Khartoum"),
+        ("Libya Standard Time", "001", "Africa/Tripoli"),
+        ("Namibia Standard Time", "001", "Africa/Windhoek"),
+        ("Arabic Standard Time", "001", "Asia/Baghdad"),
+        ("Turkey Standard Time", "001", "Europe/Istanbul"),
+        ("Arab Standard Time", "001", "Asia/Riyadh"),
+        ("Arab Standard Time", "KW", "Asia/Kuwait"),
+        ("Arab Standard Time", "QA", "Asia/Qatar"),
+        ("Arab Standard Time", "SA", "Asia/Riyadh"),
+        ("Belarus Standard Time", "001", "Europe/Minsk"),
+        ("Russian Standard Time", "001", "Europe/Moscow"),
+        ("E. Africa Standard Time", "001", "Africa/Nairobi"),
+        ("E. Africa Standard Time", "ET", "Africa/Addis_Ababa"),
+        ("E. Africa Standard Time", "KE", "Africa/Nairobi"),
+        ("E. Africa Standard Time", "TZ", "Africa/Dar_es_Salaam"),
+        ("E. Africa Standard Time", "UG", "Africa/Kampala"),
+        ("Volgograd Standard Time", "001", "Europe/Volgograd"),
+        ("Iran Standard Time", "001", "Asia/Tehran"),
+        ("Arabian Standard Time", "001", "Asia/Dubai"),
+        ("Arabian Standard Time", "AE", "Asia/Dubai"),
+        ("Arabian Standard Time", "OM", "Asia/Muscat"),
+        ("Astrakhan Standard Time", "001", "Europe/Astrakhan"),
+        ("Azerbaijan Standard Time", "001", "Asia/Baku"),
+        ("Russia Time Zone 3", "001", "Europe/Samara"),
+        ("Mauritius Standard Time", "001", "Indian/Mauritius"),
+        ("Saratov Standard Time", "001", "Europe/Saratov"),
+        ("Georgian Standard Time", "001", "Asia/Tbilisi"),
+        ("Caucasus Standard Time", "001", "Asia/Yerevan"),
+        ("Afghanistan Standard Time", "001", "Asia/Kabul"),
+        ("West Asia Standard Time", "001", "Asia/Tashkent"),
+        ("Ekaterinburg Standard Time", "001", "Asia/Yekaterinburg"),
+        ("Pakistan Standard Time", "001", "Asia/Karachi"),
+        ("Qyzylorda Standard Time", "001", "Asia/Qyzylorda"),
+        ("India Standard Time", "001", "Asia/Kolkata"),
+        ("Sri Lanka Standard Time", "001", "Asia/Colombo"),
+        ("Nepal Standard Time", "001", "Asia/Kathmandu"),
+        ("Central Asia Standard Time", "001", "Asia/Almaty"),
+        ("Bangladesh Standard Time", "001", "Asia/Dhaka"),
+        ("Omsk Standard Time", "001", "Asia/Omsk"),
+        ("Myanmar Standard Time", "001", "Asia/Yangon"),
+        ("SE Asia Standard Time", "001", "Asia/Bangkok"),
+        ("SE Asia Standard Time", "ID", "Asia/Jakarta"),
+        ("SE Asia Standard Time", "TH", "Asia/Bangkok"),
+        ("SE Asia Standard Time", "VN", "Asia/Ho_Chi_Minh"),
+        ("Altai Standard Time", "001", "Asia/Barnaul"),
+        ("W. Mongolia Standard Time", "001", "Asia/Hovd"),
+        ("North Asia Standard Time", "001", "Asia/Krasnoyarsk"),
+        ("N. Central Asia Standard Time", "001", "Asia/Novosibirsk"),
+        ("Tomsk Standard Time", "001", "Asia/Tomsk"),
+        ("China Standard Time", "001", "Asia/Shanghai"),
+        ("China Standard Time", "CN", "Asia/Shanghai"),
+        ("China Standard Time", "HK", "Asia/Hong_Kong"),
+        ("China Standard Time", "MO", "Asia/Macau"),
+        ("North Asia East Standard Time", "001", "Asia/Irkutsk"),
+        ("Singapore Standard Time", "001", "Asia/Singapore"),
+        ("Singapore Standard Time", "BN", "Asia/Brunei"),
+        ("Singapore Standard Time", "ID", "Asia/Makassar"),
+        ("Singapore Standard Time", "MY", "Asia/Kuala_Lumpur"),
+        ("Singapore Standard Time", "PH", "Asia/Manila"),
+        ("Singapore Standard Time", "SG", "Asia/Singapore"),
+        ("W. Australia Standard Time", "001", "Australia/Perth"),
+        ("Taipei Standard Time", "001", "Asia/Taipei"),
+        ("Ulaanbaatar Standard Time", "001", "Asia/Ulaanbaatar"),
+        ("Aus Central W. Standard Time", "001", "Australia/Eucla"),
+        ("Transbaikal Standard Time", "001", "Asia/Chita"),
+        ("Tokyo Standard Time", "001", "Asia/Tokyo"),
+        ("Tokyo Standard Time", "ID", "Asia/Jayapura"),
+        ("Tokyo Standard Time", "JP", "Asia/Tokyo"),
+        ("Tokyo Standard Time", "PW", "Pacific/Palau"),
+        ("North Korea Standard Time", "001", "Asia/Pyongyang"),
+        ("Korea Standard Time", "001", "Asia/Seoul"),
+        ("Yakutsk Standard Time", "001", "Asia/Yakutsk"),
+        ("Cen. Australia Standard Time", "001", "Australia/Adelaide"),
+        ("AUS Central Standard Time", "001", "Australia/Darwin"),
+        ("E. Australia Standard Time", "001", "Australia/Brisbane"),
+        ("AUS Eastern Standard Time", "001", "Australia/Sydney"),
+        ("West Pacific Standard Time", "001", "Pacific/Port_Moresby"),
+        ("Tasmania Standard Time", "001", "Australia/Hobart"),
+        ("Vladivostok Standard Time", "001", "Asia/Vladivostok"),
+        ("Lord Howe Standard Time", "001", "Australia/Lord_Howe"),
+        ("Bougainville Standard Time", "001", "Pacific/Bougainville"),
+        ("Russia Time Zone 10", "001", "Asia/Srednekolymsk"),
+        ("Magadan Standard Time", "001", "Asia/Magadan"),
+        ("Norfolk Standard Time", "001", "Pacific/Norfolk"),
+        ("Sakhalin Standard Time", "001", "Asia/Sakhalin"),
+        ("Central Pacific Standard Time", "001", "Pacific/Guadalcanal"),
+        ("Russia Time Zone 11", "001", "Asia/Kamchatka"),
+        ("New Zealand Standard Time", "001", "Pacific/Auckland"),
+        ("UTC+12", "001", "Etc/GMT-12"),
+        ("Fiji Standard Time", "001", "Pacific/Fiji"),
+        ("Chatham Islands Standard Time", "001", "Pacific/Chatham"),
+        ("UTC+13", "001", "Etc/GMT-13"),
+        ("Tonga Standard Time", "001", "Pacific/Tongatapu"),
+        ("Samoa Standard Time", "001", "Pacific/Apia"),
+        ("Line Islands Standard Time", "001", "Pacific/Kiritimati"),
+    ]
+}
+
+// ActiveSupport::TimeZone::MAPPING of Ruby on Rails
+// https://github.com/rails/rails/blob/main/activesupport/lib/active_support/values/time_zone.rb
+
+fn rails_zones() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("International Date Line West", "Etc/GMT+12"),
+        ("Midway Island", "Pacific/Midway"),
+        ("American Samoa", "Pacific/Pago_Pago"),
+        ("Hawaii", "Pacific/Honolulu"),
+        ("Alaska", "America/Juneau"),
+        ("Pacific Time (US & Canada)", "America/Los_Angeles"),
+        ("Tijuana", "America/Tijuana"),
+        ("Mountain Time (US & Canada)", "America/Denver"),
+        ("Arizona", "America/Phoenix"),
+        ("Chihuahua", "America/Chihuahua"),
+        ("Mazatlan", "America/Mazatlan"),
+        ("Central Time (US & Canada)", "America/Chicago"),
+        ("Saskatchewan", "America/Regina"),
+        ("Guadalajara", "America/Mexico_City"),
+        ("Mexico City", "America/Mexico_City"),
+        ("Monterrey", "America/Monterrey"),
+        ("Central America", "America/Guatemala"),
+        ("Eastern Time (US & Canada)", "America/New_York"),
+        ("Indiana (East)", "America/Indiana/Indianapolis"),
+        ("Bogota", "America/Bogota"),
+        ("Lima", "America/Lima"),
+        ("Quito", "America/Lima"),
+        ("Atlantic Time (Canada)", "America/Halifax"),
+        ("Caracas", "America/Caracas"),
+        ("La Paz", "America/La_Paz"),
+        ("Santiago", "America/Santiago"),
+        ("Newfoundland", "America/St_Johns"),
+        ("Brasilia", "America/Sao_Paulo"),
+        ("Buenos Aires", "America/Argentina/Buenos_Aires"),
+        ("Montevideo", "America/Montevideo"),
+        ("Georgetown", "America/Guyana"),
+        ("Puerto Rico", "America/Puerto_Rico"),
+        ("Greenland", "America/Nuuk"),
+        ("Mid-Atlantic", "Atlantic/South_Georgia"),
+        ("Azores", "Atlantic/Azores"),
+        ("Cape Verde Is.", "Atlantic/Cape_Verde"),
+        ("Dublin", "Europe/Dublin"),
+        ("Edinburgh", "Europe/London"),
+        ("Lisbon", "Europe/Lisbon"),
+        ("London", "Europe/London"),
+        ("Casablanca", "Africa/Casablanca"),
+        ("Monrovia", "Africa/Monrovia"),
+        ("UTC", "Etc/UTC"),
+        ("Belgrade", "Europe/Belgrade"),
+        ("Bratislava", "Europe/Bratislava"),
+        ("Budapest", "Europe/Budapest"),
+        ("Ljubljana", "Europe/Ljubljana"),
+        ("Prague", "Europe/Prague"),
+        ("Sarajevo", "Europe/Sarajevo"),
+        ("Skopje", "Europe/Skopje"),
+        ("Warsaw", "Europe/Warsaw"),
+        ("Zagreb", "Europe/Zagreb"),
+        ("Brussels", "Europe/Brussels"),
+        ("Copenhagen", "Europe/Copenhagen"),
+        ("Madrid", "Europe/Madrid"),
+        ("Paris", "Europe/Paris"),
+        ("Amsterdam", "Europe/Amsterdam"),
+        ("Berlin", "Europe/Berlin"),
+        ("Bern", "Europe/Zurich"),
+        ("Zurich", "Europe/Zurich"),
+        ("Rome", "Europe/Rome"),
+        ("Stockholm", "Europe/Stockholm"),
+        ("Vienna", "Europe/Vienna"),
+        ("West Central Africa", "Africa/Algiers"),
+        ("Bucharest", "Europe/Bucharest"),
+        ("Cairo", "Africa/Cairo"),
+        ("Helsinki", "Europe/Helsinki"),
+        ("Kyiv", "Europe/Kyiv"),
+        ("Riga", "Europe/Riga"),
+        ("Sofia", "Europe/Sofia"),
+        ("Tallinn", "Europe/Tallinn"),
+        ("Vilnius", "Europe/Vilnius"),
+        ("Athens", "Europe/Athens"),
+        ("Istanbul", "Europe/Istanbul"),
+        ("Minsk", "Europe/Minsk"),
+        ("Jerusalem", "Asia/Jerusalem"),
+        ("Harare", "Africa/Harare"),
+        ("Pretoria", "Africa/Johannesburg"),
+        ("Kaliningrad", "Europe/Kaliningrad"),
+        ("Moscow", "Europe/Moscow"),
+        ("St. Petersburg", "Europe/Moscow"),
+        ("Volgograd", "Europe/Volgograd"),
+        ("Samara", "Europe/Samara"),
+        ("Kuwait", "Asia/Kuwait"),
+        ("Riyadh", "Asia/Riyadh"),
+        ("Nairobi", "Africa/Nairobi"),
+        ("Baghdad", "Asia/Baghdad"),
+        ("Tehran", "Asia/Tehran"),
+        ("Abu Dhabi", "Asia/Muscat"),
+        ("Muscat", "Asia/Muscat"),
+        ("Baku", "Asia/Baku"),
+        ("Tbilisi", "Asia/Tbilisi"),
+        ("Yerevan", "Asia/Yerevan"),
+        ("Kabul", "Asia/Kabul"),
+        ("Ekaterinburg", "Asia/Yekaterinburg"),
+        ("Islamabad", "Asia/Karachi"),
+        ("Karachi", "Asia/Karachi"),
+        ("Tashkent", "Asia/Tashkent"),
+        ("Chennai", "Asia/Kolkata"),
+        ("Kolkata", "Asia/Kolkata"),
+        ("Mumbai", "Asia/Kolkata"),
+        ("New Delhi", "Asia/Kolkata"),
+        ("Kathmandu", "Asia/Kathmandu"),
+        ("Astana", "Asia/Dhaka"),
+        ("Dhaka", "Asia/Dhaka"),
+        ("Sri Jayawardenepura", "Asia/Colombo"),
+        ("Almaty", "Asia/Almaty"),
+        ("Novosibirsk", "Asia/Novosibirsk"),
+        ("Rangoon", "Asia/Yangon"),
+        ("Bangkok", "Asia/Bangkok"),
+        ("Hanoi", "Asia/Bangkok"),
+        ("Jakarta", "Asia/Jakarta"),
+        ("Krasnoyarsk", "Asia/Krasnoyarsk"),
+        ("Beijing", "Asia/Shanghai"),
+        ("Chongqing", "Asia/Chongqing"),
+        ("Hong Kong", "Asia/Hong_Kong"),
+        ("Urumqi", "Asia/Urumqi"),
+        ("Kuala Lumpur", "Asia/Kuala_Lumpur"),
+        ("Singapore", "Asia/Singapore"),
+        ("Taipei", "Asia/Taipei"),
+        ("Perth", "Australia/Perth"),
+        ("Irkutsk", "Asia/Irkutsk"),
+        ("Ulaanbaatar", "Asia/Ulaanbaatar"),
+        ("Seoul", "Asia/Seoul"),
+        ("Osaka", "Asia/Tokyo"),
+        ("Sapporo", "Asia/Tokyo"),
+        ("Tokyo", "Asia/Tokyo"),
+        ("Yakutsk", "Asia/Yakutsk"),
+        ("Darwin", "Australia/Darwin"),
+        ("Adelaide", "Australia/Adelaide"),
+        ("Canberra", "Australia/Canberra"),
+        ("Melbourne", "Australia/Melbourne"),
+        ("Sydney", "Australia/Sydney"),
+        ("Brisbane", "Australia/Brisbane"),
+        ("Hobart", "Australia/Hobart"),
+        ("Vladivostok", "Asia/Vladivostok"),
+        ("Guam", "Pacific/Guam"),
+        ("Port Moresby", "Pacific/Port_Moresby"),
+        ("Magadan", "Asia/Magadan"),
+        ("Srednekolymsk", "Asia/Srednekolymsk"),
+        ("Solomon Is.", "Pacific/Guadalcanal"),
+        ("New Caledonia", "Pacific/Noumea"),
+        ("Fiji", "Pacific/Fiji"),
+        ("Kamchatka", "Asia/Kamchatka"),
+        ("Marshall Is.", "Pacific/Majuro"),
+        ("Auckland", "Pacific/Auckland"),
+        ("Wellington", "Pacific/Auckland"),
+        ("Nuku'alofa", "Pacific/Tongatapu"),
+        ("Tokelau Is.", "Pacific/Fakaofo"),
+        ("Chatham Is.", "Pacific/Chatham"),
+        ("Samoa", "Pacific/Apia"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::Tz;
+
+    #[test]
+    fn test_to_iana() {
+        assert_eq!(Some("Asia/Tokyo"), to_iana("Tokyo Standard Time", None));
+        assert_eq!(Some("America/Los_Angeles"), to_iana("pacific standard time", None));
+        assert_eq!(Some("America/Vancouver"), to_iana("Pacific Standard Time", Some("CA")));
+        assert_eq!(Some("America/Los_Angeles"), to_iana("Pacific Standard Time", Some("JP")));
+        assert_eq!(Some("America/New_York"), to_iana("Eastern Time (US & Canada)", None));
+        assert_eq!(Some("Pacific/Tongatapu"), to_iana("Nuku'alofa", None));
+        assert_eq!(None, to_iana("Mars Standard Time", None));
+    }
+
+    #[test]
+    fn test_is_territory() {
+        assert!(is_territory("CA"));
+        assert!(is_territory("jp"));
+        assert!(!is_territory("ZZ"));
+        assert!(!is_territory("001"));
+        assert!(!is_territory(""));
+    }
+
+    #[test]
+    fn test_to_windows_id() {
+        assert_eq!(Some("Pacific Standard Time"), to_windows_id("America/Los_Angeles"));
+        assert_eq!(Some("Pacific Standard Time"), to_windows_id("US/Pacific"));
+        assert_eq!(Some("Pacific Standard Time"), to_windows_id("America/Vancouver"));
+        assert_eq!(Some("Tokyo Standard Time"), to_windows_id("Asia/Tokyo"));
+        assert_eq!(None, to_windows_id("Antarctica/Troll"));
+    }
+
+    #[test]
+    fn test_zones() {
+        for (windows_id, _, zone) in windows_zones() {
+            assert!(zone.parse::<Tz>().is_ok(), "{} {}", windows_id, zone);
+        }
+        for (rails_name, zone) in rails_zones() {
+            assert!(zone.parse::<Tz>().is_ok(), "{} {}", rails_name, zone);
+        }
+    }
+}
@@ -0,0 +1,852 @@
+use super::posix::{DstRule, PosixTz, RuleDate};
+use super::rules::{LocalTimeType, ZoneRules};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Weekday};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Rules are expanded into transitions up to this year; later times use the POSIX footer if one can be made.
+const MAX_YEAR: i32 = 2100;
+/// Years in the source are between this and its negation.
+const MIN_YEAR: i32 = -9999;
+/// Offsets and saves are less than a day, like the offsets of dates.
+const MAX_OFFSET_SEC: i32 = 24 * 3600 - 1;
+
+#[derive(Clone, Copy, PartialEq)]
+enum TimeSuffix {
+    Wall,
+    Standard,
+    Universal,
+}
+
+enum DaySpec {
+    Fixed(u32),
+    Last(Weekday),
+    OnOrAfter(Weekday, u32),
+    OnOrBefore(Weekday, u32),
+}
+
+struct Rule {
+    from: i32,
+    to: i32,
+    month: u32,
+    day: DaySpec,
+    at_sec: i32,
+    at_suffix: TimeSuffix,
+    save_sec: i32,
+    letter: String,
+}
+
+enum ZoneLineRules {
+    Standard,
+    Fixed(i32),
+    Named(String),
+}
+
+struct Until {
+    year: i32,
+    month: u32,
+    day: DaySpec,
+    time_sec: i32,
+    suffix: TimeSuffix,
+}
+
+struct ZoneLine {
+    line_no: usize,
+    std_offset_sec: i32,
+    rules: ZoneLineRules,
+    format: String,
+    until: Option<Until>,
+}
+
+struct Source {
+    rules: HashMap<String, Vec<Rule>>,
+    zones: Vec<(String, Vec<ZoneLine>)>,
+    links: Vec<(usize, String, String)>,
+}
+
+/// Compiles zone definitions written in the zic source format (Rule, Zone and Link lines) of a file.
+pub fn compile_file(path: &Path) -> Result<Vec<ZoneRules>, Vec<String>> {
+    let text = std::fs::read_to_string(path).map_err(|e| vec![format!("{}: {}", path.display(), e)])?;
+    compile(&text, &path.display().to_string())
+}
+
+pub fn compile(text: &str, source_name: &str) -> Result<Vec<ZoneRules>, Vec<String>> {
+    let source = parse_source(text).map_err(|errors| {
+        errors
+            .into_iter()
+            .map(|(line_no, e)| format!("{}:{}: {}", source_name, line_no, e))
+            .collect::<Vec<String>>()
+    })?;
+
+    let mut result: Vec<ZoneRules> = Vec::new();
+    let mut errors: Vec<String> = Vec::new();
+    for (name, lines) in &source.zones {
+        match compile_zone(name, lines, &source.rules, source_name) {
+            Ok(rules) => result.push(rules),
+            Err((line_no, e)) => errors.push(format!("{}:{}: {}", source_name, line_no, e)),
+        }
+    }
+    for (line_no, target, link_name) in &source.links {
+        match result.iter().find(|r| &r.name == target) {
+            Some(rules) => {
+                let mut link = rules.clone();
+                link.name = link_name.to_string();
+                result.push(link);
+            }
+            None => errors.push(format!("{}:{}: Unknown zone: {}", source_name, line_no, target)),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(result)
+}
+
+fn parse_source(text: &str) -> Result<Source, Vec<(usize, String)>> {
+    let mut source = Source {
+        rules: HashMap::new(),
+        zones: Vec::new(),
+        links: Vec::new(),
+    };
+    let mut errors: Vec<(usize, String)> = Vec::new();
+    let mut in_zone = false;
+
+    for (i, line) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let line = match line.find('#') {
+            Some(idx) => &line[..idx],
+            None => line,
+        };
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.is_empty() {
+            continue;
+        }
+
+        let r = if in_zone {
+            parse_zone_line(line_no, &fields).map(|zone_line| {
+                in_zone = zone_line.until.is_some();
+                source.zones.last_mut().unwrap().1.push(zone_line);
+            })
+        } else {
+            match keyword(fields[0]) {
+                Some("Rule") => parse_rule(&fields).map(|(name, rule)| source.rules.entry(name).or_default().push(rule)),
+                Some("Zone") if fields.len() >= 2 => parse_zone_line(line_no, &fields[2..]).map(|zone_line| {
+                    in_zone = zone_line.until.is_some();
+                    source.zones.push((fields[1].to_string(), vec![zone_line]));
+                }),
+                Some("Link") if fields.len() == 3 => {
+                    source.links.push((line_no, fields[1].to_string(), fields[2].to_string()));
+                    Ok(())
+                }
+                _ => Err(format!("Unknown line: {}", line.trim())),
+            }
+        };
+        if let Err(e) = r {
+            errors.push((line_no, e));
+            in_zone = false;
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(source)
+}
+
+/// Keywords may be abbreviated, e.g. "R", "Z" and "L" in the compact form of tzdata.
+fn keyword(field: &str) -> Option<&'static str> {
+    let lower = field.to_lowercase();
+    ["Rule", "Zone", "Link"].into_iter().find(|k| k.to_lowercase().starts_with(&lower))
+}
+
+/// Rule NAME FROM TO - IN ON AT SAVE LETTER/S
+fn parse_rule(fields: &[&str]) -> Result<(String, Rule), String> {
+    if fields.len() != 10 {
+        return Err("Rule line must have 10 fields".to_string());
+    }
+    let from = parse_year(fields[2])?;
+    let to = match fields[3].to_lowercase().as_str() {
+        "only" | "o" => from,
+        "max" | "ma" => i32::MAX,
+        _ => parse_year(fields[3])?,
+    };
+    let (at_sec, at_suffix) = parse_time(fields[7])?;
+    let save_sec = parse_offset(fields[8])?;
+    let rule = Rule {
+        from,
+        to,
+        month: parse_month(fields[5])?,
+        day: parse_day(fields[6])?,
+        at_sec,
+        at_suffix,
+        save_sec,
+        letter: if fields[9] == "-" { "".to_string() } else { fields[9].to_string() },
+    };
+    Ok((fields[1].to_string(), rule))
+}
+
+/// STDOFF RULES FORMAT [UNTIL]
+fn parse_zone_line(line_no: usize, fields: &[&str]) -> Result<ZoneLine, String> {
+    if fields.len() < 3 {
+        return Err("Zone line must have STDOFF, RULES and FORMAT".to_string());
+    }
+    let std_offset_sec = parse_offset(fields[0])?;
+    let rules = if fields[1] == "-" {
+        ZoneLineRules::Standard
+    } else if fields[1].starts_with(|c: char| c.is_ascii_digit() || c == '-') {
+        ZoneLineRules::Fixed(parse_offset(fields[1])?)
+    } else {
+        ZoneLineRules::Named(fields[1].to_string())
+    };
+
+    let until = if fields.len() > 3 {
+        let (time_sec, suffix) = match fields.get(6) {
+            Some(t) => parse_time(t)?,
+            None => (0, TimeSuffix::Wall),
+        };
+        Some(Until {
+            year: parse_year(fields[3])?,
+            month: fields.get(4).map_or(Ok(1), |m| parse_month(m))?,
+            day: fields.get(5).map_or(Ok(DaySpec::Fixed(1)), |d| parse_day(d))?,
+            time_sec,
+            suffix,
+        })
+    } else {
+        None
+    };
+
+    Ok(ZoneLine {
+        line_no,
+        std_offset_sec,
+        rules,
+        format: fields[2].to_string(),
+        until,
+    })
+}
+
+/// Years are limited so that rules are not expanded over billions of years.
+fn parse_year(s: &str) -> Result<i32, String> {
+    match s.parse() {
+        Ok(year) if (MIN_YEAR..=-MIN_YEAR).contains(&year) => Ok(year),
+        _ => Err(format!("Invalid year: {}", s)),
+    }
+}
+
+fn parse_month(s: &str) -> Result<u32, String> {
+    const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+    let lower: String = s.to_lowercase().chars().take(3).collect();
+    MONTHS
+        .iter()
+        .position(|m| lower.len() >= 2 && m.starts_with(&lower))
+        .map(|i| i as u32 + 1)
+        .ok_or_else(|| format!("Invalid month: {}", s))
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday, String> {
+    const WEEKDAYS: [(&str, Weekday); 7] = [
+        ("mon", Weekday::Mon),
+        ("tue", Weekday::Tue),
+        ("wed", Weekday::Wed),
+        ("thu", Weekday::Thu),
+        ("fri", Weekday::Fri),
+        ("sat", Weekday::Sat),
+        ("sun", Weekday::Sun),
+    ];
+    let lower: String = s.to_lowercase().chars().take(3).collect();
+    WEEKDAYS
+        .iter()
+        .find(|(name, _)| lower.len() >= 2 && name.starts_with(&lower))
+        .map(|(_, w)| *w)
+        .ok_or_else(|| format!("Invalid weekday: {}", s))
+}
+
+/// "15", "lastSun", "Sun>=8" or "Sun<=25"
+fn parse_day(s: &str) -> Result<DaySpec, String> {
+    let error = || format!("Invalid day: {}", s);
+    let parse_day_of_month = |day: &str| match day.parse::<u32>() {
+        Ok(day) if (1..=31).contains(&day) => Ok(day),
+        _ => Err(error()),
+    };
+    if let Some(weekday) = s.strip_prefix("last") {
+        return Ok(DaySpec::Last(parse_weekday(weekday)?));
+    }
+    if let Some((weekday, day)) = s.split_once(">=") {
+        return Ok(DaySpec::OnOrAfter(parse_weekday(weekday)?, parse_day_of_month(day)?));
+    }
+    if let Some((weekday, day)) = s.split_once("<=") {
+        return Ok(DaySpec::OnOrBefore(parse_weekday(weekday)?, parse_day_of_month(day)?));
+    }
+    Ok(DaySpec::Fixed(parse_day_of_month(s)?))
+}
+
+/// "2", "2:00", "-0:30", "1:00:30", "23:00u", "2:00s" or "-" as seconds
+fn parse_time(s: &str) -> Result<(i32, TimeSuffix), String> {
+    let error = || format!("Invalid time: {}", s);
+    if s == "-" {
+        return Ok((0, TimeSuffix::Wall));
+    }
+
+    let (body, suffix) = match s.chars().last() {
+        Some('w') => (&s[..s.len() - 1], TimeSuffix::Wall),
+        Some('s') => (&s[..s.len() - 1], TimeSuffix::Standard),
+        Some('u') | Some('g') | Some('z') => (&s[..s.len() - 1], TimeSuffix::Universal),
+        // SAVE may have "d" (daylight) which does not change the amount
+        Some('d') => (&s[..s.len() - 1], TimeSuffix::Wall),
+        _ => (s, TimeSuffix::Wall),
+    };
+    let (sign, body) = match body.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, body),
+    };
+
+    let mut sec: i32 = 0;
+    for (i, part) in body.split(':').enumerate() {
+        let value: i32 = part.split('.').next().unwrap_or("").parse().map_err(|_| error())?;
+        let part_sec = match i {
+            0 => value.checked_mul(3600),
+            1 => value.checked_mul(60),
+            2 => Some(value),
+            _ => None,
+        };
+        sec = part_sec.and_then(|p| sec.checked_add(p)).ok_or_else(error)?;
+    }
+    Ok((sign * sec, suffix))
+}
+
+/// STDOFF, SAVE or the amount of RULES as seconds
+fn parse_offset(s: &str) -> Result<i32, String> {
+    match parse_time(s)? {
+        (sec, _) if sec.abs() <= MAX_OFFSET_SEC => Ok(sec),
+        _ => Err(format!("Invalid offset: {}", s)),
+    }
+}
+
+fn day_of(year: i32, month: u32, day: &DaySpec) -> Option<NaiveDate> {
+    match day {
+        DaySpec::Fixed(d) => NaiveDate::from_ymd_opt(year, month, *d),
+        DaySpec::Last(weekday) => {
+            let next_month = if month == 12 {
+                NaiveDate::from_ymd_opt(year + 1, 1, 1)
+            } else {
+                NaiveDate::from_ymd_opt(year, month + 1, 1)
+            }?;
+            let mut date = next_month.pred_opt()?;
+            while date.weekday() != *weekday {
+                date = date.pred_opt()?;
+            }
+            Some(date)
+        }
+        DaySpec::OnOrAfter(weekday, d) => {
+            let mut date = NaiveDate::from_ymd_opt(year, month, *d)?;
+            while date.weekday() != *weekday {
+                date = date.succ_opt()?;
+            }
+            Some(date)
+        }
+        DaySpec::OnOrBefore(weekday, d) => {
+            let mut date = NaiveDate::from_ymd_opt(year, month, *d)?;
+            while date.weekday() != *weekday {
+                date = date.pred_opt()?;
+            }
+            Some(date)
+        }
+    }
+}
+
+fn local_sec(date: NaiveDate, time_sec: i32) -> i64 {
+    (date.and_hms_opt(0, 0, 0).unwrap() + Duration::seconds(time_sec as i64)).timestamp()
+}
+
+fn to_utc(local: i64, suffix: TimeSuffix, std_offset_sec: i32, save_sec: i32) -> i64 {
+    match suffix {
+        TimeSuffix::Universal => local,
+        TimeSuffix::Standard => local - std_offset_sec as i64,
+        TimeSuffix::Wall => local - std_offset_sec as i64 - save_sec as i64,
+    }
+}
+
+fn format_abbreviation(format: &str, letter: &str, save_sec: i32, offset_sec: i32) -> String {
+    if let Some((std, dst)) = format.split_once('/') {
+        return if save_sec != 0 { dst } else { std }.to_string();
+    }
+    if format.contains("%z") {
+        let sign = if offset_sec < 0 { '-' } else { '+' };
+        let (h, m) = (offset_sec.abs() / 3600, offset_sec.abs() % 3600 / 60);
+        let numeric = if m == 0 {
+            format!("{}{:02}", sign, h)
+        } else {
+            format!("{}{:02}{:02}", sign, h, m)
+        };
+        return format.replace("%z", &numeric);
+    }
+    format.replace("%s", letter)
+}
+
+struct Builder {
+    transitions: Vec<i64>,
+    transition_types: Vec<usize>,
+    types: Vec<LocalTimeType>,
+    current: Option<usize>,
+}
+
+impl Builder {
+    /// Adds a transition at `epoch_sec`; None means the type in effect since the beginning of time.
+    fn push(&mut self, epoch_sec: Option<i64>, offset_sec: i32, is_dst: bool, abbreviation: String) {
+        let idx = match self
+            .types
+            .iter()
+            .position(|t| t.offset_sec == offset_sec && t.is_dst == is_dst && t.abbreviation == abbreviation)
+        {
+            Some(idx) => idx,
+            None => {
+                self.types.push(LocalTimeType {
+                    offset_sec,
+                    is_dst,
+                    abbreviation,
+                });
+                self.types.len() - 1
+            }
+        };
+        if self.current == Some(idx) {
+            return;
+        }
+        if let Some(epoch_sec) = epoch_sec {
+            if self.transitions.last() == Some(&epoch_sec) {
+                self.transitions.pop();
+                self.transition_types.pop();
+            }
+            self.transitions.push(epoch_sec);
+            self.transition_types.push(idx);
+        }
+        self.current = Some(idx);
+    }
+}
+
+fn compile_zone(
+    name: &str,
+    lines: &[ZoneLine],
+    all_rules: &HashMap<String, Vec<Rule>>,
+    source_name: &str,
+) -> Result<ZoneRules, (usize, String)> {
+    let mut builder = Builder {
+        transitions: Vec::new(),
+        transition_types: Vec::new(),
+        types: Vec::new(),
+        current: None,
+    };
+    let mut start: Option<i64> = None;
+
+    for line in lines {
+        let std_offset_sec = line.std_offset_sec;
+        let until_local = match &line.until {
+            Some(u) => {
+                let date = day_of(u.year, u.month, &u.day).ok_or((line.line_no, "Invalid UNTIL".to_string()))?;
+                Some((local_sec(date, u.time_sec), u.suffix))
+            }
+            None => None,
+        };
+        let end_at = |save_sec: i32| until_local.map(|(local, suffix)| to_utc(local, suffix, std_offset_sec, save_sec));
+
+        let save_sec = match &line.rules {
+            ZoneLineRules::Standard | ZoneLineRules::Fixed(_) => {
+                let save_sec = if let ZoneLineRules::Fixed(s) = line.rules { s } else { 0 };
+                let offset_sec = std_offset_sec + save_sec;
+                if offset_sec.abs() > MAX_OFFSET_SEC {
+                    return Err((line.line_no, "Invalid offset: STDOFF plus SAVE is 24 hours or more".to_string()));
+                }
+                builder.push(
+                    start,
+                    offset_sec,
+                    save_sec != 0,
+                    format_abbreviation(&line.format, "", save_sec, offset_sec),
+                );
+                save_sec
+            }
+            ZoneLineRules::Named(rule_name) => {
+                let rules = all_rules
+                    .get(rule_name)
+                    .ok_or((line.line_no, format!("Unknown rule: {}", rule_name)))?;
+                if rules.iter().any(|r| (std_offset_sec + r.save_sec).abs() > MAX_OFFSET_SEC) {
+                    return Err((
+                        line.line_no,
+                        format!("Invalid offset: STDOFF plus SAVE of {} is 24 hours or more", rule_name),
+                    ));
+                }
+                apply_rules(&mut builder, line, rules, start, end_at)
+            }
+        };
+
+        if line.until.is_none() {
+            break;
+        }
+        start = end_at(save_sec);
+    }
+
+    let footer = match lines.last() {
+        Some(ZoneLine {
+            rules: ZoneLineRules::Named(rule_name),
+            std_offset_sec,
+            format,
+            until: None,
+            ..
+        }) => make_footer(&all_rules[rule_name], *std_offset_sec, format),
+        _ => None,
+    };
+
+    Ok(ZoneRules {
+        name: name.to_string(),
+        source: source_name.to_string(),
+        transitions: builder.transitions,
+        transition_types: builder.transition_types,
+        types: builder.types,
+        footer,
+    })
+}
+
+/// Adds the transitions of the rules within [start, end) and returns the save in effect at the end.
+fn apply_rules(builder: &mut Builder, line: &ZoneLine, rules: &[Rule], start: Option<i64>, end_at: impl Fn(i32) -> Option<i64>) -> i32 {
+    let std_offset_sec = line.std_offset_sec;
+    let min_year = rules.iter().map(|r| r.from).min().unwrap_or(MAX_YEAR);
+    let max_year = match end_at(0) {
+        Some(end) => NaiveDateTime::from_timestamp_opt(end, 0)
+            .map_or(MAX_YEAR, |d| d.year() + 1)
+            .min(MAX_YEAR),
+        None => MAX_YEAR,
+    };
+
+    let mut events: Vec<(i64, &Rule)> = Vec::new();
+    for year in min_year..=max_year {
+        for rule in rules.iter().filter(|r| r.from <= year && year <= r.to) {
+            if let Some(date) = day_of(year, rule.month, &rule.day) {
+                events.push((local_sec(date, rule.at_sec), rule));
+            }
+        }
+    }
+    events.sort_by_key(|(local, _)| *local);
+
+    // Before the first rule applies, standard time is used with the letter of the first standard rule.
+    let mut save_sec = 0;
+    let mut letter = rules.iter().find(|r| r.save_sec == 0).map_or("", |r| r.letter.as_str());
+    let mut started = false;
+
+    for (local, rule) in events {
+        let epoch_sec = to_utc(local, rule.at_suffix, std_offset_sec, save_sec);
+        if end_at(save_sec).is_some_and(|end| epoch_sec >= end) {
+            break;
+        }
+        if start.is_some_and(|s| epoch_sec <= s) {
+            save_sec = rule.save_sec;
+            letter = &rule.letter;
+            continue;
+        }
+        if !started {
+            let offset_sec = std_offset_sec + save_sec;
+            builder.push(
+                start,
+                offset_sec,
+                save_sec != 0,
+                format_abbreviation(&line.format, letter, save_sec, offset_sec),
+            );
+            started = true;
+        }
+        save_sec = rule.save_sec;
+        letter = &rule.letter;
+        let offset_sec = std_offset_sec + save_sec;
+        builder.push(
+            Some(epoch_sec),
+            offset_sec,
+            save_sec != 0,
+            format_abbreviation(&line.format, letter, save_sec, offset_sec),
+        );
+    }
+
+    if !started {
+        let offset_sec = std_offset_sec + save_sec;
+        builder.push(
+            start,
+            offset_sec,
+            save_sec != 0,
+            format_abbreviation(&line.format, letter, save_sec, offset_sec),
+        );
+    }
+    save_sec
+}
+
+/// Makes a POSIX TZ rule for the times after MAX_YEAR when exactly two rules last forever.
+fn make_footer(rules: &[Rule], std_offset_sec: i32, format: &str) -> Option<PosixTz> {
+    let ongoing: Vec<&Rule> = rules.iter().filter(|r| r.to == i32::MAX).collect();
+    if ongoing.len() != 2 {
+        return None;
+    }
+    let dst = ongoing.iter().find(|r| r.save_sec != 0)?;
+    let std = ongoing.iter().find(|r| r.save_sec == 0)?;
+
+    let dst_offset_sec = std_offset_sec + dst.save_sec;
+    // POSIX times are the wall clock time before each transition
+    let wall_time = |rule: &Rule, save_before: i32| match rule.at_suffix {
+        TimeSuffix::Wall => rule.at_sec,
+        TimeSuffix::Standard => rule.at_sec + save_before,
+        TimeSuffix::Universal => rule.at_sec + std_offset_sec + save_before,
+    };
+
+    let std_abbreviation = format_abbreviation(format, &std.letter, 0, std_offset_sec);
+    let dst_abbreviation = format_abbreviation(format, &dst.letter, dst.save_sec, dst_offset_sec);
+    let start = to_rule_date(dst.month, &dst.day)?;
+    let end = to_rule_date(std.month, &std.day)?;
+    let start_time_sec = wall_time(dst, 0);
+    let end_time_sec = wall_time(std, dst.save_sec);
+
+    let tz_str = format!(
+        "<{}>{}<{}>{},{}/{},{}/{}",
+        std_abbreviation,
+        posix_time(-std_offset_sec),
+        dst_abbreviation,
+        posix_time(-dst_offset_sec),
+        posix_rule_date(&start),
+        posix_time(start_time_sec),
+        posix_rule_date(&end),
+        posix_time(end_time_sec)
+    );
+
+    Some(PosixTz {
+        tz_str,
+        std_abbreviation,
+        std_offset_sec,
+        dst: Some(DstRule {
+            abbreviation: dst_abbreviation,
+            offset_sec: dst_offset_sec,
+            start,
+            start_time_sec,
+            end,
+            end_time_sec,
+        }),
+    })
+}
+
+fn to_rule_date(month: u32, day: &DaySpec) -> Option<RuleDate> {
+    let weekday = |w: &Weekday| w.num_days_from_sunday();
+    match day {
+        DaySpec::Last(w) => Some(RuleDate::MonthWeekDay(month, 5, weekday(w))),
+        DaySpec::OnOrAfter(w, d) if (d - 1) % 7 == 0 && *d <= 22 => Some(RuleDate::MonthWeekDay(month, (d - 1) / 7 + 1, weekday(w))),
+        DaySpec::Fixed(d) => {
+            let date = NaiveDate::from_ymd_opt(2001, month, *d)?;
+            Some(RuleDate::Julian(date.ordinal()))
+        }
+        _ => None,
+    }
+}
+
+fn posix_rule_date(rule_date: &RuleDate) -> String {
+    match rule_date {
+        RuleDate::Julian(n) => format!("J{}", n),
+        RuleDate::ZeroBasedJulian(n) => format!("{}", n),
+        RuleDate::MonthWeekDay(m, w, d) => format!("M{}.{}.{}", m, w, d),
+    }
+}
+
+fn posix_time(sec: i32) -> String {
+    let sign = if sec < 0 { "-" } else { "" };
+    let sec = sec.abs();
+    format!("{}{}:{:02}:{:02}", sign, sec / 3600, sec % 3600 / 60, sec % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::Tz;
+
+    // From tzdata 2023b (northamerica)
+    const LOS_ANGELES: &str = "
+Rule	US	1918	1919	-	Mar	lastSun	2:00	1:00	D
+Rule	US	1918	1919	-	Oct	lastSun	2:00	0	S
+Rule	US	1942	only	-	Feb	9	2:00	1:00	W # War
+Rule	US	1945	only	-	Aug	14	23:00u	1:00	P # Peace
+Rule	US	1945	only	-	Sep	30	2:00	0	S
+Rule	US	1967	2006	-	Oct	lastSun	2:00	0	S
+Rule	US	1967	1973	-	Apr	lastSun	2:00	1:00	D
+Rule	US	1974	only	-	Jan	6	2:00	1:00	D
+Rule	US	1975	only	-	Feb	lastSun	2:00	1:00	D
+Rule	US	1976	1986	-	Apr	lastSun	2:00	1:00	D
+Rule	US	1987	2006	-	Apr	Sun>=1	2:00	1:00	D
+Rule	US	2007	max	-	Mar	Sun>=8	2:00	1:00	D
+Rule	US	2007	max	-	Nov	Sun>=1	2:00	0	S
+Rule	CA	1948	only	-	Mar	14	2:01	1:00	D
+Rule	CA	1949	only	-	Jan	 1	2:00	0	S
+Rule	CA	1950	1966	-	Apr	lastSun	1:00	1:00	D
+Rule	CA	1950	1961	-	Sep	lastSun	2:00	0	S
+Rule	CA	1962	1966	-	Oct	lastSun	2:00	0	S
+Zone America/Los_Angeles -7:52:58 -	LMT	1883 Nov 18 20:00u
+			-8:00	US	P%sT	1946
+			-8:00	CA	P%sT	1967
+			-8:00	US	P%sT
+Link America/Los_Angeles US/Pacific
+";
+
+    // From tzdata 2023b (europe)
+    const LONDON: &str = "
+Rule	EU	1977	1980	-	Apr	Sun>=1	 1:00u	1:00	S
+Rule	EU	1977	only	-	Sep	lastSun	 1:00u	0	-
+Rule	EU	1978	only	-	Oct	 1	 1:00u	0	-
+Rule	EU	1979	1995	-	Sep	lastSun	 1:00u	0	-
+Rule	EU	1981	max	-	Mar	lastSun	 1:00u	1:00	S
+Rule	EU	1996	max	-	Oct	lastSun	 1:00u	0	-
+Zone	Europe/London	0:00	-	GMT	1996
+			 0:00	EU	GMT/BST
+";
+
+    fn assert_same_as_chrono_tz(rules: &ZoneRules, tz: Tz, start_year: i32, end_year: i32) {
+        let start = chrono::Utc.with_ymd_and_hms(start_year, 1, 1, 0, 0, 0).unwrap().timestamp();
+        let end = chrono::Utc.with_ymd_and_hms(end_year, 1, 1, 0, 0, 0).unwrap().timestamp();
+        for epoch_sec in (start..end).step_by(3600 * 7) {
+            let expected = super::super::date::offset_info_with_tz(epoch_sec, tz);
+            let actual = rules.offset_info(epoch_sec);
+            assert_eq!(expected.offset_sec, actual.offset_sec, "{} {}", tz.name(), epoch_sec);
+            assert_eq!(expected.abbreviation, actual.abbreviation, "{} {}", tz.name(), epoch_sec);
+        }
+    }
+
+    #[test]
+    fn test_compile_los_angeles() {
+        let zones = compile(LOS_ANGELES, "test").unwrap();
+        assert_eq!(2, zones.len());
+        assert_eq!("America/Los_Angeles", zones[0].name);
+        assert_eq!("US/Pacific", zones[1].name);
+        // chrono-tz 0.8 puts the LMT -> PST change of 1883 at the wrong instant, so start after it
+        assert_same_as_chrono_tz(&zones[0], Tz::America__Los_Angeles, 1900, 2040);
+
+        // After the expanded years, the footer is used
+        let footer = zones[0].footer.as_ref().unwrap();
+        assert_eq!("<PST>8:00:00<PDT>7:00:00,M3.2.0/2:00:00,M11.1.0/2:00:00", footer.tz_str);
+        // 2101-07-01T00:00:00Z
+        assert_eq!("PDT", zones[0].offset_info(4149619200).abbreviation);
+        // 2101-12-01T00:00:00Z
+        assert_eq!("PST", zones[0].offset_info(4162838400).abbreviation);
+    }
+
+    #[test]
+    fn test_compile_london() {
+        let zones = compile(LONDON, "test").unwrap();
+        assert_same_as_chrono_tz(&zones[0], Tz::Europe__London, 1996, 2099);
+
+        let footer = zones[0].footer.as_ref().unwrap();
+        assert_eq!("<GMT>0:00:00<BST>-1:00:00,M3.5.0/1:00:00,M10.5.0/2:00:00", footer.tz_str);
+        // 2101-07-01T00:00:00Z
+        assert_eq!(3600, zones[0].offset_info(4149619200).offset_sec);
+    }
+
+    #[test]
+    fn test_compile_what_if() {
+        // The EU stops DST after the summer of 2024 and stays on summer time
+        let text = "
+Rule	EU	1981	max	-	Mar	lastSun	 1:00u	1:00	S
+Rule	EU	1996	max	-	Oct	lastSun	 1:00u	0	-
+Zone	Europe/London	0:00	EU	GMT/BST	2024 Oct 27 1:00u
+			1:00	-	+01
+";
+        let zones = compile(text, "test").unwrap();
+        let london = &zones[0];
+        // 2024-10-27T00:59:59Z
+        assert_eq!("BST", london.offset_info(1729990799).abbreviation);
+        assert_eq!(3600, london.offset_info(1729990800).offset_sec);
+        assert_eq!("+01", london.offset_info(1729990800).abbreviation);
+        // 2030-01-01
+        assert_eq!(3600, london.offset_info(1893456000).offset_sec);
+        assert!(london.footer.is_none());
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        let text = "
+Rule	XX	1981	max	-	Mar	lastSun	 1:00u	1:00
+Zone	Test/A	0:00	YY	%s
+Zone	Test/B	0:00	-	GMT	2024 Foo
+Link	Test/C	Test/D
+";
+        let errors = compile(text, "custom.zi").err().unwrap();
+        assert_eq!(
+            vec!["custom.zi:2: Rule line must have 10 fields", "custom.zi:4: Invalid month: Foo"],
+            errors
+        );
+
+        let text = "
+Zone	Test/A	0:00	YY	%s
+Link	Test/C	Test/D
+";
+        let errors = compile(text, "custom.zi").err().unwrap();
+        assert_eq!(vec!["custom.zi:2: Unknown rule: YY", "custom.zi:3: Unknown zone: Test/C"], errors);
+
+        // Offsets of a day or more and years far away are rejected before anything is expanded
+        let text = "
+Zone	Test/A	99999999:00	-	X
+Zone	Test/B	25:00	-	X
+Zone	Test/C	0:00	-	X	-2000000000
+Rule	XX	-2000000000	max	-	Mar	lastSun	1:00	1:00	S
+Rule	XX	2000	max	-	Mar	lastSun	1:00	24:00	S
+";
+        let errors = compile(text, "custom.zi").err().unwrap();
+        assert_eq!(
+            vec![
+                "custom.zi:2: Invalid time: 99999999:00",
+                "custom.zi:3: Invalid offset: 25:00",
+                "custom.zi:4: Invalid year: -2000000000",
+                "custom.zi:5: Invalid year: -2000000000",
+                "custom.zi:6: Invalid offset: 24:00",
+            ],
+            errors
+        );
+
+        let text = "
+Rule	XX	2000	max	-	Mar	lastSun	1:00	2:00	S
+Zone	Test/A	23:00	XX	X%s
+Zone	Test/B	-23:00	-2:00	X
+";
+        let errors = compile(text, "custom.zi").err().unwrap();
+        assert_eq!(
+            vec![
+                "custom.zi:3: Invalid offset: STDOFF plus SAVE of XX is 24 hours or more",
+                "custom.zi:4: Invalid offset: STDOFF plus SAVE is 24 hours or more",
+            ],
+            errors
+        );
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(0, parse_time("-").unwrap().0);
+        assert_eq!(7200, parse_time("2").unwrap().0);
+        assert_eq!(-(7 * 3600 + 52 * 60 + 58), parse_time("-7:52:58").unwrap().0);
+        assert!(parse_time("23:00u").unwrap().1 == TimeSuffix::Universal);
+        assert!(parse_time("2:00s").unwrap().1 == TimeSuffix::Standard);
+        assert!(parse_time("x").is_err());
+        assert!(parse_time("99999999:00").is_err());
+        assert!(parse_time("596523:14:08").is_err());
+        assert_eq!(i32::MAX, parse_time("596523:14:07").unwrap().0);
+        assert_eq!(Ok(-(23 * 3600 + 59 * 60 + 59)), parse_offset("-23:59:59"));
+        assert!(parse_offset("24").is_err());
+    }
+
+    #[test]
+    fn test_parse_month_and_weekday() {
+        assert_eq!(3, parse_month("Mar").unwrap());
+        assert_eq!(3, parse_month("march").unwrap());
+        assert!(parse_month("M").is_err());
+        assert!(parse_month("Mär").is_err());
+        assert!(parse_month("日本").is_err());
+        assert!(parse_weekday("Sun").unwrap() == Weekday::Sun);
+        assert!(parse_weekday("Sunday").unwrap() == Weekday::Sun);
+        assert!(parse_weekday("月曜").is_err());
+    }
+
+    #[test]
+    fn test_parse_day() {
+        assert!(matches!(parse_day("15"), Ok(DaySpec::Fixed(15))));
+        assert!(matches!(parse_day("lastSun"), Ok(DaySpec::Last(Weekday::Sun))));
+        assert!(matches!(parse_day("Sun>=8"), Ok(DaySpec::OnOrAfter(Weekday::Sun, 8))));
+        assert!(matches!(parse_day("Sun<=25"), Ok(DaySpec::OnOrBefore(Weekday::Sun, 25))));
+        for s in ["0", "32", "Sun>=0", "Sun<=0", "Sun>=32", "Sun>=x"] {
+            assert!(parse_day(s).is_err(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use super::date;
+use super::types::Zone;
+use chrono::{NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// chrono-tz zones are sampled a day apart for their spans. Changes may be hours apart (e.g. Europe/Moscow in 1919),
+/// but no zone changes and changes back within a day, which two samples with the same offset could not tell.
+const TZ_SAMPLE_SEC: i64 = 86400;
+/// Samples taken on each side of an epoch, which bounds the cost of an epoch far from the previous one
+const TZ_SAMPLES: i64 = 7;
+
+impl Zone {
+    pub fn offset_info(&self, epoch_sec: i64) -> date::OffsetInfo {
+        match self {
+            Zone::Offset(offset_sec) => date::OffsetInfo {
+                offset_sec: *offset_sec,
+                dst_sec: 0,
+                abbreviation: date::to_offset_str(*offset_sec),
+            },
+            Zone::Tz(tz) => date::offset_info_with_tz(epoch_sec, *tz),
+            Zone::Posix(posix_tz) => posix_tz.offset_info(epoch_sec),
+            Zone::Rules(rules) => rules.offset_info(epoch_sec),
+        }
+    }
+
+    pub fn offset_sec(&self, epoch_sec: i64) -> i32 {
+        match self {
+            Zone::Offset(offset_sec) => *offset_sec,
+            Zone::Tz(tz) => tz
+                .offset_from_utc_datetime(&Utc.timestamp_opt(epoch_sec, 0).unwrap().naive_utc())
+                .fix()
+                .local_minus_utc(),
+            _ => self.offset_info(epoch_sec).offset_sec,
+        }
+    }
+
+    /// Ambiguous times resolve to the earlier instant; times in a gap are shifted by the offset before the gap.
+    pub fn to_epoch(&self, date_time: NaiveDateTime) -> i64 {
+        match self {
+            Zone::Offset(offset_sec) => date_time.timestamp() - *offset_sec as i64,
+            _ => date::local_to_epoch(date_time, |epoch_sec| self.offset_sec(epoch_sec)),
+        }
+    }
+
+    /// The span around `epoch_sec` in which the offset stays the same.
+    fn offset_span(&self, epoch_sec: i64) -> (i64, i64) {
+        match self {
+            Zone::Offset(_) => (i64::MIN, i64::MAX),
+            Zone::Tz(tz) => tz_offset_span(*tz, epoch_sec),
+            Zone::Posix(posix_tz) => posix_tz.offset_span(epoch_sec),
+            Zone::Rules(rules) => rules.offset_span(epoch_sec),
+        }
+    }
+}
+
+/// chrono-tz does not expose its transitions, so they are found by sampling up to `TZ_SAMPLES` days on each side
+/// and searching between the samples that differ. The span may be shorter than the real one, and is never longer
+/// as long as the offset does not change and change back between two samples.
+fn tz_offset_span(tz: Tz, epoch_sec: i64) -> (i64, i64) {
+    let offset_info = date::offset_info_with_tz(epoch_sec, tz);
+    let is_same = |epoch_sec: i64| date::offset_info_with_tz(epoch_sec, tz) == offset_info;
+    // Samples beyond the dates chrono can hold are not taken
+    let in_range = |epoch_sec: i64| NaiveDateTime::from_timestamp_opt(epoch_sec, 0).is_some();
+
+    let mut last = epoch_sec;
+    for _ in 0..TZ_SAMPLES {
+        let sample = last + TZ_SAMPLE_SEC;
+        if !in_range(sample) {
+            break;
+        }
+        if !is_same(sample) {
+            last = last_same(&is_same, last, sample);
+            break;
+        }
+        last = sample;
+    }
+    let mut first = epoch_sec;
+    for _ in 0..TZ_SAMPLES {
+        let sample = first - TZ_SAMPLE_SEC;
+        if !in_range(sample) {
+            break;
+        }
+        if !is_same(sample) {
+            first = last_same(&is_same, first, sample);
+            break;
+        }
+        first = sample;
+    }
+    (first, last + 1)
+}
+
+/// Binary search from `same`, which has the offset, toward `other`, which does not, for the last epoch with the offset.
+fn last_same(is_same: &impl Fn(i64) -> bool, mut same: i64, mut other: i64) -> i64 {
+    while (other - same).abs() > 1 {
+        let mid = same + (other - same) / 2;
+        if is_same(mid) {
+            same = mid;
+        } else {
+            other = mid;
+        }
+    }
+    same
+}
+
+/// Offsets of one column. Rows mostly come in order, so the offset is kept with the span in which it holds
+/// and looked up again only when an epoch falls outside it.
+pub struct OffsetCache<'a> {
+    zone: &'a Zone,
+    span: Option<(i64, i64, date::OffsetInfo)>,
+}
+
+impl<'a> OffsetCache<'a> {
+    pub fn new(zone: &'a Zone) -> OffsetCache<'a> {
+        OffsetCache { zone, span: None }
+    }
+
+    pub fn offset_info(&mut self, epoch_sec: i64) -> &date::OffsetInfo {
+        let is_cached = matches!(&self.span, Some((start, end, _)) if *start <= epoch_sec && epoch_sec < *end);
+        if !is_cached {
+            let (start, end) = self.zone.offset_span(epoch_sec);
+            self.span = Some((start, end, self.zone.offset_info(epoch_sec)));
+        }
+        &self.span.as_ref().unwrap().2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::posix::PosixTz;
+    use super::super::tzif;
+    use super::*;
+    use chrono_tz::{Tz, TZ_VARIANTS};
+    use std::time::Instant;
+
+    #[test]
+    fn test_offset_cache() {
+        let zones = [
+            Zone::Offset(-9000),
+            Zone::Tz(Tz::America__Santiago),
+            Zone::Posix(PosixTz::parse("EST5EDT,M3.2.0,M11.1.0").unwrap()),
+            Zone::Posix(PosixTz::parse("<-04>4<-03>,M9.1.6/24,M4.1.6/24").unwrap()),
+        ];
+        for zone in &zones {
+            let mut cache = OffsetCache::new(zone);
+            // Every 17 minutes for two years, around both DST changes and the new year
+            for epoch_sec in (1640995200..1704067200).step_by(1020) {
+                let expected = zone.offset_info(epoch_sec);
+                let actual = cache.offset_info(epoch_sec);
+                assert_eq!(expected.offset_sec, actual.offset_sec, "{}", epoch_sec);
+                assert_eq!(expected.abbreviation, actual.abbreviation);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tz_offset_span() {
+        let zone = Zone::Tz(Tz::America__New_York);
+        // DST started at 2022-03-13T07:00:00Z and ended at 2022-11-06T06:00:00Z
+        assert_eq!((1647154799 - TZ_SAMPLES * 86400, 1647154800), zone.offset_span(1647154799));
+        assert_eq!((1647154800, 1647154800 + 86400 * 8 + 1), zone.offset_span(1647154800 + 86400));
+        assert_eq!((1667714400 - 86400 * 9, 1667714400), zone.offset_span(1667714400 - 86400 * 2));
+        // The same offset from DST to standard time: British Standard Time began on 1968-10-27
+        let zone = Zone::Tz(Tz::Europe__London);
+        let (start, end) = zone.offset_span(-37242000 - 3600);
+        assert_eq!(-37242000, end);
+        assert_eq!(zone.offset_sec(end - 1), zone.offset_sec(end));
+        assert!(start < end - 3600);
+        // The ends of the dates chrono can hold
+        let zone = Zone::Tz(Tz::Asia__Tokyo);
+        for epoch_sec in [8210298326399, -8210298326399] {
+            let (start, end) = zone.offset_span(epoch_sec);
+            assert!(start <= epoch_sec && epoch_sec < end);
+        }
+    }
+
+    #[test]
+    fn test_tz_offset_span_at_transitions() {
+        // chrono-tz does not list its transitions, so those of the system zoneinfo are used where it has the zone.
+        // Both come from tzdata; where they differ, the cache is still compared with chrono-tz.
+        let dir = tzif::system_dir();
+        let mut checked = 0;
+        for tz in TZ_VARIANTS.iter() {
+            let Ok(rules) = tzif::load_from_dir(&dir, tz.name()) else {
+                continue;
+            };
+            let zone = Zone::Tz(*tz);
+            let mut cache = OffsetCache::new(&zone);
+            for transition in rules
+                .transitions
+                .iter()
+                .filter(|t| NaiveDateTime::from_timestamp_opt(**t - 1, 0).is_some())
+            {
+                for epoch_sec in [transition - 1, *transition] {
+                    let expected = tz.offset_from_utc_datetime(&Utc.timestamp_opt(epoch_sec, 0).unwrap().naive_utc());
+                    let actual = cache.offset_info(epoch_sec);
+                    assert_eq!(expected.fix().local_minus_utc(), actual.offset_sec, "{} {}", tz.name(), epoch_sec);
+                    assert_eq!(date::offset_info_with_tz(epoch_sec, *tz), *actual, "{} {}", tz.name(), epoch_sec);
+                    checked += 1;
+                }
+            }
+        }
+        assert!(checked > 0 || !dir.exists(), "no transitions in {}", dir.display());
+    }
+
+    #[test]
+    fn test_offset_sec() {
+        assert_eq!(32400, Zone::Tz(Tz::Asia__Tokyo).offset_sec(0));
+        assert_eq!(-14400, Zone::Tz(Tz::America__New_York).offset_sec(1650197389));
+        assert_eq!(-18000, Zone::Tz(Tz::America__New_York).offset_sec(1640971840));
+        // LMT
+        assert_eq!(33539, Zone::Tz(Tz::Asia__Tokyo).offset_sec(-2600000000));
+    }
+
+    #[test]
+    fn test_to_epoch() {
+        let zone = Zone::Tz(Tz::America__Los_Angeles);
+        let date_time = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M").unwrap();
+        // In the gap; shifted by the offset before it
+        assert_eq!(1647167400, zone.to_epoch(date_time("2022-03-13T02:30")));
+        // Ambiguous; the earlier instant
+        assert_eq!(1667723400, zone.to_epoch(date_time("2022-11-06T01:30")));
+        assert_eq!(1667772000, Zone::Offset(-3600).to_epoch(date_time("2022-11-06T21:00")));
+    }
+
+    /// cargo test --release -- --ignored --nocapture bench_
+    #[test]
+    #[ignore]
+    fn bench_offsets() {
+        let epochs: Vec<i64> = (0..1_000_000).map(|i| 1640995200 + i * 60).collect();
+        let zones = [
+            ("Asia/Tokyo", Zone::Tz(Tz::Asia__Tokyo)),
+            ("America/New_York", Zone::Tz(Tz::America__New_York)),
+            (
+                "EST5EDT,M3.2.0,M11.1.0",
+                Zone::Posix(PosixTz::parse("EST5EDT,M3.2.0,M11.1.0").unwrap()),
+            ),
+        ];
+        for (name, zone) in &zones {
+            let time = |offset_at: &mut dyn FnMut(i64) -> i32| {
+                let started = Instant::now();
+                let sum: i64 = epochs.iter().map(|epoch_sec| offset_at(*epoch_sec) as i64).sum();
+                (sum, started.elapsed())
+            };
+            // What every cell did before zones were resolved
+            if let Zone::Tz(_) = zone {
+                let (_, parsed) = time(&mut |epoch_sec| date::offset_info_with_tz(epoch_sec, name.parse().unwrap()).offset_sec);
+                println!("{}: {:?} parsing the name for each row", name, parsed);
+            }
+            let (sum, uncached) = time(&mut |epoch_sec| zone.offset_info(epoch_sec).offset_sec);
+            let mut cache = OffsetCache::new(zone);
+            let (cached_sum, cached) = time(&mut |epoch_sec| cache.offset_info(epoch_sec).offset_sec);
+
+            assert_eq!(sum, cached_sum);
+            println!("{}: {:?} -> {:?} cached for {} rows", name, uncached, cached, epochs.len());
+        }
+    }
+}
@@ -3,11 +3,27 @@ use std::process::exit;
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
+    if let Some(result) = epo::command::run(&args) {
+        match result {
+            Ok(code) => exit(code),
+            Err(errors) => {
+                match epo::arg::find_json_mode(&args) {
+                    Some(mode) => epo::json::print_errors(&errors, &mode),
+                    None => print_errors(&errors),
+                }
+                exit(1);
+            }
+        }
+    }
+
     let parse_settings = epo::arg::get_parse_settings();
     match epo::arg::parse_arguments(&args, &parse_settings) {
         Ok(app_settings) => epo::app::run(&app_settings),
         Err(errors) => {
-            print_errors(&errors);
+            match epo::arg::find_json_mode(&args) {
+                Some(mode) => epo::json::print_errors(&errors, &mode),
+                None => print_errors(&errors),
+            }
             exit(1);
         }
     }
@@ -13,8 +13,10 @@ def main(epo_path: str, repeat: int, timezone_exclusions: set[str]):
     ok_num = 0
     max_epoch = int(datetime.now().timestamp())
 
+    # Links (e.g. "US/Pacific") are shown as the zone they point to, so only zones are sampled.
+    zones = list_zones(epo_path)
     timezones = [t for t in available_timezones(
-    ) if t not in timezone_exclusions]
+    ) if t in zones and t not in timezone_exclusions]
     runner = make_test_runner(epo_path, timezones, max_epoch)
 
     for i in range(1, repeat+1):
@@ -35,11 +37,28 @@ def main(epo_path: str, repeat: int, timezone_exclusions: set[str]):
 def make_test_runner(epo_path: str, available_timezones: list[str], max_epoch: int) -> Callable[[int, int], bool]:
     def test_runner(epoch_num: int, timezone_num: int):
         epochs = [random.randint(315532800, max_epoch) for _ in range(epoch_num)]
-        timezones = random.sample(available_timezones, timezone_num)
+        timezones = unique_zones(random.sample(available_timezones, timezone_num))
         return run_and_compare(epo_path, epochs, timezones)
     return test_runner
 
 
+def list_zones(epo_path: str) -> set[str]:
+    r = subprocess.run([epo_path, "tz", "list", "-p"], capture_output=True, text=True, check=True)
+    return {line.split(" ")[0] for line in r.stdout.splitlines()}
+
+
+def unique_zones(timezones: list[str]) -> list[str]:
+    # epo shows one column per zone, and fixed "Etc/*" zones with the same offset (e.g. "Etc/UTC" and "Etc/GMT") are the same
+    result = []
+    seen = set()
+    for tz in timezones:
+        key = ZoneInfo(tz).utcoffset(None) if tz.startswith("Etc/") else tz
+        if key not in seen:
+            seen.add(key)
+            result.append(tz)
+    return result
+
+
 def run_and_compare(epo_path: str, epochs: list[int], timezones: list[str]) -> bool:
     actual = run_epo(epo_path, epochs, timezones)
     expected = generate_cross_check_data(epochs, timezones)
@@ -77,6 +96,28 @@ def epoch_to_datestr(epoch: int, tzname: str) -> str:
     return dt.strftime('%Y-%m-%dT%H:%M:%S%z')
 
 
+def tzdata_versions(epo_path: str) -> tuple[str, str | None]:
+    # The first lines of "epo doctor -p" are "Embedded tzdata: 2023b" and "System tzdata: 2025b (/usr/share/zoneinfo)"
+    r = subprocess.run([epo_path, "doctor", "-p", "--from", "2023", "--to", "2024"], capture_output=True, text=True)
+    versions = {}
+    for line in r.stdout.splitlines():
+        key, _, value = line.partition(": ")
+        if key in ("Embedded tzdata", "System tzdata"):
+            versions[key] = value.split(" ")[0]
+    system = versions.get("System tzdata")
+    return versions.get("Embedded tzdata", ""), system if system and system != "unknown" else None
+
+
+def tzdata_exclusions(config: dict[str, Any], embedded: str, system: str | None) -> set[str]:
+    # Zones whose data changed after the embedded release differ from the system tzdata that ZoneInfo reads
+    result = set()
+    for change in config["tzdataChanges"]:
+        if change["release"] > embedded and (system is None or change["release"] <= system):
+            print(f"Skipping {', '.join(change['zones'])}: tzdata {change['release']}: {change['reason']}")
+            result.update(change["zones"])
+    return result
+
+
 def load_config(config_path) -> dict[str, Any]:
     config = None
     with open(config_path) as file:
@@ -94,4 +135,10 @@ if __name__ == "__main__":
     parser.add_argument("--repeat", default=1000, type=int)
     args = parser.parse_args()
 
-    main(args.epo, args.repeat, set(config["timezoneExclusions"]))
+    embedded, system = tzdata_versions(args.epo)
+    if embedded != config["embeddedTzdata"]:
+        print(f"epo embeds tzdata {embedded or 'unknown'} but arbitrary_tests.json is for {config['embeddedTzdata']}; "
+              "review tzdataChanges and update embeddedTzdata", file=sys.stderr)
+        sys.exit(1)
+
+    main(args.epo, args.repeat, set(config["timezoneExclusions"]) | tzdata_exclusions(config, embedded, system))